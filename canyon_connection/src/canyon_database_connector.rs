@@ -49,6 +49,9 @@ pub struct PostgreSqlConnection {
 #[cfg(feature = "mssql")]
 pub struct SqlServerConnection {
     pub client: &'static mut tiberius::Client<TcpStream>,
+    /// Marks that a transaction was left open on this connection, and it must
+    /// be rolled back before sending any other statement through it
+    pub pending_rollback: bool,
 }
 
 /// A connection with a `Mysql` database
@@ -168,6 +171,7 @@ impl DatabaseConnection {
                     client: Box::leak(Box::new(
                         client.expect("A failure happened connecting to the database"),
                     )),
+                    pending_rollback: false,
                 }))
             }
             #[cfg(feature = "mysql")]
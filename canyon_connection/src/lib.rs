@@ -41,6 +41,9 @@ lazy_static! {
         Mutex::new(IndexMap::new());
 }
 
+/// The locked [`CACHED_DATABASE_CONN`], that no other query can use until it's dropped
+pub type CachedConnectionsGuard = MutexGuard<'static, IndexMap<&'static str, DatabaseConnection>>;
+
 fn find_canyon_config_file() -> PathBuf {
     for e in WalkDir::new(".")
         .max_depth(2)
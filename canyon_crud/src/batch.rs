@@ -0,0 +1,126 @@
+//! Contains the helpers of the operations that works over several
+//! primary keys at once, like `find_by_pks` or `delete_by_pks`
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use canyon_connection::{
+    canyon_database_connector::DatabaseType, get_database_config, DATASOURCES,
+};
+
+use crate::bounds::QueryParameter;
+
+/// The entities found by a `find_by_pks` operation
+#[derive(Debug)]
+pub struct FoundByPks<T, K> {
+    /// The entities found, in the same order that their primary keys were requested
+    pub found: Vec<T>,
+    /// The requested primary keys that don't match any entity, in the requested order
+    pub missing: Vec<K>,
+}
+
+impl<T, K> FoundByPks<T, K> {
+    /// Returns true if every requested primary key matched some entity
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Returns the maximum number of values that can be sent on the `IN` list of
+/// a single statement, given the limit of bound parameters of every database
+///
+/// * `datasource_name` - The datasource where the statements will be executed
+pub fn max_params_per_statement(datasource_name: &str) -> usize {
+    match DatabaseType::from(&get_database_config(datasource_name, &DATASOURCES).auth) {
+        #[cfg(feature = "postgres")]
+        DatabaseType::PostgreSql => 65_535,
+        // The real limit is 2100, but some of them may be used by the driver itself
+        #[cfg(feature = "mssql")]
+        DatabaseType::SqlServer => 2_000,
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => 65_535,
+    }
+}
+
+/// Splits the `pks` in as many statements as needed to not exceed the limit of
+/// parameters of the database, appending to the `stmt` an `IN` list with the
+/// placeholders of every chunk, like `{stmt} IN ($1, $2, ...)`
+///
+/// * `stmt` - The statement, that must end with the column of the primary key
+/// * `pks` - The primary keys, without repeated ones
+/// * `datasource_name` - The datasource where the statements will be executed
+pub fn in_chunks<'a, K: QueryParameter<'a>>(
+    stmt: &str,
+    pks: &'a [K],
+    datasource_name: &str,
+) -> Vec<(String, Vec<&'a dyn QueryParameter<'a>>)> {
+    pks.chunks(max_params_per_statement(datasource_name))
+        .map(|chunk| {
+            let placeholders = (1..=chunk.len())
+                .map(|idx| format!("${idx}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let params = chunk
+                .iter()
+                .map(|pk| pk as &dyn QueryParameter<'a>)
+                .collect();
+
+            (format!("{stmt} IN ({placeholders})"), params)
+        })
+        .collect()
+}
+
+/// Returns the primary keys without the repeated ones, keeping the order
+/// of their first appearance
+pub fn unique_pks<K: Eq + Hash + Clone>(pks: &[K]) -> Vec<K> {
+    let mut seen = HashSet::with_capacity(pks.len());
+    pks.iter().filter(|pk| seen.insert(*pk)).cloned().collect()
+}
+
+/// Sorts the loaded entities in the order of the requested primary keys,
+/// reporting the ones that weren't found
+///
+/// * `pks` - The requested primary keys, without repeated ones
+/// * `loaded` - The entities retrieved from the database, in any order
+/// * `pk_of` - Returns the primary key of an entity
+pub fn sort_by_pks<T, K, F>(pks: &[K], loaded: Vec<T>, pk_of: F) -> FoundByPks<T, K>
+where
+    K: Eq + Hash + Clone,
+    F: Fn(&T) -> K,
+{
+    let mut loaded: HashMap<K, T> = loaded
+        .into_iter()
+        .map(|instance| (pk_of(&instance), instance))
+        .collect();
+
+    let mut found = Vec::with_capacity(loaded.len());
+    let mut missing = Vec::new();
+    for pk in pks {
+        match loaded.remove(pk) {
+            Some(instance) => found.push(instance),
+            None => missing.push(pk.clone()),
+        }
+    }
+
+    FoundByPks { found, missing }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_by_pks() {
+        let pks = unique_pks(&[3, 1, 3, 7, 2]);
+        assert_eq!(pks, vec![3, 1, 7, 2]);
+
+        let loaded = vec![(1, "one"), (2, "two"), (3, "three")];
+        let result = sort_by_pks(&pks, loaded, |(pk, _)| *pk);
+
+        assert_eq!(result.found, vec![(3, "three"), (1, "one"), (2, "two")]);
+        assert_eq!(result.missing, vec![7]);
+        assert!(!result.is_complete());
+    }
+}
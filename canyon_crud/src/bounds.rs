@@ -110,24 +110,48 @@ impl Row for mysql_async::Row {
 
 /// Generic abstraction for hold a Column type that will be one of the Column
 /// types present in the dependent crates
-// #[derive(Copy, Clone)]
 pub struct Column<'a> {
     name: Cow<'a, str>,
     type_: ColumnType,
+    data_type: DataType,
 }
 impl<'a> Column<'a> {
     pub fn name(&self) -> &str {
         &self.name
     }
+    /// The type of the column as reported by the database client
     pub fn column_type(&self) -> &ColumnType {
         &self.type_
     }
-    // pub fn type_(&'a self) -> &'_ dyn Type {
-    //     match (*self).type_ {
-    //         #[cfg(feature = "postgres")] ColumnType::Postgres(v) => v as &'a dyn Type,
-    //         #[cfg(feature = "mssql")] ColumnType::SqlServer(v) => v as &'a dyn Type,
-    //     }
-    // }
+    /// The type of the column, normalized across the supported databases
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    #[cfg(feature = "postgres")]
+    pub(crate) fn from_postgres(column: &'a tokio_postgres::Column) -> Self {
+        Self {
+            name: Cow::from(column.name()),
+            type_: ColumnType::Postgres(column.type_().to_owned()),
+            data_type: DataType::from_postgres(column.type_()),
+        }
+    }
+    #[cfg(feature = "mssql")]
+    pub(crate) fn from_sqlserver(column: &'a tiberius::Column) -> Self {
+        Self {
+            name: Cow::from(column.name()),
+            type_: ColumnType::SqlServer(column.column_type()),
+            data_type: DataType::from_sqlserver(column.column_type()),
+        }
+    }
+    #[cfg(feature = "mysql")]
+    pub(crate) fn from_mysql(column: &'a mysql_async::Column) -> Self {
+        Self {
+            name: column.name_str(),
+            type_: ColumnType::MySQL(column.column_type()),
+            data_type: DataType::from_mysql(column),
+        }
+    }
 }
 
 pub trait Type {
@@ -162,6 +186,128 @@ pub enum ColumnType {
     MySQL(mysql_async::consts::ColumnType),
 }
 
+/// The type of a column, normalized across the supported databases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataType {
+    Bool,
+    SmallInt,
+    Int,
+    BigInt,
+    Float,
+    Double,
+    Decimal,
+    Text,
+    Binary,
+    Date,
+    Time,
+    DateTime,
+    /// A date and time with a time zone or an offset
+    DateTimeTz,
+    Uuid,
+    Json,
+    /// Any other type, holding its name as reported by the database client
+    Other(String),
+}
+
+impl DataType {
+    #[cfg(feature = "postgres")]
+    fn from_postgres(ty: &tokio_postgres::types::Type) -> Self {
+        use tokio_postgres::types::{Kind, Type as TP_TYP};
+
+        match *ty {
+            TP_TYP::BOOL => Self::Bool,
+            TP_TYP::INT2 => Self::SmallInt,
+            TP_TYP::INT4 => Self::Int,
+            TP_TYP::INT8 => Self::BigInt,
+            TP_TYP::FLOAT4 => Self::Float,
+            TP_TYP::FLOAT8 => Self::Double,
+            TP_TYP::NUMERIC => Self::Decimal,
+            TP_TYP::TEXT | TP_TYP::VARCHAR | TP_TYP::BPCHAR | TP_TYP::NAME => Self::Text,
+            TP_TYP::BYTEA => Self::Binary,
+            TP_TYP::DATE => Self::Date,
+            TP_TYP::TIME => Self::Time,
+            TP_TYP::TIMESTAMP => Self::DateTime,
+            TP_TYP::TIMESTAMPTZ => Self::DateTimeTz,
+            TP_TYP::UUID => Self::Uuid,
+            TP_TYP::JSON | TP_TYP::JSONB => Self::Json,
+            // The native `ENUM` types of the enums that derives `CanyonEnum`
+            _ if matches!(ty.kind(), Kind::Enum(_)) => Self::Text,
+            _ => Self::Other(ty.name().to_owned()),
+        }
+    }
+
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(ty: tiberius::ColumnType) -> Self {
+        use tiberius::ColumnType as TIB_TY;
+
+        match ty {
+            TIB_TY::Bit | TIB_TY::Bitn => Self::Bool,
+            TIB_TY::Int1 | TIB_TY::Int2 => Self::SmallInt,
+            TIB_TY::Int4 => Self::Int,
+            // The size of the nullable integers is only known by their values
+            TIB_TY::Int8 | TIB_TY::Intn => Self::BigInt,
+            TIB_TY::Float4 => Self::Float,
+            TIB_TY::Float8 | TIB_TY::Floatn => Self::Double,
+            TIB_TY::Decimaln | TIB_TY::Numericn | TIB_TY::Money | TIB_TY::Money4 => Self::Decimal,
+            TIB_TY::BigVarChar
+            | TIB_TY::BigChar
+            | TIB_TY::NVarchar
+            | TIB_TY::NChar
+            | TIB_TY::Text
+            | TIB_TY::NText => Self::Text,
+            TIB_TY::BigVarBin | TIB_TY::BigBinary | TIB_TY::Image => Self::Binary,
+            TIB_TY::Daten => Self::Date,
+            TIB_TY::Timen => Self::Time,
+            TIB_TY::Datetime | TIB_TY::Datetime4 | TIB_TY::Datetimen | TIB_TY::Datetime2 => {
+                Self::DateTime
+            }
+            TIB_TY::DatetimeOffsetn => Self::DateTimeTz,
+            TIB_TY::Guid => Self::Uuid,
+            other => Self::Other(format!("{other:?}")),
+        }
+    }
+
+    #[cfg(feature = "mysql")]
+    fn from_mysql(column: &mysql_async::Column) -> Self {
+        use mysql_async::consts::ColumnType as MY_TY;
+
+        /// The character set that MySQL reports for the binary strings and blobs
+        const BINARY_CHARSET: u16 = 63;
+
+        match column.column_type() {
+            MY_TY::MYSQL_TYPE_TINY if column.column_length() == 1 => Self::Bool,
+            MY_TY::MYSQL_TYPE_TINY | MY_TY::MYSQL_TYPE_SHORT => Self::SmallInt,
+            MY_TY::MYSQL_TYPE_INT24 | MY_TY::MYSQL_TYPE_LONG => Self::Int,
+            MY_TY::MYSQL_TYPE_LONGLONG => Self::BigInt,
+            MY_TY::MYSQL_TYPE_FLOAT => Self::Float,
+            MY_TY::MYSQL_TYPE_DOUBLE => Self::Double,
+            MY_TY::MYSQL_TYPE_DECIMAL | MY_TY::MYSQL_TYPE_NEWDECIMAL => Self::Decimal,
+            MY_TY::MYSQL_TYPE_VARCHAR
+            | MY_TY::MYSQL_TYPE_VAR_STRING
+            | MY_TY::MYSQL_TYPE_STRING
+            | MY_TY::MYSQL_TYPE_TINY_BLOB
+            | MY_TY::MYSQL_TYPE_MEDIUM_BLOB
+            | MY_TY::MYSQL_TYPE_LONG_BLOB
+            | MY_TY::MYSQL_TYPE_BLOB => {
+                if column.character_set() == BINARY_CHARSET {
+                    Self::Binary
+                } else {
+                    Self::Text
+                }
+            }
+            MY_TY::MYSQL_TYPE_ENUM | MY_TY::MYSQL_TYPE_SET => Self::Text,
+            MY_TY::MYSQL_TYPE_DATE | MY_TY::MYSQL_TYPE_NEWDATE => Self::Date,
+            MY_TY::MYSQL_TYPE_TIME | MY_TY::MYSQL_TYPE_TIME2 => Self::Time,
+            MY_TY::MYSQL_TYPE_DATETIME
+            | MY_TY::MYSQL_TYPE_DATETIME2
+            | MY_TY::MYSQL_TYPE_TIMESTAMP
+            | MY_TY::MYSQL_TYPE_TIMESTAMP2 => Self::DateTime,
+            MY_TY::MYSQL_TYPE_JSON => Self::Json,
+            other => Self::Other(format!("{other:?}")),
+        }
+    }
+}
+
 pub trait RowOperations {
     #[cfg(feature = "postgres")]
     fn get_postgres<'a, Output>(&'a self, col_name: &'a str) -> Output
@@ -259,53 +405,26 @@ impl RowOperations for &dyn Row {
     }
 
     fn columns(&self) -> Vec<Column> {
-        let mut cols = vec![];
-
         #[cfg(feature = "postgres")]
         {
-            if self.as_any().is::<tokio_postgres::Row>() {
-                self.as_any()
-                    .downcast_ref::<tokio_postgres::Row>()
-                    .expect("Not a tokio postgres Row for column")
-                    .columns()
-                    .iter()
-                    .for_each(|c| {
-                        cols.push(Column {
-                            name: Cow::from(c.name()),
-                            type_: ColumnType::Postgres(c.type_().to_owned()),
-                        })
-                    })
+            if let Some(row) = self.as_any().downcast_ref::<tokio_postgres::Row>() {
+                return row.columns().iter().map(Column::from_postgres).collect();
             }
         }
         #[cfg(feature = "mssql")]
         {
-            if self.as_any().is::<tiberius::Row>() {
-                self.as_any()
-                    .downcast_ref::<tiberius::Row>()
-                    .expect("Not a Tiberius Row for column")
-                    .columns()
-                    .iter()
-                    .for_each(|c| {
-                        cols.push(Column {
-                            name: Cow::from(c.name()),
-                            type_: ColumnType::SqlServer(c.column_type()),
-                        })
-                    })
-            };
+            if let Some(row) = self.as_any().downcast_ref::<tiberius::Row>() {
+                return row.columns().iter().map(Column::from_sqlserver).collect();
+            }
         }
         #[cfg(feature = "mysql")]
         {
-            if let Some(mysql_row) = self.as_any().downcast_ref::<mysql_async::Row>() {
-                mysql_row.columns_ref().iter().for_each(|c| {
-                    cols.push(Column {
-                        name: c.name_str(),
-                        type_: ColumnType::MySQL(c.column_type()),
-                    })
-                })
+            if let Some(row) = self.as_any().downcast_ref::<mysql_async::Row>() {
+                return row.columns_ref().iter().map(Column::from_mysql).collect();
             }
         }
 
-        cols
+        vec![]
     }
 }
 
@@ -873,3 +992,157 @@ impl<'a> QueryParameter<'a> for Option<DateTime<Utc>> {
         todo!()
     }
 }
+
+impl<'a> QueryParameter<'a> for Vec<u8> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(Some(Cow::Borrowed(self.as_slice())))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+impl<'a> QueryParameter<'a> for Option<Vec<u8>> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(self.as_deref().map(Cow::Borrowed))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+impl<'a> QueryParameter<'a> for &'_ [u8] {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(Some(Cow::Borrowed(*self)))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<'a> QueryParameter<'a> for uuid::Uuid {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Guid(Some(*self))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+#[cfg(feature = "uuid")]
+impl<'a> QueryParameter<'a> for Option<uuid::Uuid> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Guid(*self)
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'a> QueryParameter<'a> for rust_decimal::Decimal {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        tiberius::ToSql::to_sql(self)
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+#[cfg(feature = "decimal")]
+impl<'a> QueryParameter<'a> for Option<rust_decimal::Decimal> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        tiberius::ToSql::to_sql(self)
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+
+/// SQL Server doesn't have a native `JSON` type, so the values are sent as text
+#[cfg(feature = "json")]
+impl<'a> QueryParameter<'a> for serde_json::Value {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(Some(Cow::Owned(self.to_string())))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+#[cfg(feature = "json")]
+impl<'a> QueryParameter<'a> for Option<serde_json::Value> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(self.as_ref().map(|json| Cow::Owned(json.to_string())))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self
+    }
+}
+
+/// Lets the trait objects to be used where some concrete parameter type is expected,
+/// like on the `IN` lists built by [`crate::batch::in_chunks`]
+impl<'a> QueryParameter<'a> for &'_ dyn QueryParameter<'_> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        (**self).as_postgres_param()
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        (**self).as_sqlserver_param()
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn ToValue {
+        (**self).as_mysql_param()
+    }
+}
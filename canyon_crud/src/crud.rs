@@ -2,10 +2,14 @@ use async_trait::async_trait;
 use std::fmt::Display;
 
 use canyon_connection::canyon_database_connector::DatabaseConnection;
-use canyon_connection::{get_database_connection, CACHED_DATABASE_CONN};
+#[cfg(feature = "mssql")]
+use canyon_connection::canyon_database_connector::SqlServerConnection;
+use canyon_connection::{get_database_connection, CachedConnectionsGuard, CACHED_DATABASE_CONN};
 
 use crate::bounds::QueryParameter;
-use crate::mapper::RowMapper;
+use crate::dynamic_row::DynamicRow;
+use crate::hooks::CanyonHooks;
+use crate::mapper::{FromColumn, RowMapper};
 use crate::query_elements::query_builder::{
     DeleteQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
 };
@@ -41,29 +45,345 @@ pub trait Transaction<T> {
         match *database_conn {
             #[cfg(feature = "postgres")]
             DatabaseConnection::Postgres(_) => {
-                postgres_query_launcher::launch::<T>(
-                    database_conn,
-                    stmt.to_string(),
+                postgres_query_launcher::launch::<T, _>(
+                    &database_conn.postgres_connection().client,
+                    stmt.as_ref(),
                     params.as_ref(),
                 )
                 .await
             }
             #[cfg(feature = "mssql")]
             DatabaseConnection::SqlServer(_) => {
-                sqlserver_query_launcher::launch::<T, Z>(
-                    database_conn,
-                    &mut stmt.to_string(),
-                    params,
+                let client =
+                    sqlserver_query_launcher::client(database_conn.sqlserver_connection()).await?;
+                sqlserver_query_launcher::launch::<T>(client, stmt.as_ref(), params.as_ref()).await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySQL(_) => {
+                let mysql_connection = database_conn.mysql_connection().client.get_conn().await?;
+                mysql_query_launcher::launch::<T, _>(
+                    mysql_connection,
+                    stmt.as_ref(),
+                    params.as_ref(),
                 )
                 .await
             }
+        }
+    }
+
+    /// Executes a statement against the targeted database by the selected or
+    /// the defaulted datasource, discarding the rows that it may return.
+    ///
+    /// Returns the number of rows affected by the statement
+    async fn execute<'a, S, Z>(
+        stmt: S,
+        params: Z,
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send + 'static>>
+    where
+        S: AsRef<str> + Display + Sync + Send + 'a,
+        Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+    {
+        let mut guarded_cache = CACHED_DATABASE_CONN.lock().await;
+        let database_conn = get_database_connection(datasource_name, &mut guarded_cache);
+
+        match *database_conn {
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(_) => {
+                postgres_query_launcher::execute(
+                    &database_conn.postgres_connection().client,
+                    stmt.as_ref(),
+                    params.as_ref(),
+                )
+                .await
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseConnection::SqlServer(_) => {
+                let client =
+                    sqlserver_query_launcher::client(database_conn.sqlserver_connection()).await?;
+                sqlserver_query_launcher::execute(client, stmt.as_ref(), params.as_ref()).await
+            }
             #[cfg(feature = "mysql")]
             DatabaseConnection::MySQL(_) => {
-                mysql_query_launcher::launch::<T>(database_conn, stmt.to_string(), params.as_ref())
+                let mysql_connection = database_conn.mysql_connection().client.get_conn().await?;
+                mysql_query_launcher::execute(mysql_connection, stmt.as_ref(), params.as_ref())
                     .await
             }
         }
     }
+
+    /// Executes the statements, one after another, inside a single database transaction
+    /// against the targeted database by the selected or the defaulted datasource, so
+    /// none of them is applied if any of them fails.
+    ///
+    /// Returns the number of rows affected by every statement, in the same order
+    async fn execute_all<'a>(
+        stmts: Vec<(String, Vec<&'a dyn QueryParameter<'a>>)>,
+        datasource_name: &'a str,
+    ) -> Result<Vec<u64>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let mut guarded_cache = lock_connections().await;
+        let mut transaction =
+            DatabaseTransaction::begin_datasource(datasource_name, &mut guarded_cache).await?;
+
+        let result = async {
+            let mut affected_rows = Vec::with_capacity(stmts.len());
+            for (stmt, params) in stmts {
+                affected_rows.push(transaction.execute(&stmt, &params).await?);
+            }
+            Ok(affected_rows)
+        }
+        .await;
+
+        transaction.finish(result).await
+    }
+}
+
+/// Locks the cache of the connections, to open a [`DatabaseTransaction`] over
+/// one of them with [`DatabaseTransaction::begin_datasource`].
+///
+/// No other query can be run until the returned guard is dropped, because the
+/// cache can't be locked twice, so it'd wait forever for itself
+pub async fn lock_connections() -> CachedConnectionsGuard {
+    CACHED_DATABASE_CONN.lock().await
+}
+
+/// A database transaction opened over the cached connection of some datasource.
+///
+/// It's rolled back if it's dropped without being finished, like when the future
+/// that holds it is cancelled between two statements, so the shared connection is
+/// never left inside of an open transaction
+pub enum DatabaseTransaction<'c> {
+    #[cfg(feature = "postgres")]
+    Postgres(canyon_connection::tokio_postgres::Transaction<'c>),
+    #[cfg(feature = "mssql")]
+    SqlServer(SqlServerTransaction<'c>),
+    #[cfg(feature = "mysql")]
+    MySQL(canyon_connection::mysql_async::Transaction<'c>),
+}
+
+impl<'c> DatabaseTransaction<'c> {
+    /// Opens a new transaction over the given connection, which stays borrowed
+    /// until the transaction is finished
+    pub async fn begin(
+        database_conn: &'c mut DatabaseConnection,
+    ) -> Result<DatabaseTransaction<'c>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match database_conn {
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(conn) => Ok(DatabaseTransaction::Postgres(
+                conn.client.transaction().await?,
+            )),
+            #[cfg(feature = "mssql")]
+            DatabaseConnection::SqlServer(conn) => Ok(DatabaseTransaction::SqlServer(
+                sqlserver_query_launcher::begin(conn).await?,
+            )),
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySQL(conn) => Ok(DatabaseTransaction::MySQL(
+                conn.client
+                    .start_transaction(canyon_connection::mysql_async::TxOpts::default())
+                    .await?,
+            )),
+        }
+    }
+
+    /// Opens a new transaction over the connection of the selected or the defaulted
+    /// datasource, taken from the cache locked with [`lock_connections`]
+    pub async fn begin_datasource(
+        datasource_name: &str,
+        guarded_cache: &'c mut CachedConnectionsGuard,
+    ) -> Result<DatabaseTransaction<'c>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        Self::begin(get_database_connection(datasource_name, guarded_cache)).await
+    }
+
+    /// Performs a query within the transaction, wrapping the resultant
+    /// collection of entities in [`CanyonRows`]
+    pub async fn query<'a, T>(
+        &mut self,
+        stmt: &str,
+        params: &[&'a dyn QueryParameter<'a>],
+    ) -> Result<CanyonRows<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseTransaction::Postgres(transaction) => {
+                postgres_query_launcher::launch::<T, _>(&*transaction, stmt, params).await
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseTransaction::SqlServer(transaction) => {
+                sqlserver_query_launcher::launch::<T>(transaction.conn.client, stmt, params).await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseTransaction::MySQL(transaction) => {
+                mysql_query_launcher::launch::<T, _>(&mut *transaction, stmt, params).await
+            }
+        }
+    }
+
+    /// Executes a statement within the transaction, returning the number of rows affected
+    pub async fn execute<'a>(
+        &mut self,
+        stmt: &str,
+        params: &[&'a dyn QueryParameter<'a>],
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseTransaction::Postgres(transaction) => {
+                postgres_query_launcher::execute(&*transaction, stmt, params).await
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseTransaction::SqlServer(transaction) => {
+                sqlserver_query_launcher::execute(transaction.conn.client, stmt, params).await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseTransaction::MySQL(transaction) => {
+                mysql_query_launcher::execute(&mut *transaction, stmt, params).await
+            }
+        }
+    }
+
+    /// Commits the transaction if the `result` of the statements run within it is `Ok`,
+    /// or rolls it back otherwise, returning that `result`.
+    ///
+    /// If the rollback fails, its error is logged and the original one is returned
+    pub async fn finish<R>(
+        self,
+        result: Result<R, Box<dyn std::error::Error + Sync + Send + 'static>>,
+    ) -> Result<R, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match result {
+            Ok(value) => {
+                self.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(rollback_error) = self.rollback().await {
+                    eprintln!("Failed to roll back the transaction after the error `{e}`: {rollback_error}");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn commit(self) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseTransaction::Postgres(transaction) => Ok(transaction.commit().await?),
+            #[cfg(feature = "mssql")]
+            DatabaseTransaction::SqlServer(transaction) => {
+                sqlserver_query_launcher::finish(transaction, "COMMIT TRANSACTION").await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseTransaction::MySQL(transaction) => Ok(transaction.commit().await?),
+        }
+    }
+
+    async fn rollback(self) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseTransaction::Postgres(transaction) => Ok(transaction.rollback().await?),
+            #[cfg(feature = "mssql")]
+            DatabaseTransaction::SqlServer(transaction) => {
+                sqlserver_query_launcher::finish(transaction, "ROLLBACK TRANSACTION").await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseTransaction::MySQL(transaction) => Ok(transaction.rollback().await?),
+        }
+    }
+}
+
+/// A transaction opened over a SqlServer connection.
+///
+/// Tiberius has no transaction type, and the rollback can't be awaited when it's
+/// dropped, so it marks the connection to roll it back before its next statement
+#[cfg(feature = "mssql")]
+pub struct SqlServerTransaction<'c> {
+    conn: &'c mut SqlServerConnection,
+    finished: bool,
+}
+
+#[cfg(feature = "mssql")]
+impl Drop for SqlServerTransaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.conn.pending_rollback = true;
+        }
+    }
+}
+
+/// Dummy type that performs the ad-hoc queries, whose results aren't entities
+struct RawQuery;
+impl Transaction<RawQuery> for RawQuery {}
+
+/// Performs an arbitrary query against the targeted database by the selected or
+/// the defaulted datasource, returning its results as [`DynamicRow`].
+///
+/// * `stmt` - The SQL statement, whose parameters are written in the same way as
+///   in the rest of Canyon, with the `$1, $2...` placeholders
+/// * `params` - The values of the parameters of the statement
+/// * `datasource_name` - The targeted datasource, or an empty string for the default one
+pub async fn query_raw<'a, S, Z>(
+    stmt: S,
+    params: Z,
+    datasource_name: &'a str,
+) -> Result<Vec<DynamicRow>, Box<dyn std::error::Error + Send + Sync + 'static>>
+where
+    S: AsRef<str> + Display + Sync + Send + 'a,
+    Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+{
+    Ok(RawQuery::query(stmt, params, datasource_name)
+        .await?
+        .into_dynamic_rows())
+}
+
+/// Performs an arbitrary query against the targeted database by the selected or
+/// the defaulted datasource, mapping every row into `R`, that can be a tuple of
+/// supported types or any type that implements [`RowMapper`].
+///
+/// ```ignore
+/// let leagues: Vec<(i32, String)> =
+///     canyon_sql::query_as("SELECT id, name FROM league", [], "").await?;
+/// ```
+///
+/// * `stmt` - The SQL statement
+/// * `params` - The values of the parameters of the statement
+/// * `datasource_name` - The targeted datasource, or an empty string for the default one
+pub async fn query_as<'a, R, S, Z>(
+    stmt: S,
+    params: Z,
+    datasource_name: &'a str,
+) -> Result<Vec<R>, Box<dyn std::error::Error + Send + Sync + 'static>>
+where
+    R: RowMapper<R>,
+    S: AsRef<str> + Display + Sync + Send + 'a,
+    Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+{
+    Ok(RawQuery::query(stmt, params, datasource_name)
+        .await?
+        .into_results_as::<R>()?)
+}
+
+/// Performs an arbitrary query against the targeted database by the selected or
+/// the defaulted datasource, returning the value of the first column of the first
+/// row, like the result of an aggregate function.
+///
+/// ```ignore
+/// let last_update: Option<NaiveDateTime> =
+///     canyon_sql::query_scalar("SELECT max(updated_at) FROM league", [], "").await?;
+/// ```
+///
+/// * `stmt` - The SQL statement
+/// * `params` - The values of the parameters of the statement
+/// * `datasource_name` - The targeted datasource, or an empty string for the default one
+pub async fn query_scalar<'a, V, S, Z>(
+    stmt: S,
+    params: Z,
+    datasource_name: &'a str,
+) -> Result<V, Box<dyn std::error::Error + Send + Sync + 'static>>
+where
+    V: FromColumn,
+    S: AsRef<str> + Display + Sync + Send + 'a,
+    Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+{
+    RawQuery::query(stmt, params, datasource_name)
+        .await?
+        .into_scalar::<V>()
 }
 
 /// *CrudOperations* it's the core part of Canyon-SQL.
@@ -81,8 +401,10 @@ pub trait Transaction<T> {
 /// See it's definition and docs to see the implementations.
 /// Also, you can find the written macro-code that performs the auto-mapping
 /// in the *canyon_sql_root::canyon_macros* crates, on the root of this project.
+///
+/// Every operation calls the lifecycle hooks of the entity defined in [`CanyonHooks`]
 #[async_trait]
-pub trait CrudOperations<T>: Transaction<T>
+pub trait CrudOperations<T>: Transaction<T> + CanyonHooks
 where
     T: CrudOperations<T> + RowMapper<T>,
 {
@@ -122,6 +444,15 @@ where
         datasource_name: &'a str,
     ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
 
+    async fn insert_returning<'a>(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn insert_returning_datasource<'a>(
+        &mut self,
+        datasource_name: &'a str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+
     async fn multi_insert<'a>(
         instances: &'a mut [&'a mut T],
     ) -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>>;
@@ -131,39 +462,110 @@ where
         datasource_name: &'a str,
     ) -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>>;
 
-    async fn update(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+    async fn update(&self) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
 
     async fn update_datasource<'a>(
         &self,
         datasource_name: &'a str,
-    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn update_refreshing(&mut self) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn update_refreshing_datasource<'a>(
+        &mut self,
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
 
     fn update_query<'a>() -> UpdateQueryBuilder<'a, T>;
 
     fn update_query_datasource(datasource_name: &str) -> UpdateQueryBuilder<'_, T>;
 
-    async fn delete(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+    async fn delete(&self) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
 
     async fn delete_datasource<'a>(
         &self,
         datasource_name: &'a str,
-    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn hard_delete(&self) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn hard_delete_datasource<'a>(
+        &self,
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn restore(&self) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
+
+    async fn restore_datasource<'a>(
+        &self,
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send>>;
 
     fn delete_query<'a>() -> DeleteQueryBuilder<'a, T>;
 
     fn delete_query_datasource(datasource_name: &str) -> DeleteQueryBuilder<'_, T>;
+
+    /// Returns the column of the field annotated with `#[primary_key]`, if any
+    fn primary_key() -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the value of the field annotated with `#[primary_key]`, if any
+    fn primary_key_value(&self) -> Option<&dyn QueryParameter<'_>> {
+        None
+    }
+}
+
+/// Emulates the `RETURNING` clause of an *UPDATE* or *DELETE* statement on MySQL,
+/// selecting and locking the rows that matches the statement before executing it,
+/// inside a database transaction.
+///
+/// * `select_stmt` - The *SELECT* statement that matches the same rows that the `modify_stmt`
+/// * `modify_stmt` - The *UPDATE* or *DELETE* statement
+/// * `table_schema_data` - The table modified by the statement
+/// * `primary_key` - The primary key of the table, if the rows must be selected again
+///   after executing the `modify_stmt`, to retrieve their new values
+/// * `params` - The parameters of the `modify_stmt`, that are shared with the `select_stmt`
+/// * `datasource_name` - The targeted datasource
+#[cfg(feature = "mysql")]
+pub(crate) async fn mysql_modify_returning<'a, T>(
+    select_stmt: String,
+    modify_stmt: String,
+    table_schema_data: &str,
+    primary_key: Option<&str>,
+    params: &'a [&'a dyn QueryParameter<'a>],
+    datasource_name: &'a str,
+) -> Result<CanyonRows<T>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+{
+    let mut guarded_cache = CACHED_DATABASE_CONN.lock().await;
+    let database_conn = get_database_connection(datasource_name, &mut guarded_cache);
+
+    mysql_query_launcher::modify_returning::<T>(
+        database_conn,
+        select_stmt,
+        modify_stmt,
+        table_schema_data,
+        primary_key,
+        params,
+        datasource_name,
+    )
+    .await
 }
 
 #[cfg(feature = "postgres")]
 mod postgres_query_launcher {
-    use canyon_connection::canyon_database_connector::DatabaseConnection;
+    use canyon_connection::tokio_postgres::GenericClient;
 
     use crate::bounds::QueryParameter;
     use crate::rows::CanyonRows;
 
-    pub async fn launch<'a, T>(
-        db_conn: &DatabaseConnection,
-        stmt: String,
+    /// Performs the query through the client of the connection, or through
+    /// an open transaction
+    pub async fn launch<'a, T, C: GenericClient + Sync>(
+        client: &C,
+        stmt: &str,
         params: &'a [&'_ dyn QueryParameter<'_>],
     ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
         let mut m_params = Vec::new();
@@ -171,14 +573,25 @@ mod postgres_query_launcher {
             m_params.push(param.as_postgres_param());
         }
 
-        let r = db_conn
-            .postgres_connection()
-            .client
-            .query(&stmt, m_params.as_slice())
-            .await?;
+        let r = client.query(stmt, m_params.as_slice()).await?;
 
         Ok(CanyonRows::Postgres(r))
     }
+
+    /// Executes the statement through the client of the connection, or through
+    /// an open transaction
+    pub async fn execute<C: GenericClient + Sync>(
+        client: &C,
+        stmt: &str,
+        params: &[&'_ dyn QueryParameter<'_>],
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let mut m_params = Vec::new();
+        for param in params {
+            m_params.push(param.as_postgres_param());
+        }
+
+        Ok(client.execute(stmt, m_params.as_slice()).await?)
+    }
 }
 
 #[cfg(feature = "mssql")]
@@ -186,24 +599,30 @@ mod sqlserver_query_launcher {
     use crate::rows::CanyonRows;
     use crate::{
         bounds::QueryParameter,
-        canyon_connection::{canyon_database_connector::DatabaseConnection, tiberius::Query},
+        canyon_connection::{
+            async_std::net::TcpStream,
+            canyon_database_connector::SqlServerConnection,
+            tiberius::{Client, Query},
+        },
     };
 
-    pub async fn launch<'a, T, Z>(
-        db_conn: &mut DatabaseConnection,
-        stmt: &mut String,
-        params: Z,
-    ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
-    where
-        Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
-    {
+    use super::SqlServerTransaction;
+
+    /// Performs the query through the client of the connection, that may
+    /// have an open transaction
+    pub async fn launch<'a, T>(
+        client: &mut Client<TcpStream>,
+        stmt: &str,
+        params: &[&'a dyn QueryParameter<'a>],
+    ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+        let mut stmt = stmt.to_owned();
         // Re-generate de insert statement to adequate it to the SQL SERVER syntax to retrieve the PK value(s) after insert
         if stmt.contains("RETURNING") {
             let c = stmt.clone();
             let temp = c.split_once("RETURNING").unwrap();
             let temp2 = temp.0.split_once("VALUES").unwrap();
 
-            *stmt = format!(
+            stmt = format!(
                 "{} OUTPUT inserted.{} VALUES {}",
                 temp2.0.trim(),
                 temp.1.trim(),
@@ -211,30 +630,72 @@ mod sqlserver_query_launcher {
             );
         }
 
-        let mut mssql_query = Query::new(stmt.to_owned().replace('$', "@P"));
-        params
-            .as_ref()
-            .iter()
-            .for_each(|param| mssql_query.bind(*param));
+        let mut mssql_query = Query::new(stmt.replace('$', "@P"));
+        params.iter().for_each(|param| mssql_query.bind(*param));
 
-        let _results = mssql_query
-            .query(db_conn.sqlserver_connection().client)
-            .await?
-            .into_results()
-            .await?;
+        let _results = mssql_query.query(client).await?.into_results().await?;
 
         Ok(CanyonRows::Tiberius(
             _results.into_iter().flatten().collect(),
         ))
     }
+
+    pub async fn execute<'a>(
+        client: &mut Client<TcpStream>,
+        stmt: &str,
+        params: &[&'a dyn QueryParameter<'a>],
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let mut mssql_query = Query::new(stmt.replace('$', "@P"));
+        params.iter().for_each(|param| mssql_query.bind(*param));
+
+        Ok(mssql_query.execute(client).await?.total())
+    }
+
+    /// Returns the client of the connection, rolling back before the transaction
+    /// that a dropped [`SqlServerTransaction`] may have left open on it
+    pub async fn client(
+        conn: &mut SqlServerConnection,
+    ) -> Result<&mut Client<TcpStream>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        if conn.pending_rollback {
+            conn.client
+                .execute("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION", &[])
+                .await?;
+            conn.pending_rollback = false;
+        }
+        Ok(conn.client)
+    }
+
+    pub async fn begin(
+        conn: &mut SqlServerConnection,
+    ) -> Result<SqlServerTransaction<'_>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        client(conn)
+            .await?
+            .execute("BEGIN TRANSACTION", &[])
+            .await?;
+        Ok(SqlServerTransaction {
+            conn,
+            finished: false,
+        })
+    }
+
+    /// Commits or rolls back the transaction with the given statement
+    pub async fn finish(
+        mut transaction: SqlServerTransaction<'_>,
+        stmt: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        transaction.conn.client.execute(stmt, &[]).await?;
+        transaction.finished = true;
+        Ok(())
+    }
 }
 
 #[cfg(feature = "mysql")]
 mod mysql_query_launcher {
     use std::sync::Arc;
 
-    use mysql_async::prelude::Query;
+    use mysql_async::prelude::{Query, Queryable, ToConnection};
     use mysql_async::QueryWithParams;
+    use mysql_async::TxOpts;
     use mysql_async::Value;
 
     use canyon_connection::canyon_database_connector::DatabaseConnection;
@@ -246,23 +707,19 @@ mod mysql_query_launcher {
     use mysql_common::row;
 
     use super::reorder_params;
-    use crate::crud::{DETECT_PARAMS_IN_QUERY, DETECT_QUOTE_IN_QUERY};
+    use crate::batch::in_chunks;
+    use crate::crud::{CrudOperations, Transaction, DETECT_PARAMS_IN_QUERY, DETECT_QUOTE_IN_QUERY};
+    use crate::mapper::RowMapper;
     use regex::Regex;
 
-    pub async fn launch<'a, T>(
-        db_conn: &DatabaseConnection,
-        stmt: String,
-        params: &'a [&'_ dyn QueryParameter<'_>],
+    /// Performs the query through a connection of the pool, or through
+    /// an open transaction
+    pub async fn launch<'a, 't: 'a, T, C: ToConnection<'a, 't> + 'a>(
+        mysql_connection: C,
+        stmt: &str,
+        params: &[&'_ dyn QueryParameter<'_>],
     ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
-        let mysql_connection = db_conn.mysql_connection().client.get_conn().await?;
-
-        let stmt_with_escape_characters = regex::escape(&stmt);
-        let query_string =
-            Regex::new(DETECT_PARAMS_IN_QUERY)?.replace_all(&stmt_with_escape_characters, "?");
-
-        let mut query_string = Regex::new(DETECT_QUOTE_IN_QUERY)?
-            .replace_all(&query_string, "")
-            .to_string();
+        let mut query_string = to_mysql_syntax(stmt)?;
 
         let mut is_insert = false;
         if let Some(index_start_clausule_returning) = query_string.find(" RETURNING") {
@@ -271,7 +728,7 @@ mod mysql_query_launcher {
         }
 
         let params_query: Vec<Value> =
-            reorder_params(&stmt, params, |f| f.as_mysql_param().to_value());
+            reorder_params(stmt, params, |f| f.as_mysql_param().to_value());
 
         let query_with_params = QueryWithParams {
             query: query_string,
@@ -302,6 +759,102 @@ mod mysql_query_launcher {
 
         Ok(CanyonRows::MySQL(result_rows))
     }
+
+    /// Executes the statement through a connection of the pool, or through
+    /// an open transaction
+    pub async fn execute<'a, 't: 'a, C: ToConnection<'a, 't> + 'a>(
+        mysql_connection: C,
+        stmt: &str,
+        params: &[&'_ dyn QueryParameter<'_>],
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let query_with_params = QueryWithParams {
+            query: to_mysql_syntax(stmt)?,
+            params: reorder_params(stmt, params, |f| f.as_mysql_param().to_value()),
+        };
+
+        let query_result = query_with_params.run(mysql_connection).await?;
+        let affected_rows = query_result.affected_rows();
+        query_result.drop_result().await?;
+
+        Ok(affected_rows)
+    }
+
+    pub async fn modify_returning<T>(
+        db_conn: &DatabaseConnection,
+        select_stmt: String,
+        modify_stmt: String,
+        table_schema_data: &str,
+        primary_key: Option<&str>,
+        params: &[&'_ dyn QueryParameter<'_>],
+        datasource_name: &str,
+    ) -> Result<CanyonRows<T>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    where
+        T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+    {
+        let mut mysql_connection = db_conn.mysql_connection().client.get_conn().await?;
+        let mut transaction = mysql_connection
+            .start_transaction(TxOpts::default())
+            .await?;
+
+        let locked_rows: Vec<Row> = transaction
+            .exec(
+                to_mysql_syntax(&format!("{select_stmt} FOR UPDATE"))?,
+                reorder_params(&select_stmt, params, |f| f.as_mysql_param().to_value()),
+            )
+            .await?;
+        transaction
+            .exec_drop(
+                to_mysql_syntax(&modify_stmt)?,
+                reorder_params(&modify_stmt, params, |f| f.as_mysql_param().to_value()),
+            )
+            .await?;
+
+        let result_rows = match primary_key {
+            Some(primary_key) if !locked_rows.is_empty() => {
+                let locked_entities = CanyonRows::<T>::MySQL(locked_rows).into_results::<T>()?;
+                let pk_values = locked_entities
+                    .iter()
+                    .map(|entity| {
+                        entity
+                            .primary_key_value()
+                            .ok_or_else(|| format!("Missing the primary key value: {primary_key}"))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                let mut result_rows = Vec::with_capacity(pk_values.len());
+                for (stmt, params) in in_chunks(
+                    &format!("SELECT * FROM {table_schema_data} WHERE {primary_key}"),
+                    &pk_values,
+                    datasource_name,
+                ) {
+                    let rows: Vec<Row> = transaction
+                        .exec(
+                            to_mysql_syntax(&stmt)?,
+                            reorder_params(&stmt, &params, |f| f.as_mysql_param().to_value()),
+                        )
+                        .await?;
+                    result_rows.extend(rows);
+                }
+                result_rows
+            }
+            _ => locked_rows,
+        };
+        transaction.commit().await?;
+
+        Ok(CanyonRows::MySQL(result_rows))
+    }
+
+    /// Replaces the placeholders of the statement with the MySQL ones,
+    /// and removes the quotes of the identifiers
+    fn to_mysql_syntax(stmt: &str) -> Result<String, regex::Error> {
+        let stmt_with_escape_characters = regex::escape(stmt);
+        let query_string =
+            Regex::new(DETECT_PARAMS_IN_QUERY)?.replace_all(&stmt_with_escape_characters, "?");
+
+        Ok(Regex::new(DETECT_QUOTE_IN_QUERY)?
+            .replace_all(&query_string, "")
+            .to_string())
+    }
 }
 
 #[cfg(feature = "mysql")]
@@ -0,0 +1,221 @@
+//! Contains the [`RowDeserializer`], that allows any type implementing
+//! `serde::Deserialize` to be produced from the rows of a query, as an
+//! alternative to deriving `CanyonMapper`
+
+use serde::de::{
+    value::SeqDeserializer, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use crate::{
+    bounds::Column, dynamic_row::CellValue, dynamic_row::DynamicRow, errors::RowDeserializeError,
+};
+
+/// A `serde` [`Deserializer`] over a [`DynamicRow`].
+///
+/// The row is deserialized as a map whose keys are the names of the columns, so
+/// the fields of the structs are matched by name, honoring the `#[serde(rename)]`
+/// and the `#[serde(flatten)]` attributes, and the `NULL` values are deserialized
+/// into `None`. The tuples are deserialized from the columns in order.
+///
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct LeagueView {
+///     id: i32,
+///     #[serde(rename = "name")]
+///     title: String,
+///     image_url: Option<String>,
+/// }
+///
+/// let leagues: Vec<LeagueView> = League::select_query().deserialize().await?;
+/// ```
+pub struct RowDeserializer<'a> {
+    row: &'a DynamicRow,
+    columns: Vec<Column<'a>>,
+}
+
+impl<'a> RowDeserializer<'a> {
+    pub fn new(row: &'a DynamicRow) -> Self {
+        Self {
+            row,
+            columns: row.columns(),
+        }
+    }
+
+    fn columns(&self) -> ColumnsAccess<'_, 'a> {
+        ColumnsAccess {
+            row: self.row,
+            columns: &self.columns,
+            idx: 0,
+        }
+    }
+}
+
+impl<'de, 'a> Deserializer<'de> for RowDeserializer<'a> {
+    type Error = RowDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(self.columns())
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self.columns())
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Walks over the columns of a row, as the entries of a map or as the elements of a sequence
+struct ColumnsAccess<'r, 'a> {
+    row: &'a DynamicRow,
+    columns: &'r [Column<'a>],
+    idx: usize,
+}
+
+impl<'r, 'a> ColumnsAccess<'r, 'a> {
+    /// Reads the value of the current column, moving to the next one
+    fn next_value(&mut self) -> Result<CellValue, RowDeserializeError> {
+        let idx = self.idx;
+        self.idx += 1;
+        Ok(self.row.cell(idx, self.columns[idx].data_type())?)
+    }
+}
+
+impl<'de, 'r, 'a> MapAccess<'de> for ColumnsAccess<'r, 'a> {
+    type Error = RowDeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.columns
+            .get(self.idx)
+            .map(|column| seed.deserialize(column.name().into_deserializer()))
+            .transpose()
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(self.next_value()?)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.columns.len() - self.idx)
+    }
+}
+
+impl<'de, 'r, 'a> SeqAccess<'de> for ColumnsAccess<'r, 'a> {
+    type Error = RowDeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.idx == self.columns.len() {
+            return Ok(None);
+        }
+        seed.deserialize(self.next_value()?).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.columns.len() - self.idx)
+    }
+}
+
+/// Deserializes the value of a single column
+impl<'de> Deserializer<'de> for CellValue {
+    type Error = RowDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            Self::Bool(v) => visitor.visit_bool(v),
+            Self::Int(v) => visitor.visit_i64(v),
+            Self::Float(v) => visitor.visit_f64(v),
+            Self::Text(v) => visitor.visit_string(v),
+            Self::Bytes(v) => visitor.visit_byte_buf(v),
+            #[cfg(feature = "json")]
+            Self::Json(v) => v.deserialize_any(visitor).map_err(serde::de::Error::custom),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    /// Allows the binary values to be deserialized into a `Vec<u8>`
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Bytes(v) => visitor.visit_seq(SeqDeserializer::new(v.into_iter())),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    /// Allows the text values to be deserialized into the unit variants of an enum
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Text(v) => visitor.visit_enum(v.into_deserializer()),
+            #[cfg(feature = "json")]
+            Self::Json(v) => v
+                .deserialize_enum(_name, _variants, visitor)
+                .map_err(serde::de::Error::custom),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
@@ -0,0 +1,269 @@
+//! Contains the [`DynamicRow`], an untyped row that allows to run ad-hoc SQL
+//! with [`crate::crud::query_raw`] and read its results without declaring a type for them
+
+#[cfg(feature = "mysql")]
+use canyon_connection::mysql_async;
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius;
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres;
+
+use std::fmt::Display;
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    bounds::{Column, DataType},
+    deserializer::RowDeserializer,
+    errors::{RowDeserializeError, RowMapperError},
+    mapper::FromColumn,
+};
+
+/// The name reported by the errors of the values that can't be retrieved from a [`DynamicRow`]
+const DYNAMIC_ROW: &str = "DynamicRow";
+
+/// A row of results of any of the supported databases, whose values are retrieved
+/// by the name or by the position of their columns.
+///
+/// ```ignore
+/// let rows = canyon_sql::query_raw(
+///     "SELECT id, name FROM league WHERE region = $1",
+///     &[&"KOREA"],
+///     "",
+/// )
+/// .await?;
+///
+/// for row in &rows {
+///     let id: i32 = row.get("id");
+///     let name: String = row.get(1);
+/// }
+/// ```
+pub enum DynamicRow {
+    #[cfg(feature = "postgres")]
+    Postgres(tokio_postgres::Row),
+    #[cfg(feature = "mssql")]
+    Tiberius(tiberius::Row),
+    #[cfg(feature = "mysql")]
+    MySQL(mysql_async::Row),
+}
+
+/// Addresses a column of a [`DynamicRow`], by its name or by its position
+pub trait ColumnIndex: Display {
+    /// Returns the position of the addressed column, if the row has it
+    fn index_of(&self, row: &DynamicRow) -> Option<usize>;
+}
+
+impl ColumnIndex for usize {
+    fn index_of(&self, row: &DynamicRow) -> Option<usize> {
+        (*self < row.len()).then_some(*self)
+    }
+}
+
+impl ColumnIndex for &str {
+    fn index_of(&self, row: &DynamicRow) -> Option<usize> {
+        row.columns()
+            .iter()
+            .position(|column| column.name() == *self)
+    }
+}
+
+impl ColumnIndex for String {
+    fn index_of(&self, row: &DynamicRow) -> Option<usize> {
+        self.as_str().index_of(row)
+    }
+}
+
+impl DynamicRow {
+    /// Returns the number of columns of the row
+    pub fn len(&self) -> usize {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => row.len(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => row.len(),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(row) => row.len(),
+        }
+    }
+
+    /// Returns true whenever the row does not contains any column
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name and the type of the columns of the row
+    pub fn columns(&self) -> Vec<Column<'_>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => row.columns().iter().map(Column::from_postgres).collect(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => row.columns().iter().map(Column::from_sqlserver).collect(),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(row) => row.columns_ref().iter().map(Column::from_mysql).collect(),
+        }
+    }
+
+    /// Retrieves the value of the column addressed by `index`, being it its name or its position.
+    ///
+    /// Panics if the row doesn't have the column, or if its value can't be converted into `T`.
+    /// See [`DynamicRow::try_get`] for a fallible version
+    pub fn get<T: FromColumn, I: ColumnIndex>(&self, index: I) -> T {
+        self.try_get(index).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Retrieves the value of the column addressed by `index`, being it its name or its position
+    ///
+    /// * `index` - The name of the column, or its position on the row
+    pub fn try_get<T: FromColumn, I: ColumnIndex>(&self, index: I) -> Result<T, RowMapperError> {
+        let expected = std::any::type_name::<T>();
+        let idx = index.index_of(self).ok_or_else(|| {
+            RowMapperError::missing_column(DYNAMIC_ROW, index.to_string(), expected)
+        })?;
+        let column = index.to_string();
+
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => T::from_postgres_column(row, idx).map_err(|e| {
+                let actual = crate::mapper::postgres_column_type(row, idx).unwrap_or_default();
+                RowMapperError::mismatch(DYNAMIC_ROW, column, expected, actual, e)
+            }),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => T::from_sqlserver_column(row, idx).map_err(|e| {
+                let actual = crate::mapper::sqlserver_column_type(row, idx).unwrap_or_default();
+                RowMapperError::mismatch(DYNAMIC_ROW, column, expected, actual, e)
+            }),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(row) => T::from_mysql_column(row, idx).map_err(|e| {
+                let actual = crate::mapper::mysql_column_type(row, idx).unwrap_or_default();
+                RowMapperError::mismatch(DYNAMIC_ROW, column, expected, actual, e)
+            }),
+        }
+    }
+
+    /// Deserializes the row into an instance of `T` with `serde`, matching the names
+    /// of the columns against the fields of `T`. See [`RowDeserializer`] for the details
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, RowDeserializeError> {
+        T::deserialize(RowDeserializer::new(self))
+    }
+
+    /// Converts the row into a JSON object whose keys are the names of the columns.
+    ///
+    /// The values are converted according to the normalized type of their columns,
+    /// being the decimals, the UUIDs and the temporal values written as strings,
+    /// and the binary ones as arrays of bytes
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<serde_json::Value, RowMapperError> {
+        use serde_json::Value;
+
+        let mut object = serde_json::Map::new();
+        for (idx, column) in self.columns().iter().enumerate() {
+            let value = match self.cell(idx, column.data_type())? {
+                CellValue::Null => Value::Null,
+                CellValue::Bool(v) => Value::from(v),
+                CellValue::Int(v) => Value::from(v),
+                CellValue::Float(v) => Value::from(v),
+                CellValue::Text(v) => Value::String(v),
+                CellValue::Bytes(v) => Value::from(v),
+                CellValue::Json(v) => v,
+            };
+            object.insert(column.name().to_owned(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Reads the value of the column placed at `idx` according to its normalized type
+    pub(crate) fn cell(
+        &self,
+        idx: usize,
+        data_type: &DataType,
+    ) -> Result<CellValue, RowMapperError> {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+        fn cell_of<V>(value: Option<V>, f: impl FnOnce(V) -> CellValue) -> CellValue {
+            value.map(f).unwrap_or(CellValue::Null)
+        }
+
+        Ok(match data_type {
+            DataType::Bool => cell_of(self.try_get(idx)?, CellValue::Bool),
+            DataType::SmallInt | DataType::Int | DataType::BigInt => {
+                cell_of(self.try_get_integer(idx)?, CellValue::Int)
+            }
+            DataType::Float | DataType::Double => self
+                .try_get::<Option<f64>, _>(idx)
+                .or_else(|_| {
+                    self.try_get::<Option<f32>, _>(idx)
+                        .map(|v| v.map(f64::from))
+                })
+                .map(|v| cell_of(v, CellValue::Float))?,
+            #[cfg(feature = "decimal")]
+            DataType::Decimal => cell_of(
+                self.try_get::<Option<rust_decimal::Decimal>, _>(idx)?,
+                |v| CellValue::Text(v.to_string()),
+            ),
+            #[cfg(feature = "uuid")]
+            DataType::Uuid => cell_of(self.try_get::<Option<uuid::Uuid>, _>(idx)?, |v| {
+                CellValue::Text(v.to_string())
+            }),
+            #[cfg(feature = "json")]
+            DataType::Json => cell_of(self.try_get(idx)?, CellValue::Json),
+            DataType::Binary => cell_of(self.try_get(idx)?, CellValue::Bytes),
+            DataType::Date => cell_of(self.try_get::<Option<NaiveDate>, _>(idx)?, |v| {
+                CellValue::Text(v.to_string())
+            }),
+            DataType::Time => cell_of(self.try_get::<Option<NaiveTime>, _>(idx)?, |v| {
+                CellValue::Text(v.to_string())
+            }),
+            DataType::DateTime => cell_of(self.try_get::<Option<NaiveDateTime>, _>(idx)?, |v| {
+                CellValue::Text(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            }),
+            DataType::DateTimeTz => cell_of(self.try_get::<Option<DateTime<Utc>>, _>(idx)?, |v| {
+                CellValue::Text(v.to_rfc3339())
+            }),
+            DataType::Text => cell_of(self.try_get_text(idx)?, CellValue::Text),
+            // The decimals, the UUIDs and the JSON values without their features enabled are read as text
+            _ => cell_of(self.try_get(idx)?, CellValue::Text),
+        })
+    }
+
+    /// Reads a text value, being the native `ENUM` columns of Postgres read through their labels
+    fn try_get_text(&self, idx: usize) -> Result<Option<String>, RowMapperError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => {
+                crate::types::decode_postgres_enum_label(row, idx).map_err(|e| {
+                    let actual = crate::mapper::postgres_column_type(row, idx).unwrap_or_default();
+                    let column = row.columns()[idx].name().to_owned();
+                    RowMapperError::mismatch(DYNAMIC_ROW, column, "String", actual, e)
+                })
+            }
+            #[cfg(any(feature = "mssql", feature = "mysql"))]
+            _ => self.try_get(idx),
+        }
+    }
+
+    /// Reads an integer of any size, given that Postgres only converts the integer
+    /// columns into the Rust types of their same size
+    fn try_get_integer(&self, idx: usize) -> Result<Option<i64>, RowMapperError> {
+        self.try_get::<Option<i64>, _>(idx)
+            .or_else(|_| {
+                self.try_get::<Option<i32>, _>(idx)
+                    .map(|v| v.map(i64::from))
+            })
+            .or_else(|_| {
+                self.try_get::<Option<i16>, _>(idx)
+                    .map(|v| v.map(i64::from))
+            })
+    }
+}
+
+/// A value read from a column of a [`DynamicRow`], according to its normalized type
+pub(crate) enum CellValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    #[cfg(feature = "json")]
+    Json(serde_json::Value),
+}
@@ -0,0 +1,68 @@
+//! Contains the [`CanyonEmbeddable`] trait, that allows a group of columns to be
+//! mapped into a value object that's a field of the entities
+
+#[cfg(feature = "mysql")]
+use canyon_connection::mysql_async;
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius;
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres;
+
+use crate::{bounds::QueryParameter, errors::RowMapperError};
+
+/// A value object, like an address or an amount of money, whose fields are flattened
+/// into prefixed columns of the entities that holds it on an `#[embedded]` field.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, PartialEq, CanyonEmbeddable)]
+/// pub struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper)]
+/// #[canyon_entity]
+/// pub struct Customer {
+///     #[primary_key]
+///     id: i32,
+///     // Stored on the `billing_street` and `billing_city` columns
+///     #[embedded(prefix = "billing_")]
+///     billing: Address,
+/// }
+/// ```
+///
+/// It's implemented by deriving `CanyonEmbeddable`, that must be declared before
+/// the types that embeds it
+pub trait CanyonEmbeddable: Sized {
+    /// Returns the names of the fields of the value object, that are the names of
+    /// its columns once prefixed
+    fn columns() -> &'static [&'static str];
+
+    /// Returns the value of the field named `column`, if the value object has it
+    fn get_column(&self, column: &str) -> Option<&dyn QueryParameter<'_>>;
+
+    /// Identifies the value object on the register of the macros, as
+    /// [`CanyonType::REGISTRY_KEY`](crate::types::CanyonType::REGISTRY_KEY)
+    const REGISTRY_KEY: &'static str = "";
+
+    /// Maps the columns of the row named with the given prefix into a new instance,
+    /// reporting the column that couldn't be retrieved with the name of the `entity`
+    #[cfg(feature = "postgres")]
+    fn try_from_postgres_row(
+        row: &tokio_postgres::Row,
+        entity: &'static str,
+        prefix: &str,
+    ) -> Result<Self, RowMapperError>;
+    #[cfg(feature = "mssql")]
+    fn try_from_sqlserver_row(
+        row: &tiberius::Row,
+        entity: &'static str,
+        prefix: &str,
+    ) -> Result<Self, RowMapperError>;
+    #[cfg(feature = "mysql")]
+    fn try_from_mysql_row(
+        row: &mysql_async::Row,
+        entity: &'static str,
+        prefix: &str,
+    ) -> Result<Self, RowMapperError>;
+}
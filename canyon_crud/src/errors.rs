@@ -0,0 +1,256 @@
+//! Contains the error types that are raised by the `CRUD` operations of
+//! Canyon, when the failure is not a plain database error and the user may
+//! want to react to it
+
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Raised by the `update` operation of an entity that has a `#[version]` field,
+/// when no row matched the primary key and the version of the instance.
+///
+/// This means that the row was modified (or deleted) by someone else since
+/// the instance was retrieved, so the changes weren't persisted to avoid
+/// overwriting the ones made by the other party
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleObjectError {
+    /// The name of the entity (the Rust type) that failed to be updated
+    pub entity: &'static str,
+    /// The value of the primary key of the instance
+    pub primary_key: String,
+    /// The version that the instance was holding
+    pub version: String,
+}
+
+impl StaleObjectError {
+    pub fn new(entity: &'static str, primary_key: String, version: String) -> Self {
+        Self {
+            entity,
+            primary_key,
+            version,
+        }
+    }
+}
+
+impl Display for StaleObjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Stale {} with primary key {}: the row was modified or deleted since version {} was read",
+            self.entity, self.primary_key, self.version
+        )
+    }
+}
+
+impl Error for StaleObjectError {}
+
+/// A rule declared in a `#[validate(...)]` annotation that the value
+/// of a field doesn't satisfy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The name of the field that holds the invalid value
+    pub field: &'static str,
+    /// The name of the rule that failed, like `length`, `range`, `regex` or `custom`
+    pub rule: &'static str,
+    /// A human readable description of the failure
+    pub message: String,
+}
+
+/// Raised by the `insert`, `multi_insert` and `update` operations of an entity
+/// when some of its fields doesn't satisfy the rules declared in their
+/// `#[validate(...)]` annotations. Nothing is written to the database.
+///
+/// It lists every failing rule of the instance, not just the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationErrors {
+    /// The name of the entity (the Rust type) that failed the validation
+    pub entity: &'static str,
+    /// The position of the invalid instance, when several instances are
+    /// written at once with `multi_insert`
+    pub instance: Option<usize>,
+    /// The failing rules
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    pub fn new(entity: &'static str) -> Self {
+        Self {
+            entity,
+            instance: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records a failing rule
+    pub fn add(&mut self, field: &'static str, rule: &'static str, message: String) {
+        self.errors.push(ValidationError {
+            field,
+            rule,
+            message,
+        })
+    }
+
+    /// Sets the position of the invalid instance on a `multi_insert`
+    pub fn at(mut self, instance: usize) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+
+    /// Returns the errors of the desired field
+    pub fn field_errors<'a>(
+        &'a self,
+        field: &'a str,
+    ) -> impl Iterator<Item = &'a ValidationError> + 'a {
+        self.errors.iter().filter(move |error| error.field == field)
+    }
+
+    /// Converts the errors into a [`Result`], being an error only if there's some
+    /// failing rule
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid {}", self.entity)?;
+        if let Some(instance) = self.instance {
+            write!(f, " at position {instance}")?;
+        }
+        let errors = self
+            .errors
+            .iter()
+            .map(|error| format!("`{}` {}", error.field, error.message))
+            .collect::<Vec<String>>();
+        write!(f, ": {}", errors.join(", "))
+    }
+}
+
+impl Error for ValidationErrors {}
+
+/// Raised when a row retrieved from the database can't be mapped into an
+/// instance of some type, because a column is missing, it holds an unexpected
+/// `NULL` or its type can't be converted into the type of the target field.
+///
+/// This usually means that the schema of the database drifted away from
+/// the definition of the entity
+#[derive(Debug)]
+pub struct RowMapperError {
+    /// The name of the type that the row was being mapped into
+    pub entity: &'static str,
+    /// The name of the column, or its index for the types mapped by position
+    pub column: String,
+    /// The Rust type of the value that was expected
+    pub expected: &'static str,
+    /// The type of the column reported by the database, or `None` when
+    /// the column isn't present on the row
+    pub actual: Option<String>,
+    /// The error reported by the database client, if any
+    pub source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl RowMapperError {
+    /// The column isn't present on the row
+    pub fn missing_column(entity: &'static str, column: String, expected: &'static str) -> Self {
+        Self {
+            entity,
+            column,
+            expected,
+            actual: None,
+            source: None,
+        }
+    }
+
+    /// The value of the column can't be converted into the expected type
+    pub fn mismatch(
+        entity: &'static str,
+        column: String,
+        expected: &'static str,
+        actual: String,
+        source: Box<dyn Error + Send + Sync>,
+    ) -> Self {
+        Self {
+            entity,
+            column,
+            expected,
+            actual: Some(actual),
+            source: Some(source),
+        }
+    }
+}
+
+impl Display for RowMapperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to map a row into {}: ", self.entity)?;
+        match &self.actual {
+            Some(actual) => write!(
+                f,
+                "the column `{}` of type {} can't be converted into `{}`",
+                self.column, actual, self.expected
+            )?,
+            None => write!(
+                f,
+                "the column `{}` (expected as `{}`) isn't present on the row",
+                self.column, self.expected
+            )?,
+        }
+        if let Some(source) = &self.source {
+            write!(f, " ({source})")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RowMapperError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+/// Raised when a row can't be deserialized with `serde` into an instance of some
+/// type, because a value can't be read from the row or because it doesn't fit
+/// into the fields of the type
+#[derive(Debug)]
+pub struct RowDeserializeError {
+    /// The description of the failure, as reported by the deserialized type
+    pub message: String,
+    /// The error that happened reading the value of a column, if any
+    pub source: Option<RowMapperError>,
+}
+
+impl Display for RowDeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to deserialize a row: {}", self.message)
+    }
+}
+
+impl Error for RowDeserializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl serde::de::Error for RowDeserializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+            source: None,
+        }
+    }
+}
+
+impl From<RowMapperError> for RowDeserializeError {
+    fn from(error: RowMapperError) -> Self {
+        Self {
+            message: error.to_string(),
+            source: Some(error),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Contains the lifecycle hooks that Canyon calls around the `CRUD`
+//! operations of an entity
+
+use std::{error::Error, future::Future, pin::Pin};
+
+use async_trait::async_trait;
+
+/// Callbacks that are invoked by the `CRUD` operations generated by the
+/// `CanyonCrud` derive macro, letting an entity to normalize its data,
+/// validate it or run side effects when it's written or retrieved.
+///
+/// Every hook does nothing by default. The derive macro implements this trait
+/// with the default hooks, unless the entity is annotated with `#[canyon_crud(hooks)]`,
+/// which means that the implementation is provided by the user:
+///
+/// ```ignore
+/// #[derive(CanyonCrud, CanyonMapper)]
+/// #[canyon_crud(hooks)]
+/// #[canyon_entity]
+/// pub struct League { /* ... */ }
+///
+/// #[canyon_sql::macros::async_trait]
+/// impl CanyonHooks for League {
+///     async fn before_insert(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+///         self.name = self.name.trim().to_string();
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// An error returned by a `before_*` hook aborts the operation before any statement
+/// is sent to the database, and it's returned to the caller. The `after_*` hooks are
+/// called once the statement succeeded, within the same database transaction, that's
+/// only committed if the hook succeeds too. So an error returned by them rolls back
+/// the changes, and it's returned to the caller.
+///
+/// The connections of Canyon stay locked while that transaction is open, so the
+/// `after_insert`, `after_update` and `after_delete` hooks can't run queries through
+/// Canyon, which would wait forever for the connections to be released
+#[async_trait]
+pub trait CanyonHooks: Send + Sync {
+    /// Called by `insert` and `multi_insert`, before writing the instance
+    async fn before_insert(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Called by `insert` and `multi_insert`, once the instance is written
+    /// and its primary key has been assigned, before committing it
+    async fn after_insert(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Called by `update` (and `update_refreshing`), before writing the instance.
+    ///
+    /// Unlike [`Self::before_insert`], it can't modify the instance, because
+    /// `update` borrows it immutably
+    async fn before_update(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Called by `update` (and `update_refreshing`), once the instance is written,
+    /// before committing it
+    async fn after_update(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Called by `delete` (and `hard_delete`), before removing the instance
+    async fn before_delete(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Called by `delete` (and `hard_delete`), once the instance is removed,
+    /// before committing it
+    async fn after_delete(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Called on every instance retrieved from the database by the `find_*`
+    /// operations, the foreign key searches and the queries of the
+    /// [`crate::query_elements::query_builder::SelectQueryBuilder`]
+    async fn after_load(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+/// A pointer to the [`CanyonHooks::after_load`] hook of some type,
+/// for the places that only know the type if it's an entity
+pub type AfterLoadHook<R> = for<'b> fn(
+    &'b mut R,
+) -> Pin<
+    Box<dyn Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send + 'b>,
+>;
+
+/// Calls the [`CanyonHooks::after_load`] hook on every loaded instance,
+/// stopping on the first one that fails
+pub async fn after_load_all<T: CanyonHooks>(
+    instances: &mut [T],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for instance in instances.iter_mut() {
+        instance.after_load().await?;
+    }
+    Ok(())
+}
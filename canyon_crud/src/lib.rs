@@ -1,11 +1,22 @@
 pub extern crate async_trait;
 extern crate canyon_connection;
 
+pub mod batch;
 pub mod bounds;
 pub mod crud;
+pub mod deserializer;
+pub mod dynamic_row;
+pub mod embeddable;
+pub mod errors;
+pub mod hooks;
 pub mod mapper;
 pub mod query_elements;
+pub mod relations;
 pub mod rows;
+pub mod timestamps;
+pub mod tracked;
+pub mod types;
+pub mod validation;
 
 pub use query_elements::operators::*;
 
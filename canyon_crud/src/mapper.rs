@@ -1,20 +1,760 @@
 #[cfg(feature = "mysql")]
 use canyon_connection::mysql_async;
 #[cfg(feature = "mssql")]
-use canyon_connection::tiberius;
+use canyon_connection::tiberius::{self, ColumnData};
 #[cfg(feature = "postgres")]
 use canyon_connection::tokio_postgres;
 
-use crate::crud::Transaction;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::errors::RowMapperError;
 
 /// Declares functions that takes care to deserialize data incoming
 /// from some supported database in Canyon-SQL into a user's defined
 /// type `T`
-pub trait RowMapper<T: Transaction<T>>: Sized {
+pub trait RowMapper<T>: Sized {
+    /// Returns the names of the columns that the implementor expects to find
+    /// on the rows, or an empty slice if the values are retrieved by position
+    fn columns() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Maps the row into a new instance of `T`, reporting the column that
+    /// couldn't be retrieved if the row doesn't match the type
+    #[cfg(feature = "postgres")]
+    fn try_deserialize_postgresql(row: &tokio_postgres::Row) -> Result<T, RowMapperError>;
+    #[cfg(feature = "mssql")]
+    fn try_deserialize_sqlserver(row: &tiberius::Row) -> Result<T, RowMapperError>;
+    #[cfg(feature = "mysql")]
+    fn try_deserialize_mysql(row: &mysql_async::Row) -> Result<T, RowMapperError>;
+
+    /// Maps the row into a new instance of `T`.
+    ///
+    /// Panics if the row doesn't match the type. Prefer
+    /// [`RowMapper::try_deserialize_postgresql`] on fallible contexts
+    #[cfg(feature = "postgres")]
+    fn deserialize_postgresql(row: &tokio_postgres::Row) -> T {
+        Self::try_deserialize_postgresql(row).unwrap_or_else(|e| panic!("{e}"))
+    }
+    #[cfg(feature = "mssql")]
+    fn deserialize_sqlserver(row: &tiberius::Row) -> T {
+        Self::try_deserialize_sqlserver(row).unwrap_or_else(|e| panic!("{e}"))
+    }
+    #[cfg(feature = "mysql")]
+    fn deserialize_mysql(row: &mysql_async::Row) -> T {
+        Self::try_deserialize_mysql(row).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// Returns the type of the column placed at `idx`, as reported by the database
+#[cfg(feature = "postgres")]
+pub(crate) fn postgres_column_type(row: &tokio_postgres::Row, idx: usize) -> Option<String> {
+    row.columns()
+        .get(idx)
+        .map(|column| column.type_().name().to_owned())
+}
+
+/// Returns the type of the column placed at `idx`, as reported by the database
+#[cfg(feature = "mssql")]
+pub(crate) fn sqlserver_column_type(row: &tiberius::Row, idx: usize) -> Option<String> {
+    row.columns()
+        .get(idx)
+        .map(|column| format!("{:?}", column.column_type()))
+}
+
+/// Returns the type of the column placed at `idx`, as reported by the database
+#[cfg(feature = "mysql")]
+pub(crate) fn mysql_column_type(row: &mysql_async::Row, idx: usize) -> Option<String> {
+    row.columns_ref()
+        .get(idx)
+        .map(|column| format!("{:?}", column.column_type()))
+}
+
+/// Retrieves the value of the column named `column`, used by the `CanyonMapper`
+/// derive to map the fields of the entities
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `column` - The name of the column
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "postgres")]
+pub fn try_get_postgres_column<V: FromColumn>(
+    row: &tokio_postgres::Row,
+    entity: &'static str,
+    column: &str,
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    try_get_postgres_column_aliased(row, entity, &[column], expected)
+}
+
+/// Retrieves the value of the first of the `columns` that's present on the row, used by
+/// the `CanyonMapper` derive to map the fields annotated with `#[column(alias = "...")]`
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `columns` - The name of the field, followed by its aliases
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "postgres")]
+pub fn try_get_postgres_column_aliased<V: FromColumn>(
+    row: &tokio_postgres::Row,
+    entity: &'static str,
+    columns: &[&str],
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    let (idx, column) = columns
+        .iter()
+        .find_map(|column| {
+            row.columns()
+                .iter()
+                .position(|c| c.name() == *column)
+                .map(|idx| (idx, *column))
+        })
+        .ok_or_else(|| RowMapperError::missing_column(entity, columns.join(" | "), expected))?;
+
+    V::from_postgres_column(row, idx).map_err(|e| {
+        let actual = postgres_column_type(row, idx).unwrap_or_default();
+        RowMapperError::mismatch(entity, column.to_owned(), expected, actual, e)
+    })
+}
+
+/// Retrieves the value of the column placed at `idx`, used by the `CanyonMapper`
+/// derive to map the fields annotated with `#[column_index(idx)]`
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `idx` - The position of the column
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "postgres")]
+pub fn try_get_postgres_column_at<V: FromColumn>(
+    row: &tokio_postgres::Row,
+    entity: &'static str,
+    idx: usize,
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    let actual = postgres_column_type(row, idx)
+        .ok_or_else(|| RowMapperError::missing_column(entity, idx.to_string(), expected))?;
+
+    V::from_postgres_column(row, idx)
+        .map_err(|e| RowMapperError::mismatch(entity, idx.to_string(), expected, actual, e))
+}
+
+/// Retrieves the value of the column named `column`, used by the `CanyonMapper`
+/// derive to map the fields of the entities
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `column` - The name of the column
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "mssql")]
+pub fn try_get_sqlserver_column<V: FromColumn>(
+    row: &tiberius::Row,
+    entity: &'static str,
+    column: &str,
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    try_get_sqlserver_column_aliased(row, entity, &[column], expected)
+}
+
+/// Retrieves the value of the first of the `columns` that's present on the row, used by
+/// the `CanyonMapper` derive to map the fields annotated with `#[column(alias = "...")]`
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `columns` - The name of the field, followed by its aliases
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "mssql")]
+pub fn try_get_sqlserver_column_aliased<V: FromColumn>(
+    row: &tiberius::Row,
+    entity: &'static str,
+    columns: &[&str],
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    let (idx, column) = columns
+        .iter()
+        .find_map(|column| {
+            row.columns()
+                .iter()
+                .position(|c| c.name() == *column)
+                .map(|idx| (idx, *column))
+        })
+        .ok_or_else(|| RowMapperError::missing_column(entity, columns.join(" | "), expected))?;
+
+    V::from_sqlserver_column(row, idx).map_err(|e| {
+        let actual = sqlserver_column_type(row, idx).unwrap_or_default();
+        RowMapperError::mismatch(entity, column.to_owned(), expected, actual, e)
+    })
+}
+
+/// Retrieves the value of the column placed at `idx`, used by the `CanyonMapper`
+/// derive to map the fields annotated with `#[column_index(idx)]`
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `idx` - The position of the column
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "mssql")]
+pub fn try_get_sqlserver_column_at<V: FromColumn>(
+    row: &tiberius::Row,
+    entity: &'static str,
+    idx: usize,
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    let actual = sqlserver_column_type(row, idx)
+        .ok_or_else(|| RowMapperError::missing_column(entity, idx.to_string(), expected))?;
+
+    V::from_sqlserver_column(row, idx)
+        .map_err(|e| RowMapperError::mismatch(entity, idx.to_string(), expected, actual, e))
+}
+
+/// Retrieves the value of the column named `column`, used by the `CanyonMapper`
+/// derive to map the fields of the entities
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `column` - The name of the column
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "mysql")]
+pub fn try_get_mysql_column<V: FromColumn>(
+    row: &mysql_async::Row,
+    entity: &'static str,
+    column: &str,
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    try_get_mysql_column_aliased(row, entity, &[column], expected)
+}
+
+/// Retrieves the value of the first of the `columns` that's present on the row, used by
+/// the `CanyonMapper` derive to map the fields annotated with `#[column(alias = "...")]`
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `columns` - The name of the field, followed by its aliases
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "mysql")]
+pub fn try_get_mysql_column_aliased<V: FromColumn>(
+    row: &mysql_async::Row,
+    entity: &'static str,
+    columns: &[&str],
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    let (idx, column) = columns
+        .iter()
+        .find_map(|column| {
+            row.columns_ref()
+                .iter()
+                .position(|c| c.name_str() == *column)
+                .map(|idx| (idx, *column))
+        })
+        .ok_or_else(|| RowMapperError::missing_column(entity, columns.join(" | "), expected))?;
+
+    V::from_mysql_column(row, idx).map_err(|e| {
+        let actual = mysql_column_type(row, idx).unwrap_or_default();
+        RowMapperError::mismatch(entity, column.to_owned(), expected, actual, e)
+    })
+}
+
+/// Retrieves the value of the column placed at `idx`, used by the `CanyonMapper`
+/// derive to map the fields annotated with `#[column_index(idx)]`
+///
+/// * `entity` - The name of the type that the row is being mapped into
+/// * `idx` - The position of the column
+/// * `expected` - The name of the Rust type of the value
+#[cfg(feature = "mysql")]
+pub fn try_get_mysql_column_at<V: FromColumn>(
+    row: &mysql_async::Row,
+    entity: &'static str,
+    idx: usize,
+    expected: &'static str,
+) -> Result<V, RowMapperError> {
+    let actual = mysql_column_type(row, idx)
+        .ok_or_else(|| RowMapperError::missing_column(entity, idx.to_string(), expected))?;
+
+    V::from_mysql_column(row, idx)
+        .map_err(|e| RowMapperError::mismatch(entity, idx.to_string(), expected, actual, e))
+}
+
+/// Turns the error of a missing column into the default value of `V`, used by the
+/// `CanyonMapper` derive to map the optional fields of the types annotated with
+/// `#[canyon_mapper(lenient)]` as `None` when their column isn't present
+pub fn default_if_missing<V: Default>(
+    result: Result<V, RowMapperError>,
+) -> Result<V, RowMapperError> {
+    match result {
+        Err(error) if error.actual.is_none() => Ok(V::default()),
+        other => other,
+    }
+}
+
+/// Declares functions that retrieves a single value from a column of a row
+/// incoming from some supported database in Canyon-SQL, by the index of
+/// the column.
+///
+/// Types implementing this trait can be used as the type of the fields of the
+/// types deriving `CanyonMapper`, and as the elements of the tuples that Canyon
+/// is able to map query results into. The user defined types gets it by
+/// implementing [`crate::types::CanyonType`]
+pub trait FromColumn: Sized {
     #[cfg(feature = "postgres")]
-    fn deserialize_postgresql(row: &tokio_postgres::Row) -> T;
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
     #[cfg(feature = "mssql")]
-    fn deserialize_sqlserver(row: &tiberius::Row) -> T;
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
     #[cfg(feature = "mysql")]
-    fn deserialize_mysql(row: &mysql_async::Row) -> T;
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
 }
+
+#[cfg(feature = "mssql")]
+fn unexpected_null(idx: usize) -> Box<dyn std::error::Error + Send + Sync> {
+    format!("Unexpected NULL value found on the column with index: {idx}").into()
+}
+
+#[cfg(feature = "mysql")]
+fn missing_column(idx: usize) -> Box<dyn std::error::Error + Send + Sync> {
+    format!("There's no column with index: {idx} on the row").into()
+}
+
+/// Implements [`FromColumn`] for types (and their optional counterparts) that
+/// are natively supported by the three database clients
+macro_rules! impl_from_column {
+    ($($ty:ty),*) => {
+        $(
+            impl FromColumn for $ty {
+                #[cfg(feature = "postgres")]
+                fn from_postgres_column(
+                    row: &tokio_postgres::Row,
+                    idx: usize,
+                ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(row.try_get::<usize, Self>(idx)?)
+                }
+                #[cfg(feature = "mssql")]
+                fn from_sqlserver_column(
+                    row: &tiberius::Row,
+                    idx: usize,
+                ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                    row.try_get::<Self, usize>(idx)?
+                        .ok_or_else(|| unexpected_null(idx))
+                }
+                #[cfg(feature = "mysql")]
+                fn from_mysql_column(
+                    row: &mysql_async::Row,
+                    idx: usize,
+                ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(row.get_opt::<Self, usize>(idx).ok_or_else(|| missing_column(idx))??)
+                }
+            }
+
+            impl FromColumn for Option<$ty> {
+                #[cfg(feature = "postgres")]
+                fn from_postgres_column(
+                    row: &tokio_postgres::Row,
+                    idx: usize,
+                ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(row.try_get::<usize, Self>(idx)?)
+                }
+                #[cfg(feature = "mssql")]
+                fn from_sqlserver_column(
+                    row: &tiberius::Row,
+                    idx: usize,
+                ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(row.try_get::<$ty, usize>(idx)?)
+                }
+                #[cfg(feature = "mysql")]
+                fn from_mysql_column(
+                    row: &mysql_async::Row,
+                    idx: usize,
+                ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(row.get_opt::<Self, usize>(idx).ok_or_else(|| missing_column(idx))??)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_column!(
+    bool,
+    i16,
+    i32,
+    f32,
+    f64,
+    NaiveDate,
+    NaiveTime,
+    NaiveDateTime
+);
+
+/// Retrieves an integer of any size from a SQL Server column, given that some values,
+/// like the result of `COUNT(*)`, are reported as `INT` where the other databases
+/// reports a `BIGINT`
+#[cfg(feature = "mssql")]
+fn sqlserver_integer(
+    row: &tiberius::Row,
+    idx: usize,
+) -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+    match row.cells().nth(idx) {
+        Some((_, ColumnData::U8(v))) => Ok(v.map(i64::from)),
+        Some((_, ColumnData::I16(v))) => Ok(v.map(i64::from)),
+        Some((_, ColumnData::I32(v))) => Ok(v.map(i64::from)),
+        _ => Ok(row.try_get::<i64, usize>(idx)?),
+    }
+}
+
+impl FromColumn for i64 {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        sqlserver_integer(row, idx)?.ok_or_else(|| unexpected_null(idx))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+impl FromColumn for Option<i64> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        sqlserver_integer(row, idx)
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl_from_column!(uuid::Uuid);
+
+#[cfg(feature = "decimal")]
+impl_from_column!(rust_decimal::Decimal);
+
+impl FromColumn for String {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        row.try_get::<&str, usize>(idx)?
+            .map(str::to_owned)
+            .ok_or_else(|| unexpected_null(idx))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+impl FromColumn for Option<String> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<&str, usize>(idx)?.map(str::to_owned))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+impl FromColumn for Vec<u8> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        row.try_get::<&[u8], usize>(idx)?
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| unexpected_null(idx))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+impl FromColumn for Option<Vec<u8>> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<&[u8], usize>(idx)?.map(<[u8]>::to_vec))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+/// SQL Server doesn't have a native `JSON` type, so the values are parsed from text
+#[cfg(feature = "json")]
+impl FromColumn for serde_json::Value {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let json = row
+            .try_get::<&str, usize>(idx)?
+            .ok_or_else(|| unexpected_null(idx))?;
+        Ok(serde_json::from_str(json)?)
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+#[cfg(feature = "json")]
+impl FromColumn for Option<serde_json::Value> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .try_get::<&str, usize>(idx)?
+            .map(serde_json::from_str)
+            .transpose()?)
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row
+            .get_opt::<Self, usize>(idx)
+            .ok_or_else(|| missing_column(idx))??)
+    }
+}
+
+impl FromColumn for DateTime<Utc> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        row.try_get::<Self, usize>(idx)?
+            .ok_or_else(|| unexpected_null(idx))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        // MySQL does not store the offset, so the values are considered as UTC ones
+        NaiveDateTime::from_mysql_column(row, idx).map(|dt| dt.and_utc())
+    }
+}
+
+impl FromColumn for Option<DateTime<Utc>> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<usize, Self>(idx)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(row.try_get::<DateTime<Utc>, usize>(idx)?)
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Option::<NaiveDateTime>::from_mysql_column(row, idx).map(|dt| dt.map(|dt| dt.and_utc()))
+    }
+}
+
+/// Implements [`RowMapper`] for tuples whose elements implements [`FromColumn`],
+/// mapping every element of the tuple against the column placed at the same
+/// position on the row
+macro_rules! impl_row_mapper_for_tuple {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<$($ty: FromColumn),+> RowMapper<($($ty,)+)> for ($($ty,)+) {
+            #[cfg(feature = "postgres")]
+            fn try_deserialize_postgresql(
+                row: &tokio_postgres::Row,
+            ) -> Result<($($ty,)+), RowMapperError> {
+                let entity = std::any::type_name::<($($ty,)+)>();
+                Ok(($(
+                    $ty::from_postgres_column(row, $idx).map_err(|e| match postgres_column_type(row, $idx) {
+                        Some(actual) => RowMapperError::mismatch(
+                            entity,
+                            $idx.to_string(),
+                            std::any::type_name::<$ty>(),
+                            actual,
+                            e,
+                        ),
+                        None => RowMapperError::missing_column(
+                            entity,
+                            $idx.to_string(),
+                            std::any::type_name::<$ty>(),
+                        ),
+                    })?,
+                )+))
+            }
+            #[cfg(feature = "mssql")]
+            fn try_deserialize_sqlserver(
+                row: &tiberius::Row,
+            ) -> Result<($($ty,)+), RowMapperError> {
+                let entity = std::any::type_name::<($($ty,)+)>();
+                Ok(($(
+                    $ty::from_sqlserver_column(row, $idx).map_err(|e| match sqlserver_column_type(row, $idx) {
+                        Some(actual) => RowMapperError::mismatch(
+                            entity,
+                            $idx.to_string(),
+                            std::any::type_name::<$ty>(),
+                            actual,
+                            e,
+                        ),
+                        None => RowMapperError::missing_column(
+                            entity,
+                            $idx.to_string(),
+                            std::any::type_name::<$ty>(),
+                        ),
+                    })?,
+                )+))
+            }
+            #[cfg(feature = "mysql")]
+            fn try_deserialize_mysql(
+                row: &mysql_async::Row,
+            ) -> Result<($($ty,)+), RowMapperError> {
+                let entity = std::any::type_name::<($($ty,)+)>();
+                Ok(($(
+                    $ty::from_mysql_column(row, $idx).map_err(|e| match mysql_column_type(row, $idx) {
+                        Some(actual) => RowMapperError::mismatch(
+                            entity,
+                            $idx.to_string(),
+                            std::any::type_name::<$ty>(),
+                            actual,
+                            e,
+                        ),
+                        None => RowMapperError::missing_column(
+                            entity,
+                            $idx.to_string(),
+                            std::any::type_name::<$ty>(),
+                        ),
+                    })?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_row_mapper_for_tuple!(A => 0);
+impl_row_mapper_for_tuple!(A => 0, B => 1);
+impl_row_mapper_for_tuple!(A => 0, B => 1, C => 2);
+impl_row_mapper_for_tuple!(A => 0, B => 1, C => 2, D => 3);
+impl_row_mapper_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4);
+impl_row_mapper_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
+impl_row_mapper_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6);
+impl_row_mapper_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7);
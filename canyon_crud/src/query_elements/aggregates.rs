@@ -0,0 +1,142 @@
+use std::marker::PhantomData;
+
+use canyon_connection::canyon_database_connector::DatabaseType;
+
+use crate::{
+    bounds::FieldIdentifier,
+    crud::{CrudOperations, Transaction},
+    mapper::RowMapper,
+};
+
+/// The SQL aggregate functions available on the Canyon-SQL QueryBuilder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    /// `COUNT(*)`
+    Count,
+    /// `SUM(column)`
+    Sum,
+    /// `AVG(column)`
+    Avg,
+    /// `MIN(column)`
+    Min,
+    /// `MAX(column)`
+    Max,
+}
+
+impl AggregateFunction {
+    /// Returns the prefix of the alias given to the results of the aggregate
+    /// on the projection of the query
+    pub fn alias_prefix(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+}
+
+/// Represents an aggregate function applied over some column of the
+/// entity `T`, built by the variants of the `Fields` enum of the entity.
+///
+/// ```ignore
+/// let total = Aggregate::<League>::count();
+/// let greatest_id = Aggregate::max(LeagueField::id);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Aggregate<T> {
+    function: AggregateFunction,
+    column: Option<&'static str>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Aggregate<T>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+{
+    /// Counts the number of rows
+    pub fn count() -> Self {
+        Self {
+            function: AggregateFunction::Count,
+            column: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sum of the values of the `column`.
+    ///
+    /// > Note: the type of the result depends on the database and the type of the column
+    pub fn sum<Z: FieldIdentifier<T>>(column: Z) -> Self {
+        Self::over(AggregateFunction::Sum, column)
+    }
+
+    /// Average of the values of the `column`, always retrieved as a floating
+    /// point number (`f64`)
+    pub fn avg<Z: FieldIdentifier<T>>(column: Z) -> Self {
+        Self::over(AggregateFunction::Avg, column)
+    }
+
+    /// Minimum value of the `column`
+    pub fn min<Z: FieldIdentifier<T>>(column: Z) -> Self {
+        Self::over(AggregateFunction::Min, column)
+    }
+
+    /// Maximum value of the `column`
+    pub fn max<Z: FieldIdentifier<T>>(column: Z) -> Self {
+        Self::over(AggregateFunction::Max, column)
+    }
+
+    fn over<Z: FieldIdentifier<T>>(function: AggregateFunction, column: Z) -> Self {
+        Self {
+            function,
+            column: Some(column.as_str()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the [`AggregateFunction`] applied
+    pub fn function(&self) -> AggregateFunction {
+        self.function
+    }
+
+    /// Returns the name of the alias given to the aggregate when it's part
+    /// of the projection of the query, like `count` or `sum_<column>`
+    pub fn alias(&self) -> String {
+        match self.column {
+            Some(column) => format!("{}_{column}", self.function.alias_prefix()),
+            None => self.function.alias_prefix().to_string(),
+        }
+    }
+
+    /// Generates the SQL expression of the aggregate for the targeted database
+    pub fn as_sql(&self, datasource_type: &DatabaseType) -> String {
+        let column = self.column.unwrap_or("*");
+        match self.function {
+            AggregateFunction::Count => match datasource_type {
+                #[cfg(feature = "postgres")]
+                DatabaseType::PostgreSql => format!("COUNT({column})"),
+                // The `COUNT` function returns an `INT` on SqlServer, so we
+                // use `COUNT_BIG` to be able to always retrieve an `i64`
+                #[cfg(feature = "mssql")]
+                DatabaseType::SqlServer => format!("COUNT_BIG({column})"),
+                #[cfg(feature = "mysql")]
+                DatabaseType::MySQL => format!("COUNT({column})"),
+            },
+            AggregateFunction::Sum => format!("SUM({column})"),
+            AggregateFunction::Avg => {
+                let float_type = match datasource_type {
+                    #[cfg(feature = "postgres")]
+                    DatabaseType::PostgreSql => "DOUBLE PRECISION",
+                    #[cfg(feature = "mssql")]
+                    DatabaseType::SqlServer => "FLOAT",
+                    #[cfg(feature = "mysql")]
+                    DatabaseType::MySQL => "DOUBLE",
+                };
+                format!("CAST(AVG({column}) AS {float_type})")
+            }
+            AggregateFunction::Min => format!("MIN({column})"),
+            AggregateFunction::Max => format!("MAX({column})"),
+        }
+    }
+}
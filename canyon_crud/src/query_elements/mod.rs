@@ -1,3 +1,4 @@
+pub mod aggregates;
 pub mod operators;
 pub mod query;
 pub mod query_builder;
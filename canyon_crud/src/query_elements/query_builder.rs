@@ -1,4 +1,14 @@
-use std::fmt::Debug;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    future::Future,
+    hash::Hash,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+};
+
+use serde::de::DeserializeOwned;
 
 use canyon_connection::{
     canyon_database_connector::DatabaseType, get_database_config, DATASOURCES,
@@ -7,8 +17,11 @@ use canyon_connection::{
 use crate::{
     bounds::{FieldIdentifier, FieldValueIdentifier, QueryParameter},
     crud::{CrudOperations, Transaction},
-    mapper::RowMapper,
-    query_elements::query::Query,
+    hooks::AfterLoadHook,
+    mapper::{FromColumn, RowMapper},
+    query_elements::{aggregates::Aggregate, query::Query},
+    relations::{Loaded, Relation},
+    timestamps::{current_timestamp_sql, Clock, Timestamp},
     Operator,
 };
 
@@ -143,6 +156,14 @@ where
     query: Query<'a, T>,
     datasource_name: &'a str,
     datasource_type: DatabaseType,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    keyset_order: Option<(&'static str, bool)>,
+    keyset_condition: Option<(String, usize)>,
+    pagination_idx: Option<usize>,
+    columns: Vec<&'static str>,
+    group_columns: Vec<&'static str>,
+    aggregates: Vec<String>,
 }
 
 unsafe impl<'a, T> Send for QueryBuilder<'a, T> where
@@ -154,6 +175,14 @@ unsafe impl<'a, T> Sync for QueryBuilder<'a, T> where
 {
 }
 
+/// The kind of statement whose modified rows are returned by
+/// the [`QueryBuilder::query_returning`]
+#[derive(Debug, Clone, Copy)]
+enum Modification {
+    Update,
+    Delete,
+}
+
 impl<'a, T> QueryBuilder<'a, T>
 where
     T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
@@ -166,6 +195,14 @@ where
             datasource_type: DatabaseType::from(
                 &get_database_config(datasource_name, &DATASOURCES).auth,
             ),
+            limit: None,
+            offset: None,
+            keyset_order: None,
+            keyset_condition: None,
+            pagination_idx: None,
+            columns: Vec::new(),
+            group_columns: Vec::new(),
+            aggregates: Vec::new(),
         }
     }
 
@@ -182,7 +219,148 @@ where
             self.datasource_name,
         )
         .await?
-        .into_results::<T>())
+        .into_results::<T>()?)
+    }
+
+    /// Executes the generated statement against the database targeted
+    /// by the selected datasource, returning the number of affected rows
+    pub async fn execute(
+        &'a mut self,
+    ) -> Result<u64, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.query.sql.push(';');
+
+        T::execute(
+            self.query.sql.clone(),
+            self.query.params.to_vec(),
+            self.datasource_name,
+        )
+        .await
+    }
+
+    /// Launches the generated *UPDATE* or *DELETE* statement against the database
+    /// targeted by the selected datasource, returning the modified rows.
+    ///
+    /// Postgres returns them with a `RETURNING *` clause, and SqlServer with an
+    /// `OUTPUT` one, while on MySQL the rows are locked and selected before the
+    /// statement is executed (and selected again by their primary key after an
+    /// update), all of it inside a database transaction
+    ///
+    /// The [`crate::hooks::CanyonHooks::after_load`] hook is called on every returned entity
+    ///
+    /// * `modification` - The kind of the generated statement
+    /// * `table_schema_data` - The table modified by the statement
+    #[cfg_attr(
+        not(all(feature = "mssql", feature = "mysql")),
+        allow(unused_variables)
+    )]
+    async fn query_returning(
+        &'a mut self,
+        modification: Modification,
+        table_schema_data: &str,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let rows = match self.datasource_type {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => {
+                self.query.sql.push_str(" RETURNING *;");
+                T::query(
+                    self.query.sql.clone(),
+                    self.query.params.to_vec(),
+                    self.datasource_name,
+                )
+                .await?
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => {
+                insert_output_clause(&mut self.query.sql, modification);
+                self.query.sql.push(';');
+                T::query(
+                    self.query.sql.clone(),
+                    self.query.params.to_vec(),
+                    self.datasource_name,
+                )
+                .await?
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseType::MySQL => {
+                // The updated rows must be selected again, so they're identified by their primary key
+                let primary_key = match modification {
+                    Modification::Update => Some(T::primary_key().ok_or(
+                        "You can't use the 'returning' method of the UpdateQueryBuilder on \
+                        MySQL for a CanyonEntity that does not have a #[primary_key] annotation.",
+                    )?),
+                    Modification::Delete => None,
+                };
+                let where_clause_position =
+                    where_clause_position(&self.query.sql).unwrap_or(self.query.sql.len());
+                let select_stmt = format!(
+                    "SELECT * FROM {table_schema_data}{}",
+                    &self.query.sql[where_clause_position..]
+                );
+
+                crate::crud::mysql_modify_returning::<T>(
+                    select_stmt,
+                    self.query.sql.clone(),
+                    table_schema_data,
+                    primary_key,
+                    self.query.params.as_slice(),
+                    self.datasource_name,
+                )
+                .await?
+            }
+        };
+
+        let mut results = rows.into_results::<T>()?;
+        crate::hooks::after_load_all(&mut results).await?;
+        Ok(results)
+    }
+
+    /// Launches the generated query against the database targeted
+    /// by the selected datasource, mapping the results into `R`
+    pub async fn query_as<R: RowMapper<R>>(
+        &'a mut self,
+    ) -> Result<Vec<R>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.query.sql.push(';');
+
+        Ok(T::query(
+            self.query.sql.clone(),
+            self.query.params.to_vec(),
+            self.datasource_name,
+        )
+        .await?
+        .into_results_as::<R>()?)
+    }
+
+    /// Launches the generated query against the database targeted by the selected
+    /// datasource, mapping the results into `R` along with the value of their `key_column`
+    async fn query_keyed<R: RowMapper<R>, K: FromColumn>(
+        &'a mut self,
+        key_column: &str,
+    ) -> Result<Vec<(K, R)>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.query.sql.push(';');
+
+        Ok(T::query(
+            self.query.sql.clone(),
+            self.query.params.to_vec(),
+            self.datasource_name,
+        )
+        .await?
+        .into_keyed_results::<R, K>(key_column)?)
+    }
+
+    /// Launches the generated query against the database targeted
+    /// by the selected datasource, deserializing the results into `D` with `serde`
+    pub async fn deserialize<D: DeserializeOwned>(
+        &'a mut self,
+    ) -> Result<Vec<D>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.query.sql.push(';');
+
+        Ok(T::query(
+            self.query.sql.clone(),
+            self.query.params.to_vec(),
+            self.datasource_name,
+        )
+        .await?
+        .deserialize::<D>()?)
     }
 
     pub fn r#where<Z: FieldValueIdentifier<'a, T>>(&mut self, r#where: Z, op: impl Operator) {
@@ -192,7 +370,7 @@ where
             + column_name
             + &op.as_str(self.query.params.len() + 1, &self.datasource_type);
 
-        self.query.sql.push_str(&where_);
+        self.push_clause(&where_);
         self.query.params.push(value);
     }
 
@@ -203,7 +381,7 @@ where
             + column_name
             + &op.as_str(self.query.params.len() + 1, &self.datasource_type);
 
-        self.query.sql.push_str(&and_);
+        self.push_clause(&and_);
         self.query.params.push(value);
     }
 
@@ -214,7 +392,7 @@ where
             + column_name
             + &op.as_str(self.query.params.len() + 1, &self.datasource_type);
 
-        self.query.sql.push_str(&and_);
+        self.push_clause(&and_);
         self.query.params.push(value);
     }
 
@@ -223,66 +401,339 @@ where
         Z: FieldIdentifier<T>,
         Q: QueryParameter<'a>,
     {
+        self.values_in("AND", r#and.as_str(), values)
+    }
+
+    fn or_values_in<Z, Q>(&mut self, r#or: Z, values: &'a [Q])
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self.values_in("OR", r#or.as_str(), values)
+    }
+
+    /// Appends an `IN` condition over the `column`, joined with the rest of the
+    /// conditions by the `connector`, or starting the *WHERE* clause if there's
+    /// no previous condition
+    fn values_in<Q: QueryParameter<'a>>(&mut self, connector: &str, column: &str, values: &'a [Q]) {
         if values.is_empty() {
             return;
         }
 
-        self.query
-            .sql
-            .push_str(&format!(" AND {} IN (", r#and.as_str()));
-
-        let mut counter = 1;
-        values.iter().for_each(|qp| {
-            if values.len() != counter {
-                self.query
-                    .sql
-                    .push_str(&format!("${}, ", self.query.params.len()));
-                counter += 1;
-            } else {
-                self.query
-                    .sql
-                    .push_str(&format!("${}", self.query.params.len()));
-            }
-            self.query.params.push(qp)
-        });
+        let connector = if self.has_where_clause() {
+            connector
+        } else {
+            "WHERE"
+        };
+        let placeholders = values
+            .iter()
+            .map(|qp| {
+                self.query.params.push(qp);
+                format!("${}", self.query.params.len())
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        self.push_clause(&format!(" {connector} {column} IN ({placeholders})"));
+    }
 
-        self.query.sql.push(')')
+    /// Appends a `condition` over a single `value`, joined with the rest of the conditions
+    /// by `AND`, or starting the *WHERE* clause if there's no previous condition.
+    ///
+    /// The `condition` receives the placeholder of the value, so it can be placed anywhere
+    /// within it, like inside of a subquery
+    pub(crate) fn and_condition(
+        &mut self,
+        condition: impl FnOnce(&str) -> String,
+        value: &'a dyn QueryParameter<'a>,
+    ) {
+        let connector = if self.has_where_clause() {
+            "AND"
+        } else {
+            "WHERE"
+        };
+        self.query.params.push(value);
+        let placeholder = format!("${}", self.query.params.len());
+
+        self.push_clause(&format!(" {connector} {}", condition(&placeholder)));
     }
 
-    fn or_values_in<Z, Q>(&mut self, r#or: Z, values: &'a [Q])
+    /// Sets the maximum number of rows that the query will return
+    pub fn limit(&mut self, limit: usize) {
+        self.limit = Some(limit);
+        self.render_pagination();
+    }
+
+    /// Sets the number of rows that will be skipped before start to return rows
+    pub fn offset(&mut self, offset: usize) {
+        self.offset = Some(offset);
+        self.render_pagination();
+    }
+
+    /// Generates the keyset pagination clauses, that filters the rows placed after
+    /// the provided `value` of the `column`, and orders them by that column, in
+    /// descending order if `desc` is true.
+    ///
+    /// The ordering is rendered along with the pagination clause, at the end of
+    /// the SQL sentence. The rows can't be ordered by other columns, because the
+    /// filter only selects the next rows if the key column is the first sort key
+    pub fn after<Z, Q>(&mut self, column: Z, value: &'a Q, desc: bool)
     where
         Z: FieldIdentifier<T>,
         Q: QueryParameter<'a>,
     {
-        if values.is_empty() {
-            return;
+        assert!(
+            !self.has_order_by_clause(),
+            "The keyset pagination orders the rows by its key column, \
+            so it can't be combined with an `order_by`"
+        );
+        let clause = if self.has_where_clause() {
+            " AND "
+        } else {
+            " WHERE "
+        };
+        let column_name = column.as_str();
+        let op = if desc { "<" } else { ">" };
+        let condition = format!("{column_name} {op} ${}", self.query.params.len() + 1);
+
+        self.keyset_order = Some((column_name, desc));
+        self.keyset_condition = Some((condition.clone(), self.query.params.len()));
+        self.push_clause(&format!("{clause}{condition}"));
+        self.query.params.push(value);
+    }
+
+    /// Adds the [`Aggregate`] to the projection of the query, aliased
+    /// with the name returned by [`Aggregate::alias`]
+    pub fn aggregate(&mut self, aggregate: Aggregate<T>) {
+        self.aggregates.push(format!(
+            "{} AS {}",
+            aggregate.as_sql(&self.datasource_type),
+            aggregate.alias()
+        ));
+        self.render_projection();
+    }
+
+    /// Restricts the projection of the query to the provided columns
+    pub fn project(&mut self, columns: Vec<&'static str>) {
+        self.columns = columns;
+        self.render_projection();
+    }
+
+    /// Generates (or extends) the `GROUP BY` clause with the provided column,
+    /// that will be also part of the projection of the query
+    pub fn group_by<Z: FieldIdentifier<T>>(&mut self, column: Z) {
+        let column_name = column.as_str();
+        if self.group_columns.is_empty() {
+            self.push_clause(&format!(" GROUP BY {column_name}"));
+        } else {
+            self.push_clause(&format!(", {column_name}"));
         }
 
+        self.group_columns.push(column_name);
+        self.render_projection();
+    }
+
+    /// Generates a `HAVING` SQL clause (or an `AND` one if the clause already
+    /// exists) that constraints the groups by the result of the [`Aggregate`]
+    pub fn having<Q: QueryParameter<'a>>(
+        &mut self,
+        aggregate: Aggregate<T>,
+        op: impl Operator,
+        value: &'a Q,
+    ) {
+        let clause = if self.query.sql.contains(" HAVING ") {
+            " AND "
+        } else {
+            " HAVING "
+        };
+
+        let having_ = String::from(clause)
+            + &aggregate.as_sql(&self.datasource_type)
+            + &op.as_str(self.query.params.len() + 1, &self.datasource_type);
+
+        self.push_clause(&having_);
+        self.query.params.push(value);
+    }
+
+    /// Rewrites the projection of the *SELECT* statement with the projected (or
+    /// the grouped) columns and the aggregates of the query, or `*` if there's
+    /// none of them
+    fn render_projection(&mut self) {
+        let columns = if self.columns.is_empty() {
+            &self.group_columns
+        } else {
+            &self.columns
+        };
+
+        let projection = if columns.is_empty() && self.aggregates.is_empty() {
+            String::from("*")
+        } else {
+            columns
+                .iter()
+                .map(|column| column.to_string())
+                .chain(self.aggregates.iter().cloned())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let from_idx = self
+            .query
+            .sql
+            .find(" FROM ")
+            .expect("The projection is only available for SELECT statements");
+        let select_clause = format!("SELECT {projection}");
+
+        if let Some(idx) = self.pagination_idx.as_mut() {
+            *idx = *idx + select_clause.len() - from_idx;
+        }
+        self.query.sql.replace_range(..from_idx, &select_clause);
+    }
+
+    /// Returns true if the SQL sentence already contains a `WHERE` clause,
+    /// ignoring the ones that belongs to subqueries
+    fn has_where_clause(&self) -> bool {
+        where_clause_position(&self.query.sql).is_some()
+    }
+
+    /// Returns true if the SQL sentence already contains an `ORDER BY` clause,
+    /// ignoring the one rendered along with the pagination clauses
+    fn has_order_by_clause(&self) -> bool {
+        let end = self.pagination_idx.unwrap_or(self.query.sql.len());
+        self.query.sql[..end].contains(" ORDER BY ")
+    }
+
+    /// Replaces the source of the rows (the table or the subquery placed after
+    /// the `FROM` keyword) of the *SELECT* statement
+    fn replace_source(&mut self, old_source: &str, new_source: &str) {
+        let source_idx = self
+            .query
+            .sql
+            .find(" FROM ")
+            .expect("The source of the rows can only be replaced on SELECT statements")
+            + " FROM ".len();
+        debug_assert!(self.query.sql[source_idx..].starts_with(old_source));
+
+        if let Some(idx) = self.pagination_idx.as_mut() {
+            *idx = *idx + new_source.len() - old_source.len();
+        }
         self.query
             .sql
-            .push_str(&format!(" OR {} IN (", r#or.as_str()));
-
-        let mut counter = 1;
-        values.iter().for_each(|qp| {
-            if values.len() != counter {
-                self.query
-                    .sql
-                    .push_str(&format!("${}, ", self.query.params.len()));
-                counter += 1;
-            } else {
-                self.query
-                    .sql
-                    .push_str(&format!("${}", self.query.params.len()));
+            .replace_range(source_idx..source_idx + old_source.len(), new_source);
+    }
+
+    /// Appends a clause to the SQL sentence, keeping the pagination clause (if any)
+    /// at the end of it
+    fn push_clause(&mut self, clause: &str) {
+        if let Some(idx) = self.pagination_idx.take() {
+            self.query.sql.truncate(idx);
+        }
+        self.query.sql.push_str(clause);
+        self.render_pagination();
+    }
+
+    /// Writes (or rewrites, if it was already written) the pagination clauses at the
+    /// end of the SQL sentence, with the correct syntax for the targeted database.
+    ///
+    /// The ordering of the keyset pagination (if any) is written first
+    fn render_pagination(&mut self) {
+        if let Some(idx) = self.pagination_idx.take() {
+            self.query.sql.truncate(idx);
+        }
+        if self.limit.is_none() && self.offset.is_none() && self.keyset_order.is_none() {
+            return;
+        }
+        self.pagination_idx = Some(self.query.sql.len());
+
+        if let Some((column_name, desc)) = self.keyset_order {
+            self.query.sql.push_str(&format!(" ORDER BY {column_name}"));
+            if desc {
+                self.query.sql.push_str(" DESC");
             }
-            self.query.params.push(qp)
-        });
+        }
+        if self.limit.is_none() && self.offset.is_none() {
+            return;
+        }
 
-        self.query.sql.push(')')
+        let clause = match self.datasource_type {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => {
+                let mut clause = String::new();
+                if let Some(limit) = self.limit {
+                    clause.push_str(&format!(" LIMIT {limit}"));
+                }
+                if let Some(offset) = self.offset {
+                    clause.push_str(&format!(" OFFSET {offset}"));
+                }
+                clause
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => {
+                // SqlServer only allows the OFFSET-FETCH clause along with an ORDER BY one
+                let mut clause = if self.query.sql.contains(" ORDER BY ") {
+                    String::new()
+                } else {
+                    String::from(" ORDER BY (SELECT NULL)")
+                };
+                clause.push_str(&format!(" OFFSET {} ROWS", self.offset.unwrap_or_default()));
+                if let Some(limit) = self.limit {
+                    clause.push_str(&format!(" FETCH NEXT {limit} ROWS ONLY"));
+                }
+                clause
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseType::MySQL => {
+                // MySQL does not accept an OFFSET without a LIMIT, so the max
+                // value allowed for the LIMIT is used when no one is provided
+                let mut clause = format!(" LIMIT {}", self.limit.unwrap_or(u64::MAX as usize));
+                if let Some(offset) = self.offset {
+                    clause.push_str(&format!(" OFFSET {offset}"));
+                }
+                clause
+            }
+        };
+
+        self.query.sql.push_str(&clause);
+    }
+
+    /// Performs a `COUNT(*)` query with the same filters of the generated
+    /// query, discarding the ordering, the pagination clauses and the condition
+    /// of the keyset pagination, that only selects the rows of the page
+    async fn count_with_filters(
+        &self,
+    ) -> Result<i64, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let mut sql = self.query.sql.clone();
+        let mut params = self.query.params.to_vec();
+        if let Some(idx) = self.pagination_idx {
+            sql.truncate(idx);
+        }
+        if let Some((condition, param_idx)) = &self.keyset_condition {
+            sql = remove_placeholder(&sql.replacen(condition, "1 = 1", 1), param_idx + 1);
+            params.remove(*param_idx);
+        }
+        if let Some(idx) = sql.find(" ORDER BY ") {
+            sql.truncate(idx);
+        }
+        let sql = if self.group_columns.is_empty() {
+            let from_idx = sql.find(" FROM ").unwrap_or_default();
+            format!("SELECT COUNT(*){};", &sql[from_idx..])
+        } else {
+            // Counts the number of groups instead of the number of rows
+            format!("SELECT COUNT(*) FROM ({sql}) AS grouped_rows;")
+        };
+
+        T::query(sql, params, self.datasource_name)
+            .await?
+            .into_scalar::<i64>()
     }
 
     #[inline]
     pub fn order_by<Z: FieldIdentifier<T>>(&mut self, order_by: Z, desc: bool) {
-        self.query.sql.push_str(
+        assert!(
+            self.keyset_order.is_none(),
+            "The keyset pagination orders the rows by its key column, \
+            so it can't be combined with an `order_by`"
+        );
+        self.push_clause(
             &(format!(
                 " ORDER BY {}{}",
                 order_by.as_str(),
@@ -292,12 +743,90 @@ where
     }
 }
 
+/// Decrements the number of the placeholders placed after the `removed` one,
+/// once its parameter has been removed from the statement
+fn remove_placeholder(sql: &str, removed: usize) -> String {
+    regex::Regex::new(r"\$(\d+)")
+        .expect("Error creating the regex for the placeholders")
+        .replace_all(sql, |captures: &regex::Captures| {
+            match captures[1].parse::<usize>() {
+                Ok(number) if number > removed => format!("${}", number - 1),
+                _ => captures[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Returns the position of the *WHERE* clause of the statement, ignoring
+/// the ones that belongs to the subqueries
+fn where_clause_position(sql: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in sql.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ' ' if depth == 0 && sql[idx..].starts_with(" WHERE ") => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Inserts the SqlServer `OUTPUT` clause, that returns the modified rows, right
+/// before the *WHERE* clause of the statement (or at its end, if there's none)
+#[cfg(feature = "mssql")]
+fn insert_output_clause(sql: &mut String, modification: Modification) {
+    let output = match modification {
+        Modification::Update => " OUTPUT inserted.*",
+        Modification::Delete => " OUTPUT deleted.*",
+    };
+    let position = where_clause_position(sql).unwrap_or(sql.len());
+    sql.insert_str(position, output);
+}
+
+/// Contains the specific database operations of the *SELECT* SQL statements.
+///
+/// The results of the query are mapped into `R`, which is the queried entity `T`
+/// unless a projection over some of its columns is made with [`Self::project`]
 #[derive(Debug, Clone)]
-pub struct SelectQueryBuilder<'a, T>
+pub struct SelectQueryBuilder<'a, T, R = T>
 where
     T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+    R: RowMapper<R>,
 {
     _inner: QueryBuilder<'a, T>,
+    _projection: PhantomData<R>,
+    soft_delete: Option<SoftDelete>,
+    after_load: Option<AfterLoadHook<R>>,
+}
+
+/// Holds the data of the `#[soft_delete]` column of an entity, needed by
+/// the [`SelectQueryBuilder`] to filter the soft deleted rows
+#[derive(Debug, Clone)]
+struct SoftDelete {
+    table_schema_data: String,
+    column: &'static str,
+    source: String,
+}
+
+impl SoftDelete {
+    /// Generates the source of the rows of the query. The rows are filtered
+    /// within a subquery (aliased with the name of the table), so the rest of
+    /// the clauses of the query can't include the filtered rows again
+    fn source(&self, condition: Option<&str>) -> String {
+        match condition {
+            Some(condition) => format!(
+                "(SELECT * FROM {} WHERE {} {condition}) AS {}",
+                self.table_schema_data,
+                self.column,
+                self.table_schema_data
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or(&self.table_schema_data)
+            ),
+            None => self.table_schema_data.clone(),
+        }
+    }
 }
 
 impl<'a, T> SelectQueryBuilder<'a, T>
@@ -311,16 +840,167 @@ where
                 Query::new(format!("SELECT * FROM {table_schema_data}")),
                 datasource_name,
             ),
+            _projection: PhantomData,
+            soft_delete: None,
+            after_load: Some(|instance| instance.after_load()),
+        }
+    }
+
+    /// Generates a new public instance of the [`SelectQueryBuilder`] for an entity
+    /// with a `#[soft_delete]` field, that excludes the soft deleted rows unless
+    /// [`Self::with_deleted`] or [`Self::only_deleted`] are used
+    pub fn new_with_soft_delete(
+        table_schema_data: &str,
+        soft_delete_column: &'static str,
+        datasource_name: &'a str,
+    ) -> Self {
+        let mut soft_delete = SoftDelete {
+            table_schema_data: table_schema_data.to_string(),
+            column: soft_delete_column,
+            source: String::new(),
+        };
+        soft_delete.source = soft_delete.source(Some("IS NULL"));
+
+        Self {
+            _inner: QueryBuilder::<T>::new(
+                Query::new(format!("SELECT * FROM {}", soft_delete.source)),
+                datasource_name,
+            ),
+            _projection: PhantomData,
+            soft_delete: Some(soft_delete),
+            after_load: Some(|instance| instance.after_load()),
+        }
+    }
+
+    /// Restricts the columns retrieved by the query to the provided ones,
+    /// mapping the results into `R`, a lighter type that only needs
+    /// to implement [`RowMapper`] (usually, by deriving `CanyonMapper`)
+    ///
+    /// ```ignore
+    /// let summaries: Vec<LeagueSummary> = League::select_query()
+    ///     .project::<LeagueSummary>(&[LeagueField::id, LeagueField::name])
+    ///     .query()
+    ///     .await?;
+    /// ```
+    ///
+    /// Panics if some of the columns expected by `R` (see [`RowMapper::columns`])
+    /// isn't part of the projected ones
+    ///
+    /// * `columns` - The [`FieldIdentifier`] variants of the columns to retrieve
+    pub fn project<R: RowMapper<R>>(
+        mut self,
+        columns: &[impl FieldIdentifier<T>],
+    ) -> SelectQueryBuilder<'a, T, R> {
+        let columns = columns
+            .iter()
+            .map(|column| column.as_str())
+            .collect::<Vec<&'static str>>();
+
+        let missing_columns = R::columns()
+            .iter()
+            .filter(|column| !columns.contains(column))
+            .copied()
+            .collect::<Vec<&str>>();
+        if !missing_columns.is_empty() {
+            panic!(
+                "\n\t[PANIC!] - The projected type `{}` expects the columns: {:?}, \
+                that aren't part of the projection: {:?}\n",
+                std::any::type_name::<R>(),
+                missing_columns,
+                columns
+            )
+        }
+
+        self._inner.project(columns);
+        SelectQueryBuilder {
+            _inner: self._inner,
+            _projection: PhantomData,
+            soft_delete: self.soft_delete,
+            // The projected types aren't entities, so they don't have hooks
+            after_load: None,
         }
     }
 
+    /// Loads the entities referenced by the `relation` along with the queried ones,
+    /// retrieving all of them with a single additional query, instead of one query
+    /// per queried entity
+    ///
+    /// ```ignore
+    /// let tournaments: Vec<Loaded<Tournament, League>> = Tournament::select_query()
+    ///     .with(TournamentRelation::league)
+    ///     .query()
+    ///     .await?;
+    /// ```
+    ///
+    /// The returned [`EagerSelectQueryBuilder`] can be chained with another `with`,
+    /// to load the entities referenced by several relations. See [`EagerSelectQueryBuilder::with`]
+    ///
+    /// * `relation` - The [`Relation`] of a `#[foreign_key]` field of the entity
+    pub fn with<U, K>(
+        self,
+        relation: Relation<T, U, K>,
+    ) -> EagerSelectQueryBuilder<'a, T, Loaded<T, U>>
+    where
+        T: 'static,
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U> + 'static,
+        K: for<'q> QueryParameter<'q> + FromColumn + Eq + Hash + Clone + 'static,
+    {
+        let datasource_name = self._inner.datasource_name.to_owned();
+        EagerSelectQueryBuilder {
+            select: self,
+            load_relations: Arc::new(move |entities: Vec<T>| {
+                let datasource_name = datasource_name.clone();
+                Box::pin(async move {
+                    let related = load_related(&entities, relation, &datasource_name).await?;
+                    Ok(entities
+                        .into_iter()
+                        .zip(related)
+                        .map(|(entity, related)| Loaded { entity, related })
+                        .collect())
+                })
+            }),
+        }
+    }
+
+    /// Appends a condition over a single value. See [`QueryBuilder::and_condition`]
+    pub(crate) fn and_condition(
+        &mut self,
+        condition: impl FnOnce(&str) -> String,
+        value: &'a dyn QueryParameter<'a>,
+    ) -> &mut Self {
+        self._inner.and_condition(condition, value);
+        self
+    }
+}
+
+impl<'a, T, R> SelectQueryBuilder<'a, T, R>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+    R: RowMapper<R>,
+{
     /// Launches the generated query to the database pointed by the
-    /// selected datasource
-    #[inline]
+    /// selected datasource, calling the [`crate::hooks::CanyonHooks::after_load`] hook
+    /// on every retrieved entity
     pub async fn query(
         &'a mut self,
-    ) -> Result<Vec<T>, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
-        self._inner.query().await
+    ) -> Result<Vec<R>, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
+        let after_load = self.after_load;
+        let mut results = self._inner.query_as::<R>().await?;
+        Self::run_after_load(after_load, &mut results).await?;
+        Ok(results)
+    }
+
+    /// Calls the `after_load` hook (if any) on every retrieved element
+    async fn run_after_load(
+        after_load: Option<AfterLoadHook<R>>,
+        results: &mut [R],
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        if let Some(after_load) = after_load {
+            for result in results.iter_mut() {
+                after_load(result).await?;
+            }
+        }
+        Ok(())
     }
 
     /// Adds a *LEFT JOIN* SQL statement to the underlying
@@ -386,11 +1066,388 @@ where
             .push_str(&format!(" FULL JOIN {join_table} ON {col1} = {col2}"));
         self
     }
+
+    /// Includes the soft deleted rows on the results of the query.
+    ///
+    /// Has no effect if the entity does not have a `#[soft_delete]` field
+    pub fn with_deleted(&mut self) -> &mut Self {
+        self.set_soft_delete_condition(None);
+        self
+    }
+
+    /// Retrieves only the soft deleted rows.
+    ///
+    /// Has no effect if the entity does not have a `#[soft_delete]` field
+    pub fn only_deleted(&mut self) -> &mut Self {
+        self.set_soft_delete_condition(Some("IS NOT NULL"));
+        self
+    }
+
+    fn set_soft_delete_condition(&mut self, condition: Option<&str>) {
+        if let Some(soft_delete) = self.soft_delete.as_mut() {
+            let new_source = soft_delete.source(condition);
+            self._inner.replace_source(&soft_delete.source, &new_source);
+            soft_delete.source = new_source;
+        }
+    }
+
+    /// Limits the number of rows returned by the query.
+    ///
+    /// The pagination clauses are rendered at the end of the SQL sentence, so they
+    /// must be the last ones in the chain of calls of the builder
+    ///
+    /// * `limit` - The maximum number of rows to retrieve
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self._inner.limit(limit);
+        self
+    }
+
+    /// Skips the first `offset` rows of the query result.
+    ///
+    /// > Note: SqlServer requires an `ORDER BY` clause for use `OFFSET`, so
+    /// > `ORDER BY (SELECT NULL)` is generated when the query does not have one
+    ///
+    /// * `offset` - The number of rows to skip
+    pub fn offset(&mut self, offset: usize) -> &mut Self {
+        self._inner.offset(offset);
+        self
+    }
+
+    /// Generates the `LIMIT` and `OFFSET` clauses needed to retrieve the
+    /// requested page of results, where:
+    ///
+    /// * `page` - The index of the page, starting at zero
+    /// * `size` - The number of elements per page
+    pub fn page(&mut self, page: usize, size: usize) -> &mut Self {
+        self._inner.limit(size);
+        self._inner.offset(page.saturating_mul(size));
+        self
+    }
+
+    /// Keyset pagination. Filters the rows whose `column` value is greater than
+    /// `value` (generally, the last one of the previous page), ordering the
+    /// results by that column. Combine it with [`Self::limit`] to fetch the next page.
+    ///
+    /// > Note: The rows are only ordered by the key column, so it panics if the
+    /// > query is already ordered with [`Self::order_by`], or if it's ordered later
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column used as the key
+    /// * `value` - The last value of the key column already retrieved
+    pub fn after<Z, Q>(&mut self, column: Z, value: &'a Q) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.after(column, value, false);
+        self
+    }
+
+    /// Same as [`Self::after`], but for the pages ordered by the `column` in
+    /// descending order, so the rows whose `column` value is lower than `value`
+    /// are the retrieved ones
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column used as the key
+    /// * `value` - The last value of the key column already retrieved
+    pub fn after_desc<Z, Q>(&mut self, column: Z, value: &'a Q) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.after(column, value, true);
+        self
+    }
+
+    /// Adds a `COUNT(*)` to the projection of the query, aliased as `count`.
+    ///
+    /// The results of the queries with aggregates must be retrieved with
+    /// [`Self::query_as`], mapping them into tuples or into a type that
+    /// implements [`RowMapper`]. The grouped columns (if any) are placed
+    /// first on the projection, followed by the aggregates in the same order
+    /// that they were declared
+    pub fn count(&mut self) -> &mut Self {
+        self._inner.aggregate(Aggregate::count());
+        self
+    }
+
+    /// Adds a `SUM(column)` to the projection of the query, aliased as `sum_<column>`
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column to aggregate
+    pub fn sum<Z: FieldIdentifier<T>>(&mut self, column: Z) -> &mut Self {
+        self._inner.aggregate(Aggregate::sum(column));
+        self
+    }
+
+    /// Adds an `AVG(column)` to the projection of the query, aliased as `avg_<column>`.
+    /// The average is always retrieved as a double precision floating point number
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column to aggregate
+    pub fn avg<Z: FieldIdentifier<T>>(&mut self, column: Z) -> &mut Self {
+        self._inner.aggregate(Aggregate::avg(column));
+        self
+    }
+
+    /// Adds a `MIN(column)` to the projection of the query, aliased as `min_<column>`
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column to aggregate
+    pub fn min<Z: FieldIdentifier<T>>(&mut self, column: Z) -> &mut Self {
+        self._inner.aggregate(Aggregate::min(column));
+        self
+    }
+
+    /// Adds a `MAX(column)` to the projection of the query, aliased as `max_<column>`
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column to aggregate
+    pub fn max<Z: FieldIdentifier<T>>(&mut self, column: Z) -> &mut Self {
+        self._inner.aggregate(Aggregate::max(column));
+        self
+    }
+
+    /// Generates a `GROUP BY` SQL clause with the provided column, or appends
+    /// it to the existing one. The grouped columns are included in the projection
+    ///
+    /// * `column` - A [`FieldIdentifier`] with the column to group by
+    pub fn group_by<Z: FieldIdentifier<T>>(&mut self, column: Z) -> &mut Self {
+        self._inner.group_by(column);
+        self
+    }
+
+    /// Generates a `HAVING` SQL clause for constraint the groups of the query.
+    /// Subsequent calls are chained with an `AND` operator
+    ///
+    /// * `aggregate` - The [`Aggregate`] whose result will be compared
+    /// * `op` - Any element that implements [`Operator`] for create the comparison
+    ///   or equality binary operator
+    /// * `value` - The value to compare against
+    pub fn having<Q: QueryParameter<'a>>(
+        &mut self,
+        aggregate: Aggregate<T>,
+        op: impl Operator,
+        value: &'a Q,
+    ) -> &mut Self {
+        self._inner.having(aggregate, op, value);
+        self
+    }
+
+    /// Launches the generated query to the database pointed by the selected
+    /// datasource, mapping every row into `M`, that can be a tuple of
+    /// supported types or any type that implements [`RowMapper`]
+    #[inline]
+    pub async fn query_as<M: RowMapper<M>>(
+        &'a mut self,
+    ) -> Result<Vec<M>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner.query_as::<M>().await
+    }
+
+    /// Launches the generated query to the database pointed by the selected
+    /// datasource, deserializing every row into `D` with `serde`, matching the
+    /// names of the columns against the fields of `D`
+    #[inline]
+    pub async fn deserialize<D: DeserializeOwned>(
+        &'a mut self,
+    ) -> Result<Vec<D>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner.deserialize::<D>().await
+    }
+
+    /// Launches the generated query with the pagination clauses for the requested
+    /// page, along with a `COUNT(*)` query that uses the same filters, returning
+    /// the results wrapped in a [`Page`]
+    ///
+    /// * `page` - The index of the page, starting at zero
+    /// * `size` - The number of elements per page
+    pub async fn paginate(
+        &'a mut self,
+        page: usize,
+        size: usize,
+    ) -> Result<Page<R>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.page(page, size);
+        let after_load = self.after_load;
+        let total = self._inner.count_with_filters().await?;
+        let mut items = self._inner.query_as::<R>().await?;
+        Self::run_after_load(after_load, &mut items).await?;
+
+        Ok(Page {
+            items,
+            total,
+            page,
+            size,
+        })
+    }
 }
 
-impl<'a, T> ops::QueryBuilder<'a, T> for SelectQueryBuilder<'a, T>
+/// Attaches the entities referenced by the relations of an [`EagerSelectQueryBuilder`]
+/// to the queried ones, being `L` the queried entities wrapped in as many [`Loaded`]
+/// as relations
+type LoadRelations<T, L> = Arc<
+    dyn Fn(
+            Vec<T>,
+        ) -> Pin<
+            Box<
+                dyn Future<Output = Result<Vec<L>, Box<dyn std::error::Error + Sync + Send>>>
+                    + Send,
+            >,
+        > + Send
+        + Sync,
+>;
+
+/// A [`SelectQueryBuilder`] that loads the entities referenced by one or more
+/// [`Relation`]s along with the queried ones. See [`SelectQueryBuilder::with`]
+pub struct EagerSelectQueryBuilder<'a, T, L>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+{
+    select: SelectQueryBuilder<'a, T>,
+    load_relations: LoadRelations<T, L>,
+}
+
+impl<'a, T, L> EagerSelectQueryBuilder<'a, T, L>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T> + 'static,
+    L: Send + 'static,
+{
+    /// Loads the entities referenced by another `relation` of the queried entity,
+    /// with its own additional query, wrapping the already loaded ones into a new [`Loaded`]
+    ///
+    /// ```ignore
+    /// let tournaments: Vec<Loaded<Loaded<Tournament, League>, Player>> =
+    ///     Tournament::select_query()
+    ///         .with(TournamentRelation::league)
+    ///         .with(TournamentRelation::winner)
+    ///         .query()
+    ///         .await?;
+    ///
+    /// for tournament in tournaments {
+    ///     let winner = tournament.related;
+    ///     let league = tournament.entity.related;
+    /// }
+    /// ```
+    ///
+    /// The fields and methods of the queried entity are reachable through the nested
+    /// [`Loaded`], as all of them dereferences into the wrapped one
+    ///
+    /// * `relation` - The [`Relation`] of a `#[foreign_key]` field of the entity
+    pub fn with<U, K>(
+        self,
+        relation: Relation<T, U, K>,
+    ) -> EagerSelectQueryBuilder<'a, T, Loaded<L, U>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U> + 'static,
+        K: for<'q> QueryParameter<'q> + FromColumn + Eq + Hash + Clone + 'static,
+    {
+        let datasource_name = self.select._inner.datasource_name.to_owned();
+        let load_relations = self.load_relations;
+        EagerSelectQueryBuilder {
+            select: self.select,
+            load_relations: Arc::new(move |entities: Vec<T>| {
+                let datasource_name = datasource_name.clone();
+                let load_relations = load_relations.clone();
+                Box::pin(async move {
+                    let related = load_related(&entities, relation, &datasource_name).await?;
+                    Ok(load_relations(entities)
+                        .await?
+                        .into_iter()
+                        .zip(related)
+                        .map(|(entity, related)| Loaded { entity, related })
+                        .collect())
+                })
+            }),
+        }
+    }
+
+    /// Launches the generated query to the database pointed by the selected datasource,
+    /// followed by a single query for every relation that retrieves the referenced entities,
+    /// calling the [`crate::hooks::CanyonHooks::after_load`] hook on every retrieved entity
+    pub async fn query(
+        &'a mut self,
+    ) -> Result<Vec<L>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let entities = self.select.query().await?;
+        (self.load_relations)(entities).await
+    }
+}
+
+/// Retrieves the entities referenced by the `relation` from the `entities`, with an `IN`
+/// condition over their distinct foreign keys (split in as many queries as needed to not
+/// exceed the limit of parameters of the database), calling the
+/// [`crate::hooks::CanyonHooks::after_load`] hook on every one of them.
+///
+/// Returns the referenced entity of every one of the `entities`, in the same order
+async fn load_related<T, U, K>(
+    entities: &[T],
+    relation: Relation<T, U, K>,
+    datasource_name: &str,
+) -> Result<Vec<Option<Arc<U>>>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+    K: for<'q> QueryParameter<'q> + FromColumn + Eq + Hash + Clone,
+{
+    let mut keys = Vec::new();
+    let mut seen_keys = HashSet::new();
+    for key in entities
+        .iter()
+        .filter_map(|entity| relation.foreign_key(entity))
+    {
+        if seen_keys.insert(key.clone()) {
+            keys.push(key);
+        }
+    }
+
+    let mut related = HashMap::new();
+    for keys in keys.chunks(crate::batch::max_params_per_statement(datasource_name)) {
+        let mut related_query = U::select_query_datasource(datasource_name);
+        related_query
+            ._inner
+            .values_in("AND", relation.referenced_column(), keys);
+        let after_load = related_query.after_load;
+        for (key, mut instance) in related_query
+            ._inner
+            .query_keyed::<U, K>(relation.referenced_column())
+            .await?
+        {
+            if let Some(after_load) = after_load {
+                after_load(&mut instance).await?;
+            }
+            related.insert(key, Arc::new(instance));
+        }
+    }
+
+    Ok(entities
+        .iter()
+        .map(|entity| {
+            relation
+                .foreign_key(entity)
+                .and_then(|key| related.get(&key).cloned())
+        })
+        .collect())
+}
+
+/// A page of results of a paginated query, along with the total
+/// number of rows that matches the filters of the query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub page: usize,
+    pub size: usize,
+}
+
+impl<T> Page<T> {
+    /// Returns the total number of pages available for the query
+    pub fn total_pages(&self) -> usize {
+        if self.size == 0 {
+            return 0;
+        }
+        (self.total.max(0) as usize).div_ceil(self.size)
+    }
+
+    /// Returns true whenever there's more pages after the current one
+    pub fn has_next(&self) -> bool {
+        self.page + 1 < self.total_pages()
+    }
+}
+
+impl<'a, T, R> ops::QueryBuilder<'a, T> for SelectQueryBuilder<'a, T, R>
 where
     T: Debug + CrudOperations<T> + Transaction<T> + RowMapper<T> + Send,
+    R: RowMapper<R>,
 {
     #[inline]
     fn read_sql(&'a self) -> &'a str {
@@ -461,6 +1518,17 @@ where
     T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
 {
     _inner: QueryBuilder<'a, T>,
+    table_schema_data: String,
+    updated_at: Option<UpdatedAt>,
+}
+
+/// Holds the data of the `#[updated_at]` column of an entity, needed by
+/// the [`UpdateQueryBuilder`] to refresh it on every update
+#[derive(Debug, Clone, Copy)]
+struct UpdatedAt {
+    column: &'static str,
+    clock: Clock,
+    current_timestamp_sql: fn(&DatabaseType, Clock) -> String,
 }
 
 impl<'a, T> UpdateQueryBuilder<'a, T>
@@ -474,19 +1542,53 @@ where
                 Query::new(format!("UPDATE {table_schema_data}")),
                 datasource_name,
             ),
+            table_schema_data: table_schema_data.to_string(),
+            updated_at: None,
+        }
+    }
+
+    /// Generates a new public instance of the [`UpdateQueryBuilder`] for an entity
+    /// with an `#[updated_at]` field, whose column is set with the current time
+    /// (obtained from the `clock`) by the `SET` clause
+    pub fn new_with_updated_at<Ts: Timestamp>(
+        table_schema_data: &str,
+        updated_at_column: &'static str,
+        clock: Clock,
+        datasource_name: &'a str,
+    ) -> Self {
+        Self {
+            updated_at: Some(UpdatedAt {
+                column: updated_at_column,
+                clock,
+                current_timestamp_sql: current_timestamp_sql::<Ts>,
+            }),
+            ..Self::new(table_schema_data, datasource_name)
         }
     }
 
     /// Launches the generated query to the database pointed by the
-    /// selected datasource
+    /// selected datasource, returning the number of affected rows
     #[inline]
     pub async fn query(
         &'a mut self,
-    ) -> Result<Vec<T>, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
-        self._inner.query().await
+    ) -> Result<u64, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
+        self._inner.execute().await
     }
 
-    /// Creates an SQL `SET` clause to especify the columns that must be updated in the sentence
+    /// Launches the generated query to the database pointed by the
+    /// selected datasource, returning the updated rows with their new values
+    pub async fn returning(
+        &'a mut self,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner
+            .query_returning(Modification::Update, &self.table_schema_data)
+            .await
+    }
+
+    /// Creates an SQL `SET` clause to especify the columns that must be updated in the sentence.
+    ///
+    /// If the entity has an `#[updated_at]` field, its column is also set with the current time,
+    /// unless it's already present on the `columns`
     pub fn set<Z, Q>(&mut self, columns: &'a [(Z, Q)]) -> &mut Self
     where
         Z: FieldIdentifier<T> + Clone,
@@ -520,9 +1622,67 @@ where
             self._inner.query.params.push(&column.1);
         }
 
+        let sets_updated_at = self
+            .updated_at
+            .is_some_and(|updated_at| columns.iter().any(|c| c.0.as_str() == updated_at.column));
+        if !sets_updated_at {
+            set_clause.push_str(&self.updated_at_assignment());
+        }
+
         self._inner.query.sql.push_str(&set_clause);
         self
     }
+
+    /// Creates the `SET` clause of a single `column`, that's set to `NULL` when
+    /// there's no `value`, along with the `#[updated_at]` column, if any
+    pub(crate) fn set_column(
+        &mut self,
+        column: &str,
+        value: Option<&'a dyn QueryParameter<'a>>,
+    ) -> &mut Self {
+        let value = match value {
+            Some(value) => {
+                self._inner.query.params.push(value);
+                format!("${}", self._inner.query.params.len())
+            }
+            None => String::from("NULL"),
+        };
+        let updated_at = self.updated_at_assignment();
+        self._inner
+            .query
+            .sql
+            .push_str(&format!(" SET {column} = {value}{updated_at}"));
+        self
+    }
+
+    /// Appends a condition over a single value. See [`QueryBuilder::and_condition`]
+    pub(crate) fn and_condition(
+        &mut self,
+        condition: impl FnOnce(&str) -> String,
+        value: &'a dyn QueryParameter<'a>,
+    ) -> &mut Self {
+        self._inner.and_condition(condition, value);
+        self
+    }
+
+    /// Returns the generated statement along with its parameters, to be executed
+    /// with other ones through [`Transaction::execute_all`]
+    pub(crate) fn into_statement(self) -> (String, Vec<&'a dyn QueryParameter<'a>>) {
+        (self._inner.query.sql, self._inner.query.params)
+    }
+
+    /// Returns the assignment of the current time to the `#[updated_at]` column,
+    /// to append it to the `SET` clause, or nothing if the entity doesn't have one
+    fn updated_at_assignment(&self) -> String {
+        match self.updated_at {
+            Some(updated_at) => format!(
+                ", {} = {}",
+                updated_at.column,
+                (updated_at.current_timestamp_sql)(&self._inner.datasource_type, updated_at.clock)
+            ),
+            None => String::new(),
+        }
+    }
 }
 
 impl<'a, T> ops::QueryBuilder<'a, T> for UpdateQueryBuilder<'a, T>
@@ -599,6 +1759,7 @@ where
     T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
 {
     _inner: QueryBuilder<'a, T>,
+    table_schema_data: String,
 }
 
 impl<'a, T> DeleteQueryBuilder<'a, T>
@@ -612,16 +1773,27 @@ where
                 Query::new(format!("DELETE FROM {table_schema_data}")),
                 datasource_name,
             ),
+            table_schema_data: table_schema_data.to_string(),
         }
     }
 
     /// Launches the generated query to the database pointed by the
-    /// selected datasource
+    /// selected datasource, returning the number of affected rows
     #[inline]
     pub async fn query(
         &'a mut self,
-    ) -> Result<Vec<T>, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
-        self._inner.query().await
+    ) -> Result<u64, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
+        self._inner.execute().await
+    }
+
+    /// Launches the generated query to the database pointed by the
+    /// selected datasource, returning the deleted rows
+    pub async fn returning(
+        &'a mut self,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner
+            .query_returning(Modification::Delete, &self.table_schema_data)
+            .await
     }
 }
 
@@ -661,7 +1833,7 @@ where
         Z: FieldIdentifier<T>,
         Q: QueryParameter<'a>,
     {
-        self._inner.or_values_in(and, values);
+        self._inner.and_values_in(and, values);
         self
     }
 
@@ -687,3 +1859,35 @@ where
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_placeholder() {
+        assert_eq!(
+            remove_placeholder(
+                "SELECT * FROM league WHERE region = $1 AND 1 = 1 AND id < $3",
+                2
+            ),
+            "SELECT * FROM league WHERE region = $1 AND 1 = 1 AND id < $2"
+        );
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn test_insert_output_clause() {
+        let mut update =
+            String::from("UPDATE [league] SET [name] = @P1 WHERE [id] IN (SELECT 1 WHERE 1 = 1)");
+        insert_output_clause(&mut update, Modification::Update);
+        assert_eq!(
+            update,
+            "UPDATE [league] SET [name] = @P1 OUTPUT inserted.* WHERE [id] IN (SELECT 1 WHERE 1 = 1)"
+        );
+
+        let mut delete = String::from("DELETE FROM [league]");
+        insert_output_clause(&mut delete, Modification::Delete);
+        assert_eq!(delete, "DELETE FROM [league] OUTPUT deleted.*");
+    }
+}
@@ -0,0 +1,458 @@
+//! Contains the [`Relation`] between the entities declared with the `#[foreign_key]`
+//! annotation, that allows the [`crate::query_elements::query_builder::SelectQueryBuilder`]
+//! to load the referenced entities along with the queried ones, attached as [`Loaded`].
+//!
+//! Also, the collections of related entities declared with the `#[has_many]` and
+//! `#[many_to_many]` annotations, that are loaded and modified through [`HasMany`]
+//! and [`ManyToMany`]
+
+use std::{error::Error, marker::PhantomData, ops::Deref, sync::Arc};
+
+use crate::{
+    bounds::QueryParameter,
+    crud::{CrudOperations, Transaction},
+    mapper::RowMapper,
+};
+
+/// The relation between an entity `T` and the entity `U` referenced by one of its
+/// `#[foreign_key]` fields, whose values are of the `K` type.
+///
+/// The `CanyonCrud` derive generates one for every foreign key of the entity,
+/// as the associated constants of a type named as the entity plus `Relation`:
+///
+/// ```ignore
+/// let tournaments: Vec<Loaded<Tournament, League>> = Tournament::select_query()
+///     .with(TournamentRelation::league)
+///     .query()
+///     .await?;
+/// ```
+pub struct Relation<T, U, K> {
+    column: &'static str,
+    referenced_column: &'static str,
+    foreign_key: fn(&T) -> Option<K>,
+    _related: PhantomData<fn() -> U>,
+}
+
+impl<T, U, K> Relation<T, U, K> {
+    /// Creates a new [`Relation`], where:
+    ///
+    /// * `column` - The column of `T` that holds the foreign key
+    /// * `referenced_column` - The column of `U` referenced by the foreign key
+    /// * `foreign_key` - Returns the value of the foreign key of an instance of `T`, if any
+    pub const fn new(
+        column: &'static str,
+        referenced_column: &'static str,
+        foreign_key: fn(&T) -> Option<K>,
+    ) -> Self {
+        Self {
+            column,
+            referenced_column,
+            foreign_key,
+            _related: PhantomData,
+        }
+    }
+
+    /// The column of `T` that holds the foreign key
+    pub fn column(&self) -> &'static str {
+        self.column
+    }
+
+    /// The column of `U` referenced by the foreign key
+    pub fn referenced_column(&self) -> &'static str {
+        self.referenced_column
+    }
+
+    /// Returns the value of the foreign key of the `entity`, if any
+    pub fn foreign_key(&self, entity: &T) -> Option<K> {
+        (self.foreign_key)(entity)
+    }
+
+    /// Retrieves the entity referenced by the `entity`, if any, through the
+    /// [`CrudOperations::select_query_datasource`] of `U`, so the soft deleted
+    /// ones are excluded and the `after_load` hook is called on it
+    ///
+    /// * `entity` - The entity that holds the foreign key
+    /// * `datasource_name` - The datasource where the referenced entity lives
+    pub async fn load(
+        &self,
+        entity: &T,
+        datasource_name: &str,
+    ) -> Result<Option<U>, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let Some(key) = self.foreign_key(entity) else {
+            return Ok(None);
+        };
+        let referenced_column = self.referenced_column;
+        let mut select = U::select_query_datasource(datasource_name);
+        select.and_condition(|value| format!("{referenced_column} = {value}"), &key);
+        Ok(select.query().await?.into_iter().next())
+    }
+}
+
+impl<T, U, K> Clone for Relation<T, U, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U, K> Copy for Relation<T, U, K> {}
+
+/// An entity `T` retrieved along with the entity `U` that it references, if any.
+///
+/// The referenced entity is shared between all the entities that references it.
+/// Dereferences into the loaded entity, so its fields and methods are reachable
+/// as if it were the entity itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loaded<T, U> {
+    pub entity: T,
+    pub related: Option<Arc<U>>,
+}
+
+impl<T, U> Loaded<T, U> {
+    /// Discards the related entity, returning the loaded one
+    pub fn into_entity(self) -> T {
+        self.entity
+    }
+
+    /// Splits the loaded entity and the related one
+    pub fn into_parts(self) -> (T, Option<Arc<U>>) {
+        (self.entity, self.related)
+    }
+}
+
+impl<T, U> Deref for Loaded<T, U> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+/// The relation declared with `#[has_many(U, fk = "...")]` on an entity, whose
+/// related entities `U` references it through their `foreign_key` column.
+///
+/// The `CanyonCrud` derive generates the `load_*`, `add_*`, `remove_*` and `set_*`
+/// methods of the field over it. Removing a related entity sets its foreign key
+/// to `NULL`, so it's only available when that column is nullable
+#[derive(Debug, Clone, Copy)]
+pub struct HasMany {
+    foreign_key: &'static str,
+}
+
+impl HasMany {
+    /// Creates a new [`HasMany`], where:
+    ///
+    /// * `foreign_key` - The column of the related entities that references the entity
+    pub const fn new(foreign_key: &'static str) -> Self {
+        Self { foreign_key }
+    }
+
+    /// Retrieves the related entities whose foreign key is the `key`
+    ///
+    /// * `key` - The primary key of the entity that declares the relation
+    /// * `datasource_name` - The datasource where the related entities lives
+    pub async fn load<'a, U, K>(
+        &self,
+        key: &'a K,
+        datasource_name: &'a str,
+    ) -> Result<Vec<U>, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let foreign_key = self.foreign_key;
+        let mut select = U::select_query_datasource(datasource_name);
+        select.and_condition(|value| format!("{foreign_key} = {value}"), key);
+        select.query().await
+    }
+
+    /// Sets the foreign key of every one of the `related` entities to the `key`,
+    /// returning the number of affected rows. The entities are updated inside
+    /// a single database transaction, so none of them is updated if any fails
+    pub async fn add<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let stmts = self.add_statements(key, related, datasource_name)?;
+        Ok(U::execute_all(stmts, datasource_name).await?.iter().sum())
+    }
+
+    /// Sets to `NULL` the foreign key of the `related` entities that references
+    /// the `key`, returning the number of affected rows. The entities are updated
+    /// inside a single database transaction, so none of them is updated if any fails
+    pub async fn remove<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let primary_key = related_primary_key::<U>()?;
+        let foreign_key = self.foreign_key;
+        let mut stmts = Vec::with_capacity(related.len());
+        for entity in related {
+            let mut update = U::update_query_datasource(datasource_name);
+            update
+                .set_column(foreign_key, None)
+                .and_condition(
+                    |value| format!("{primary_key} = {value}"),
+                    related_primary_key_value(entity)?,
+                )
+                .and_condition(|value| format!("{foreign_key} = {value}"), key);
+            stmts.push(update.into_statement());
+        }
+        Ok(U::execute_all(stmts, datasource_name).await?.iter().sum())
+    }
+
+    /// Replaces the entities related with the `key` by the `related` ones, setting to
+    /// `NULL` the foreign key of the current ones before adding the new ones, all of it
+    /// inside a single database transaction. Returns the number of rows affected by the
+    /// addition
+    pub async fn set<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let foreign_key = self.foreign_key;
+        let mut clear = U::update_query_datasource(datasource_name);
+        clear
+            .set_column(foreign_key, None)
+            .and_condition(|value| format!("{foreign_key} = {value}"), key);
+
+        let mut stmts = vec![clear.into_statement()];
+        stmts.extend(self.add_statements(key, related, datasource_name)?);
+        Ok(U::execute_all(stmts, datasource_name)
+            .await?
+            .iter()
+            .skip(1)
+            .sum())
+    }
+
+    /// Generates the statements that sets the foreign key of the `related` entities to the `key`
+    fn add_statements<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<Statements<'a>, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let primary_key = related_primary_key::<U>()?;
+        let mut stmts = Vec::with_capacity(related.len());
+        for entity in related {
+            let mut update = U::update_query_datasource(datasource_name);
+            update
+                .set_column(self.foreign_key, Some(key))
+                .and_condition(
+                    |value| format!("{primary_key} = {value}"),
+                    related_primary_key_value(entity)?,
+                );
+            stmts.push(update.into_statement());
+        }
+        Ok(stmts)
+    }
+}
+
+/// The relation declared with `#[many_to_many(U, through = "...")]` on an entity, that's
+/// related with the entities `U` through the rows of a join table, whose columns references
+/// the primary keys of both of them.
+///
+/// The `CanyonCrud` derive generates the `load_*`, `add_*`, `remove_*` and `set_*`
+/// methods of the field over it, and the migrations creates the join table, with
+/// a composite primary key over both columns
+#[derive(Debug, Clone, Copy)]
+pub struct ManyToMany {
+    through: &'static str,
+    column: &'static str,
+    related_column: &'static str,
+}
+
+impl ManyToMany {
+    /// Creates a new [`ManyToMany`], where:
+    ///
+    /// * `through` - The join table
+    /// * `column` - The column of the join table that references the entity
+    /// * `related_column` - The column of the join table that references the related entities
+    pub const fn new(
+        through: &'static str,
+        column: &'static str,
+        related_column: &'static str,
+    ) -> Self {
+        Self {
+            through,
+            column,
+            related_column,
+        }
+    }
+
+    /// Retrieves the entities related with the `key` through the join table
+    ///
+    /// * `key` - The primary key of the entity that declares the relation
+    /// * `datasource_name` - The datasource where the related entities lives
+    pub async fn load<'a, U, K>(
+        &self,
+        key: &'a K,
+        datasource_name: &'a str,
+    ) -> Result<Vec<U>, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let primary_key = related_primary_key::<U>()?;
+        let Self {
+            through,
+            column,
+            related_column,
+        } = *self;
+
+        let mut select = U::select_query_datasource(datasource_name);
+        select.and_condition(
+            |value| {
+                format!(
+                    "{primary_key} IN (SELECT {related_column} FROM {through} WHERE {column} = {value})"
+                )
+            },
+            key,
+        );
+        select.query().await
+    }
+
+    /// Inserts a row on the join table for every one of the `related` entities, returning
+    /// the number of inserted rows. Adding an entity that's already related fails, due to
+    /// the primary key of the join table, and then none of them is added, given that the
+    /// rows are inserted inside a single database transaction
+    pub async fn add<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let stmts = self.add_statements(key, related)?;
+        Ok(U::execute_all(stmts, datasource_name).await?.iter().sum())
+    }
+
+    /// Deletes the rows of the join table that relates the `key` with the `related`
+    /// entities, returning the number of deleted rows. The rows are deleted inside
+    /// a single database transaction, so none of them is deleted if any fails
+    pub async fn remove<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let stmt = format!(
+            "DELETE FROM {} WHERE {} = $1 AND {} = $2",
+            self.through, self.column, self.related_column
+        );
+        let mut stmts = Vec::with_capacity(related.len());
+        for entity in related {
+            let params: Vec<&'a dyn QueryParameter<'a>> =
+                vec![key, related_primary_key_value(entity)?];
+            stmts.push((stmt.clone(), params));
+        }
+        Ok(U::execute_all(stmts, datasource_name).await?.iter().sum())
+    }
+
+    /// Replaces the entities related with the `key` by the `related` ones, deleting all
+    /// the rows of the join table that relates the `key` before inserting the new ones,
+    /// all of it inside a single database transaction. Returns the number of inserted rows
+    pub async fn set<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let clear = format!("DELETE FROM {} WHERE {} = $1", self.through, self.column);
+        let mut stmts: Statements<'a> = vec![(clear, vec![key])];
+        stmts.extend(self.add_statements(key, related)?);
+        Ok(U::execute_all(stmts, datasource_name)
+            .await?
+            .iter()
+            .skip(1)
+            .sum())
+    }
+
+    /// Generates the statements that inserts the rows of the join table
+    /// that relates the `key` with the `related` entities
+    fn add_statements<'a, U, K>(
+        &self,
+        key: &'a K,
+        related: &'a [U],
+    ) -> Result<Statements<'a>, Box<dyn Error + Send + Sync + 'static>>
+    where
+        U: CrudOperations<U> + Transaction<U> + RowMapper<U>,
+        K: for<'q> QueryParameter<'q>,
+    {
+        let stmt = format!(
+            "INSERT INTO {} ({}, {}) VALUES ($1, $2)",
+            self.through, self.column, self.related_column
+        );
+        let mut stmts = Vec::with_capacity(related.len());
+        for entity in related {
+            let params: Vec<&'a dyn QueryParameter<'a>> =
+                vec![key, related_primary_key_value(entity)?];
+            stmts.push((stmt.clone(), params));
+        }
+        Ok(stmts)
+    }
+}
+
+/// The statements executed by the modifications of the relations, along with their parameters
+type Statements<'a> = Vec<(String, Vec<&'a dyn QueryParameter<'a>>)>;
+
+/// Returns the column of the primary key of the related entities, that's required
+/// to identify them on the statements of the relations
+fn related_primary_key<U: CrudOperations<U> + RowMapper<U>>(
+) -> Result<&'static str, Box<dyn Error + Send + Sync + 'static>> {
+    U::primary_key().ok_or_else(|| {
+        format!(
+            "The related entity `{}` must have a #[primary_key] annotation",
+            std::any::type_name::<U>()
+        )
+        .into()
+    })
+}
+
+/// Returns the value of the primary key of a related entity
+fn related_primary_key_value<U: CrudOperations<U> + RowMapper<U>>(
+    entity: &U,
+) -> Result<&dyn QueryParameter<'_>, Box<dyn Error + Send + Sync + 'static>> {
+    entity.primary_key_value().ok_or_else(|| {
+        format!(
+            "The related entity `{}` must have a #[primary_key] annotation",
+            std::any::type_name::<U>()
+        )
+        .into()
+    })
+}
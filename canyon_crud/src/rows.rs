@@ -1,5 +1,8 @@
 use crate::crud::Transaction;
-use crate::mapper::RowMapper;
+use crate::dynamic_row::DynamicRow;
+use crate::errors::{RowDeserializeError, RowMapperError};
+use crate::mapper::{FromColumn, RowMapper};
+use serde::de::DeserializeOwned;
 use std::marker::PhantomData;
 
 /// Lightweight wrapper over the collection of results of the different crates
@@ -44,18 +47,134 @@ impl<T> CanyonRows<T> {
         }
     }
 
-    /// Consumes `self` and returns the wrapped [`std::vec::Vec`] with the instances of T
-    pub fn into_results<Z: RowMapper<T>>(self) -> Vec<T>
+    /// Consumes `self` and returns the wrapped [`std::vec::Vec`] with the instances of T,
+    /// or the error of the first row that can't be mapped into T
+    pub fn into_results<Z: RowMapper<T>>(self) -> Result<Vec<T>, RowMapperError>
     where
         T: Transaction<T>,
     {
         match self {
             #[cfg(feature = "postgres")]
-            Self::Postgres(v) => v.iter().map(|row| Z::deserialize_postgresql(row)).collect(),
+            Self::Postgres(v) => v
+                .iter()
+                .map(|row| Z::try_deserialize_postgresql(row))
+                .collect(),
             #[cfg(feature = "mssql")]
-            Self::Tiberius(v) => v.iter().map(|row| Z::deserialize_sqlserver(row)).collect(),
+            Self::Tiberius(v) => v
+                .iter()
+                .map(|row| Z::try_deserialize_sqlserver(row))
+                .collect(),
             #[cfg(feature = "mysql")]
-            Self::MySQL(v) => v.iter().map(|row| Z::deserialize_mysql(row)).collect(),
+            Self::MySQL(v) => v.iter().map(|row| Z::try_deserialize_mysql(row)).collect(),
+            _ => panic!("This branch will never ever should be reachable"),
+        }
+    }
+
+    /// Consumes `self` and returns the rows mapped into instances of `R`, for
+    /// the queries whose results are not instances of the queried entity, like
+    /// tuples of columns or the results of aggregate functions
+    pub fn into_results_as<R: RowMapper<R>>(self) -> Result<Vec<R>, RowMapperError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(v) => v
+                .iter()
+                .map(|row| R::try_deserialize_postgresql(row))
+                .collect(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(v) => v
+                .iter()
+                .map(|row| R::try_deserialize_sqlserver(row))
+                .collect(),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(v) => v.iter().map(|row| R::try_deserialize_mysql(row)).collect(),
+            _ => panic!("This branch will never ever should be reachable"),
+        }
+    }
+
+    /// Consumes `self` and returns the rows mapped into instances of `R`, each one
+    /// along with the value of its `key_column`, used to attach the related entities
+    /// to the ones that references them
+    pub(crate) fn into_keyed_results<R: RowMapper<R>, K: FromColumn>(
+        self,
+        key_column: &str,
+    ) -> Result<Vec<(K, R)>, RowMapperError> {
+        let entity = std::any::type_name::<R>();
+        let expected = std::any::type_name::<K>();
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(v) => v
+                .iter()
+                .map(|row| {
+                    let key =
+                        crate::mapper::try_get_postgres_column(row, entity, key_column, expected)?;
+                    Ok((key, R::try_deserialize_postgresql(row)?))
+                })
+                .collect(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(v) => v
+                .iter()
+                .map(|row| {
+                    let key =
+                        crate::mapper::try_get_sqlserver_column(row, entity, key_column, expected)?;
+                    Ok((key, R::try_deserialize_sqlserver(row)?))
+                })
+                .collect(),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(v) => v
+                .iter()
+                .map(|row| {
+                    let key =
+                        crate::mapper::try_get_mysql_column(row, entity, key_column, expected)?;
+                    Ok((key, R::try_deserialize_mysql(row)?))
+                })
+                .collect(),
+            _ => panic!("This branch will never ever should be reachable"),
+        }
+    }
+
+    /// Consumes `self` and returns the rows deserialized with `serde` into instances of `R`,
+    /// as an alternative to derive `CanyonMapper` on the types that already implements
+    /// `serde::Deserialize`. See [`crate::deserializer::RowDeserializer`] for the details
+    pub fn deserialize<R: DeserializeOwned>(self) -> Result<Vec<R>, RowDeserializeError> {
+        self.into_dynamic_rows()
+            .iter()
+            .map(DynamicRow::deserialize)
+            .collect()
+    }
+
+    /// Consumes `self` and returns the value of the first column of the first row, for
+    /// the queries that returns a single value, like the ones with aggregate functions.
+    ///
+    /// Returns an error if the query didn't return any row
+    pub fn into_scalar<V: FromColumn>(
+        self,
+    ) -> Result<V, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let scalar = match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(v) => v.first().map(<(V,)>::try_deserialize_postgresql),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(v) => v.first().map(<(V,)>::try_deserialize_sqlserver),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(v) => v.first().map(<(V,)>::try_deserialize_mysql),
+            _ => panic!("This branch will never ever should be reachable"),
+        };
+
+        match scalar {
+            Some(result) => Ok(result?.0),
+            None => Err("The query didn't return any row to retrieve a scalar value from".into()),
+        }
+    }
+
+    /// Consumes `self` and returns the wrapped rows as [`DynamicRow`], whose values
+    /// are retrieved by the name or the position of their columns
+    pub fn into_dynamic_rows(self) -> Vec<DynamicRow> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(v) => v.into_iter().map(DynamicRow::Postgres).collect(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(v) => v.into_iter().map(DynamicRow::Tiberius).collect(),
+            #[cfg(feature = "mysql")]
+            Self::MySQL(v) => v.into_iter().map(DynamicRow::MySQL).collect(),
             _ => panic!("This branch will never ever should be reachable"),
         }
     }
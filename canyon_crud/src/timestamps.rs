@@ -0,0 +1,154 @@
+//! Contains the types that allows Canyon to automatically fill the fields
+//! annotated with `#[created_at]` and `#[updated_at]`
+
+use canyon_connection::{
+    canyon_database_connector::DatabaseType, get_database_config, DATASOURCES,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::{crud::Transaction, mapper::FromColumn, rows::CanyonRows};
+
+/// The source of the current time used to fill the `#[created_at]`
+/// and `#[updated_at]` fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clock {
+    /// The current time of the machine that runs the application. It's the
+    /// default one, because it does not require an extra round trip to the database
+    App,
+    /// The current time of the database server, useful when several applications
+    /// with clocks that may be out of sync are writing on the same tables
+    Database,
+}
+
+/// The types that can be used to keep the audit timestamps of an entity.
+///
+/// Every timestamp is generated in `UTC`, no matter the clock used to obtain it
+pub trait Timestamp: FromColumn + Copy + Sized {
+    /// Converts the current `UTC` time into `Self`
+    fn from_utc(now: DateTime<Utc>) -> Self;
+
+    /// The SQL expression that returns the current time on the database
+    /// with the same type that `Self` is mapped to
+    fn database_now(db_type: &DatabaseType) -> &'static str;
+
+    /// Returns `self` as a literal that can be written directly on an SQL statement
+    fn as_sql_literal(&self, db_type: &DatabaseType) -> String;
+}
+
+/// The format of the literals used to write the timestamps on the statements. The
+/// fractional seconds are truncated to milliseconds, due to the `DATETIME` type of
+/// `SqlServer` that does not admit more precision
+const SQL_LITERAL_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+impl Timestamp for NaiveDateTime {
+    fn from_utc(now: DateTime<Utc>) -> Self {
+        now.naive_utc()
+    }
+
+    fn database_now(db_type: &DatabaseType) -> &'static str {
+        match db_type {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => "CAST(CURRENT_TIMESTAMP AT TIME ZONE 'UTC' AS TIMESTAMP)",
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => "SYSUTCDATETIME()",
+            #[cfg(feature = "mysql")]
+            DatabaseType::MySQL => "UTC_TIMESTAMP(6)",
+        }
+    }
+
+    fn as_sql_literal(&self, _db_type: &DatabaseType) -> String {
+        format!("'{}'", self.format(SQL_LITERAL_FORMAT))
+    }
+}
+
+impl Timestamp for DateTime<Utc> {
+    fn from_utc(now: DateTime<Utc>) -> Self {
+        now
+    }
+
+    fn database_now(db_type: &DatabaseType) -> &'static str {
+        match db_type {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => "CURRENT_TIMESTAMP",
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => "SYSDATETIMEOFFSET()",
+            #[cfg(feature = "mysql")]
+            DatabaseType::MySQL => "UTC_TIMESTAMP(6)",
+        }
+    }
+
+    fn as_sql_literal(&self, db_type: &DatabaseType) -> String {
+        match db_type {
+            // MySQL does not store the offset, so the values are considered as UTC ones
+            #[cfg(feature = "mysql")]
+            DatabaseType::MySQL => self.naive_utc().as_sql_literal(db_type),
+            #[allow(unreachable_patterns)]
+            _ => format!("'{}+00:00'", self.format(SQL_LITERAL_FORMAT)),
+        }
+    }
+}
+
+/// Returns the SQL expression that obtains the current `UTC` time on the database
+/// of the `datasource_name`, with the same type that `T` is mapped to
+pub fn database_now<T: Timestamp>(datasource_name: &str) -> &'static str {
+    T::database_now(&DatabaseType::from(
+        &get_database_config(datasource_name, &DATASOURCES).auth,
+    ))
+}
+
+/// Helper to retrieve the current time of the database server
+struct DatabaseClock;
+impl Transaction<Self> for DatabaseClock {}
+
+/// Returns the current time, obtained from the desired [`Clock`]
+///
+/// * `clock` - The source of the current time
+/// * `datasource_name` - The datasource whose server will be asked for the time,
+///   if the [`Clock::Database`] is used
+pub async fn now<T: Timestamp>(
+    clock: Clock,
+    datasource_name: &str,
+) -> Result<T, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    if clock == Clock::App {
+        return Ok(T::from_utc(Utc::now()));
+    }
+
+    let rows = DatabaseClock::query(
+        format!("SELECT {}", database_now::<T>(datasource_name)),
+        [],
+        datasource_name,
+    )
+    .await?;
+
+    match rows {
+        #[cfg(feature = "postgres")]
+        CanyonRows::Postgres(v) => T::from_postgres_column(
+            v.first()
+                .ok_or("The database didn't return the current time")?,
+            0,
+        ),
+        #[cfg(feature = "mssql")]
+        CanyonRows::Tiberius(v) => T::from_sqlserver_column(
+            v.first()
+                .ok_or("The database didn't return the current time")?,
+            0,
+        ),
+        #[cfg(feature = "mysql")]
+        CanyonRows::MySQL(v) => T::from_mysql_column(
+            v.first()
+                .ok_or("The database didn't return the current time")?,
+            0,
+        ),
+        _ => panic!("This branch will never ever should be reachable"),
+    }
+}
+
+/// Returns the SQL that writes the current time on a column mapped to `T`,
+/// being a literal for the [`Clock::App`] or an SQL expression that is
+/// evaluated by the database for the [`Clock::Database`]
+pub fn current_timestamp_sql<T: Timestamp>(db_type: &DatabaseType, clock: Clock) -> String {
+    match clock {
+        Clock::App => T::from_utc(Utc::now()).as_sql_literal(db_type),
+        Clock::Database => T::database_now(db_type).to_string(),
+    }
+}
@@ -0,0 +1,123 @@
+//! Contains the types that allows Canyon to know which fields of an entity
+//! were modified since it was retrieved, so only those ones are updated
+
+use std::ops::{Deref, DerefMut};
+
+use async_trait::async_trait;
+
+/// The entities that can be wrapped in a [`Tracked`]. It's implemented by the
+/// `CanyonCrud` derive macro when the entity is annotated with `#[canyon_crud(tracked)]`,
+/// which requires every field to be `Clone` and `PartialEq`
+#[async_trait]
+pub trait Trackable: Sized + Send + Sync {
+    /// Returns a copy of the current values of the fields
+    fn snapshot(&self) -> Self;
+
+    /// Returns the columns whose values differs from the ones in the `snapshot`
+    fn changed_columns(&self, snapshot: &Self) -> Vec<&'static str>;
+
+    /// Updates the database record that matches the current instance, writing just the
+    /// columns whose values differs from the ones in the `snapshot`, and doing nothing
+    /// at all if there isn't any. Returns the number of affected rows.
+    ///
+    /// It behaves as the regular `update` operation regarding the lifecycle hooks,
+    /// the validations, the `#[updated_at]` and the `#[version]` fields
+    async fn update_changed<'a>(
+        &mut self,
+        snapshot: &Self,
+        datasource_name: &'a str,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Wraps an entity, remembering the values that it had when it was retrieved
+/// (or last written), so the [`Tracked::update`] operation only writes
+/// the modified columns.
+///
+/// The entity is reachable through [`Deref`] and [`DerefMut`]:
+///
+/// ```ignore
+/// #[derive(CanyonCrud, CanyonMapper)]
+/// #[canyon_crud(tracked)]
+/// #[canyon_entity]
+/// pub struct League { /* ... */ }
+///
+/// let mut league = Tracked::new(League::find_by_pk(&1).await?.unwrap());
+/// league.name = "New name".to_string();
+/// league.update().await?; // UPDATE league SET "name" = $2 WHERE id = $1
+/// league.update().await?; // Nothing changed, so nothing is sent to the database
+/// ```
+#[derive(Debug)]
+pub struct Tracked<T: Trackable> {
+    entity: T,
+    snapshot: T,
+}
+
+impl<T: Trackable> Tracked<T> {
+    /// Starts tracking the changes made over the `entity`
+    pub fn new(entity: T) -> Self {
+        Self {
+            snapshot: entity.snapshot(),
+            entity,
+        }
+    }
+
+    /// Returns true if some field was modified since the last snapshot
+    pub fn is_dirty(&self) -> bool {
+        !self.changed_columns().is_empty()
+    }
+
+    /// Returns the columns that were modified since the last snapshot
+    pub fn changed_columns(&self) -> Vec<&'static str> {
+        self.entity.changed_columns(&self.snapshot)
+    }
+
+    /// Takes a new snapshot of the entity, so its current values are
+    /// considered the unmodified ones
+    pub fn reset(&mut self) {
+        self.snapshot = self.entity.snapshot();
+    }
+
+    /// Returns the wrapped entity, discarding the snapshot
+    pub fn into_inner(self) -> T {
+        self.entity
+    }
+
+    /// Updates the modified columns of the entity, skipping the round trip to
+    /// the database if nothing was modified. See [`Trackable::update_changed`]
+    pub async fn update(&mut self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        self.update_datasource("").await
+    }
+
+    /// Same as [`Self::update`], but with the specified datasource
+    pub async fn update_datasource(
+        &mut self,
+        datasource_name: &str,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let affected_rows = self
+            .entity
+            .update_changed(&self.snapshot, datasource_name)
+            .await?;
+        self.reset();
+        Ok(affected_rows)
+    }
+}
+
+impl<T: Trackable> From<T> for Tracked<T> {
+    fn from(entity: T) -> Self {
+        Self::new(entity)
+    }
+}
+
+impl<T: Trackable> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+impl<T: Trackable> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.entity
+    }
+}
@@ -0,0 +1,263 @@
+//! Contains the [`CanyonType`] trait, that allows the user defined types to be
+//! used as the type of the fields of the entities, and the helpers used by the
+//! enums that derives `CanyonEnum`
+
+#[cfg(feature = "mysql")]
+use canyon_connection::mysql_async;
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius::{self, ColumnData};
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres::{self, types::ToSql};
+
+use std::error::Error;
+
+use crate::{bounds::QueryParameter, mapper::FromColumn};
+
+/// Declares how a user defined type is written to and read from the database,
+/// so it can be used as the type of the fields of the entities, as the value of
+/// the filters of the query builders and by the migrations.
+///
+/// Usually, it's implemented by deriving `CanyonType` on a newtype over some
+/// type supported by Canyon, like `struct UserId(i64)` or `struct Email(String)`.
+///
+/// Every implementor is a [`QueryParameter`] and a [`FromColumn`], as well as
+/// its optional counterpart
+pub trait CanyonType: std::fmt::Debug + Send + Sync + Sized + 'static {
+    /// Returns the value that's bound as a query parameter in place of `self`
+    fn encode(&self) -> &dyn QueryParameter<'_>;
+
+    /// Returns the query parameter that's bound in place of a `NULL` value of the type
+    fn encode_null() -> &'static dyn QueryParameter<'static>;
+
+    /// Retrieves the value of the column placed at `idx`, being `None` for a `NULL` value
+    #[cfg(feature = "postgres")]
+    fn decode_postgres(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>>;
+    #[cfg(feature = "mssql")]
+    fn decode_sqlserver(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>>;
+    #[cfg(feature = "mysql")]
+    fn decode_mysql(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>>;
+
+    /// The SQL type of the columns that stores the values, when it isn't the one
+    /// of the type that it wraps. The migrations declares the columns with it
+    fn sql_type() -> Option<&'static str> {
+        None
+    }
+
+    /// Identifies the type on the register of the macros, that finds the user defined
+    /// types by their names and checks at compile time that the type of a field is
+    /// the one that they found. Implemented by the derives
+    const REGISTRY_KEY: &'static str = "";
+}
+
+/// Compares two keys of the register of the macros at compile time, as a `const`
+/// assertion emitted by them for every field whose type they found by its name
+pub const fn same_registry_key(key: &str, expected: &str) -> bool {
+    let (key, expected) = (key.as_bytes(), expected.as_bytes());
+    if key.len() != expected.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < key.len() {
+        if key[idx] != expected[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+fn unexpected_null(idx: usize) -> Box<dyn Error + Send + Sync> {
+    format!("Unexpected NULL value found on the column with index: {idx}").into()
+}
+
+impl<T: CanyonType> FromColumn for T {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::decode_postgres(row, idx)?.ok_or_else(|| unexpected_null(idx))
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::decode_sqlserver(row, idx)?.ok_or_else(|| unexpected_null(idx))
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::decode_mysql(row, idx)?.ok_or_else(|| unexpected_null(idx))
+    }
+}
+
+impl<T: CanyonType> FromColumn for Option<T> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_column(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::decode_postgres(row, idx)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_column(
+        row: &tiberius::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::decode_sqlserver(row, idx)
+    }
+    #[cfg(feature = "mysql")]
+    fn from_mysql_column(
+        row: &mysql_async::Row,
+        idx: usize,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::decode_mysql(row, idx)
+    }
+}
+
+impl<'a, T: CanyonType> QueryParameter<'a> for T {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self.encode().as_postgres_param()
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        self.encode().as_sqlserver_param()
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        self.encode().as_mysql_param()
+    }
+}
+
+impl<'a, T: CanyonType> QueryParameter<'a> for Option<T> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        match self {
+            Some(value) => value.encode().as_postgres_param(),
+            None => T::encode_null().as_postgres_param(),
+        }
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        match self {
+            Some(value) => value.encode().as_sqlserver_param(),
+            None => T::encode_null().as_sqlserver_param(),
+        }
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        match self {
+            Some(value) => value.encode().as_mysql_param(),
+            None => T::encode_null().as_mysql_param(),
+        }
+    }
+}
+
+/// The label of a variant of an enum that derives `CanyonEnum`, bound as a query
+/// parameter in place of the enum when it's stored as text or as a native
+/// `PostgreSQL` `ENUM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnumLabel(pub &'static str);
+
+#[cfg(feature = "postgres")]
+impl ToSql for EnumLabel {
+    fn to_sql(
+        &self,
+        _ty: &tokio_postgres::types::Type,
+        out: &mut tokio_postgres::types::private::BytesMut,
+    ) -> Result<tokio_postgres::types::IsNull, Box<dyn Error + Sync + Send>> {
+        self.0.to_sql(&tokio_postgres::types::Type::TEXT, out)
+    }
+
+    fn accepts(ty: &tokio_postgres::types::Type) -> bool {
+        matches!(ty.kind(), tokio_postgres::types::Kind::Enum(_)) || <&str as ToSql>::accepts(ty)
+    }
+
+    tokio_postgres::types::to_sql_checked!();
+}
+
+impl<'a> QueryParameter<'a> for EnumLabel {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(Some(std::borrow::Cow::Borrowed(self.0)))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        &self.0
+    }
+}
+
+impl<'a> QueryParameter<'a> for Option<EnumLabel> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(self.map(|label| std::borrow::Cow::Borrowed(label.0)))
+    }
+    #[cfg(feature = "mysql")]
+    fn as_mysql_param(&self) -> &dyn mysql_async::prelude::ToValue {
+        match self {
+            Some(label) => &label.0,
+            None => &None::<&str>,
+        }
+    }
+}
+
+/// The label of a variant read from a text or a native `ENUM` column
+#[cfg(feature = "postgres")]
+struct RawEnumLabel(String);
+
+#[cfg(feature = "postgres")]
+impl<'a> tokio_postgres::types::FromSql<'a> for RawEnumLabel {
+    fn from_sql(
+        _ty: &tokio_postgres::types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        <&str as tokio_postgres::types::FromSql>::from_sql(&tokio_postgres::types::Type::TEXT, raw)
+            .map(|label| Self(label.to_owned()))
+    }
+
+    fn accepts(ty: &tokio_postgres::types::Type) -> bool {
+        matches!(ty.kind(), tokio_postgres::types::Kind::Enum(_))
+            || <&str as tokio_postgres::types::FromSql>::accepts(ty)
+    }
+}
+
+/// Retrieves the label of the variant stored on the column placed at `idx`,
+/// being it a text or a native `ENUM` column
+#[cfg(feature = "postgres")]
+pub fn decode_postgres_enum_label(
+    row: &tokio_postgres::Row,
+    idx: usize,
+) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    Ok(row
+        .try_get::<usize, Option<RawEnumLabel>>(idx)?
+        .map(|label| label.0))
+}
+
+/// The error returned when the value of a column doesn't match any variant of an enum
+pub fn unknown_enum_variant(
+    enum_name: &str,
+    value: impl std::fmt::Display,
+) -> Box<dyn Error + Send + Sync> {
+    format!("`{value}` doesn't match any variant of the enum `{enum_name}`").into()
+}
@@ -0,0 +1,203 @@
+//! Contains the types that checks the rules declared in the `#[validate(...)]`
+//! annotations of the fields of an entity
+
+use std::fmt::Display;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+pub use regex::Regex;
+
+use crate::errors::ValidationErrors;
+
+/// Checks the rules declared in the `#[validate(...)]` annotations of the fields
+/// of an entity. It's implemented by the `CanyonCrud` derive macro, and it's
+/// called by the operations that writes the entity, before sending anything
+/// to the database
+pub trait Validate {
+    /// Returns a [`ValidationErrors`] with every failing rule, if any
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// The values whose length can be validated with the `length` rule
+pub trait LengthValue {
+    /// The number of characters or elements of the value, or [`None`] if there's
+    /// no value at all, so the rule doesn't apply
+    fn length(&self) -> Option<usize>;
+}
+
+impl LengthValue for String {
+    fn length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl LengthValue for &str {
+    fn length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl<T> LengthValue for Vec<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: LengthValue> LengthValue for Option<T> {
+    fn length(&self) -> Option<usize> {
+        self.as_ref().and_then(|value| value.length())
+    }
+}
+
+/// The values that can be validated with the `range` rule
+pub trait RangeValue {
+    /// The type of the bounds of the range
+    type Value: PartialOrd + Display;
+
+    /// The value to compare with the bounds, or [`None`] if there's
+    /// no value at all, so the rule doesn't apply
+    fn range_value(&self) -> Option<&Self::Value>;
+}
+
+macro_rules! impl_range_value {
+    ($($ty:ty),*) => {
+        $(
+            impl RangeValue for $ty {
+                type Value = $ty;
+
+                fn range_value(&self) -> Option<&Self::Value> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_range_value!(
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64,
+    f32,
+    f64,
+    NaiveDate,
+    NaiveTime,
+    NaiveDateTime,
+    DateTime<Utc>
+);
+
+impl<T: RangeValue> RangeValue for Option<T> {
+    type Value = T::Value;
+
+    fn range_value(&self) -> Option<&Self::Value> {
+        self.as_ref().and_then(|value| value.range_value())
+    }
+}
+
+/// The values that can be validated with the `regex` rule
+pub trait TextValue {
+    /// The text to match, or [`None`] if there's no value at all, so the rule doesn't apply
+    fn text(&self) -> Option<&str>;
+}
+
+impl TextValue for String {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl TextValue for &str {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: TextValue> TextValue for Option<T> {
+    fn text(&self) -> Option<&str> {
+        self.as_ref().and_then(|value| value.text())
+    }
+}
+
+/// Checks the `length` rule, returning the error message if it isn't satisfied
+///
+/// * `value` - The value of the field
+/// * `min` - The minimum length allowed, if any
+/// * `max` - The maximum length allowed, if any
+pub fn check_length<V: LengthValue>(
+    value: &V,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Result<(), String> {
+    match value.length() {
+        Some(length) if min.is_some_and(|min| length < min) => Err(format!(
+            "length must be at least {}, but it's {length}",
+            min.unwrap()
+        )),
+        Some(length) if max.is_some_and(|max| length > max) => Err(format!(
+            "length must be at most {}, but it's {length}",
+            max.unwrap()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the `range` rule, returning the error message if it isn't satisfied
+///
+/// * `value` - The value of the field
+/// * `min` - The inclusive lower bound, if any
+/// * `max` - The inclusive upper bound, if any
+pub fn check_range<V: RangeValue>(
+    value: &V,
+    min: Option<V::Value>,
+    max: Option<V::Value>,
+) -> Result<(), String> {
+    match value.range_value() {
+        Some(value) if min.as_ref().is_some_and(|min| value < min) => Err(format!(
+            "must be greater than or equal to {}, but it's {value}",
+            min.unwrap()
+        )),
+        Some(value) if max.as_ref().is_some_and(|max| value > max) => Err(format!(
+            "must be less than or equal to {}, but it's {value}",
+            max.unwrap()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the `regex` rule, returning the error message if it isn't satisfied
+///
+/// * `value` - The value of the field
+/// * `regex` - The regular expression that the value must match
+pub fn check_regex<V: TextValue>(value: &V, regex: &Regex) -> Result<(), String> {
+    match value.text() {
+        Some(text) if !regex.is_match(text) => {
+            Err(format!("must match the pattern `{}`", regex.as_str()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_checks() {
+        assert!(check_length(&"Canyon".to_string(), Some(1), Some(6)).is_ok());
+        assert!(check_length(&"Canyon".to_string(), None, Some(5)).is_err());
+        assert!(check_length(&Some(String::new()), Some(1), None).is_err());
+        assert!(check_length(&None::<String>, Some(1), None).is_ok());
+
+        assert!(check_range(&5_i32, Some(0), Some(5)).is_ok());
+        assert!(check_range(&-1_i64, Some(0), None).is_err());
+        assert!(check_range(&Some(0.5_f64), None, Some(0.25)).is_err());
+        assert!(check_range(&None::<i32>, Some(0), None).is_ok());
+
+        let regex = Regex::new("^[a-z-]+$").unwrap();
+        assert!(check_regex(&"canyon-sql".to_string(), &regex).is_ok());
+        assert!(check_regex(&Some("Canyon".to_string()), &regex).is_err());
+    }
+}
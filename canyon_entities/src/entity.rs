@@ -1,5 +1,5 @@
 use partialdebug::placeholder::PartialDebug;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use std::convert::TryFrom;
 use syn::{
@@ -26,15 +26,25 @@ unsafe impl Send for CanyonEntity {}
 unsafe impl Sync for CanyonEntity {}
 
 impl CanyonEntity {
+    /// Returns the names of the columns of the entity, where the `#[embedded]`
+    /// value objects are flattened into several prefixed ones
+    fn column_names(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .flat_map(|f| f.columns())
+            .map(|column| column.field_name)
+            .collect()
+    }
+
     /// Generates as many variants for the enum as fields has the type
     /// which this enum is related to, and that type it's the entity
     /// stored in [`CanyonEntity`]
     /// of the corresponding field
     pub fn get_fields_as_enum_variants(&self) -> Vec<TokenStream> {
-        self.fields
+        self.column_names()
             .iter()
-            .map(|f| {
-                let field_name = &f.name;
+            .map(|column| {
+                let field_name = Ident::new(column, Span::call_site());
                 quote! { #field_name }
             })
             .collect::<Vec<_>>()
@@ -46,21 +56,21 @@ impl CanyonEntity {
     /// Makes a variant `#field_name(#ty)` where `#ty` it's a trait object
     /// of type [`canyon_crud::bounds::QueryParameter`]
     pub fn get_fields_as_enum_variants_with_value(&self) -> Vec<TokenStream> {
-        self.fields
+        self.column_names()
             .iter()
-            .map(|f| {
-                let field_name = &f.name;
+            .map(|column| {
+                let field_name = Ident::new(column, Span::call_site());
                 quote! { #field_name(&'a dyn canyon_sql::crud::bounds::QueryParameter<'a>) }
             })
             .collect::<Vec<_>>()
     }
 
     pub fn create_match_arm_for_get_variant_as_str(&self, enum_name: &Ident) -> Vec<TokenStream> {
-        self.fields
+        self.column_names()
             .iter()
-            .map(|f| {
-                let field_name = &f.name;
-                let field_name_as_str = f.name.to_string();
+            .map(|column| {
+                let field_name = Ident::new(column, Span::call_site());
+                let field_name_as_str = column;
 
                 quote! {
                     #enum_name::#field_name => #field_name_as_str
@@ -76,11 +86,11 @@ impl CanyonEntity {
         &self,
         enum_name: &Ident,
     ) -> Vec<TokenStream> {
-        self.fields
+        self.column_names()
             .iter()
-            .map(|f| {
-                let field_name = &f.name;
-                let field_name_as_string = f.name.to_string();
+            .map(|column| {
+                let field_name = Ident::new(column, Span::call_site());
+                let field_name_as_string = column;
 
                 quote! {
                     #enum_name::#field_name => #field_name_as_string.to_string()
@@ -96,11 +106,11 @@ impl CanyonEntity {
         &self,
         enum_name: &Ident,
     ) -> Vec<TokenStream> {
-        self.fields
+        self.column_names()
             .iter()
-            .map(|f| {
-                let field_name = &f.name;
-                let field_name_as_string = f.name.to_string();
+            .map(|column| {
+                let field_name = Ident::new(column, Span::call_site());
+                let field_name_as_string = column;
 
                 quote! {
                     #enum_name::#field_name(v) => (#field_name_as_string, v)
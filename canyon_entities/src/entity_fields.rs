@@ -5,12 +5,18 @@ use std::convert::TryFrom;
 use syn::{Attribute, Field, Type};
 
 use super::field_annotation::EntityFieldAnnotation;
+use super::register_types::{
+    CanyonRegisterEmbeddable, CanyonRegisterEntityField, CanyonRegisterType,
+};
 /// Represents any of the fields and annotations (if any valid annotation) found for an Rust struct
 #[derive(PartialDebug, Clone)]
 pub struct EntityField {
     pub name: Ident,
     pub field_type: Type,
     pub attributes: Vec<EntityFieldAnnotation>,
+    /// The registered value object of the field, if it's `#[embedded]`
+    pub embeddable: Option<CanyonRegisterEmbeddable>,
+    columns: Vec<CanyonRegisterEntityField>,
 }
 
 impl EntityField {
@@ -35,9 +41,47 @@ impl EntityField {
         }
     }
 
+    /// Returns the prefix of the columns of the field, if it's an `#[embedded]` value object
+    pub fn embedded_prefix(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|attr| match attr {
+            EntityFieldAnnotation::Embedded(prefix) => Some(prefix.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns the `#[has_many]` or `#[many_to_many]` annotation of the field,
+    /// if it holds a collection of related entities
+    pub fn relation(&self) -> Option<&EntityFieldAnnotation> {
+        self.attributes.iter().find(|attr| {
+            matches!(
+                attr,
+                EntityFieldAnnotation::HasMany(..) | EntityFieldAnnotation::ManyToMany(..)
+            )
+        })
+    }
+
+    /// Returns the columns that maps the field. The `#[embedded]` value objects
+    /// are flattened into a prefixed column for every field of them, and the
+    /// collections of related entities aren't persisted in the table of the entity
+    pub fn columns(&self) -> Vec<CanyonRegisterEntityField> {
+        self.columns.clone()
+    }
+
+    /// Returns the registered user defined type of the field, if any
+    pub fn user_type(&self) -> Option<&CanyonRegisterType> {
+        match self.embeddable {
+            Some(_) => None,
+            None => self.columns.first()?.user_type.as_ref(),
+        }
+    }
+
     pub fn new(name: &Ident, raw_helper_attributes: &[Attribute], ty: &Type) -> syn::Result<Self> {
         let mut attributes = Vec::new();
         for attr in raw_helper_attributes {
+            // Only the `CanyonMapper` derive reads the columns of the fields from another place
+            if attr.path.is_ident("column_index") || attr.path.is_ident("column") {
+                continue;
+            }
             let result = Some(EntityFieldAnnotation::try_from(&attr)?);
             match result {
                 Some(res) => attributes.push(res),
@@ -45,10 +89,121 @@ impl EntityField {
             }
         }
 
+        // The soft deleted rows are the ones with a value on the column, so it must be nullable
+        if attributes
+            .iter()
+            .any(|attr| matches!(attr, EntityFieldAnnotation::SoftDelete))
+            && !ty.to_token_stream().to_string().starts_with("Option")
+        {
+            return Err(syn::Error::new_spanned(
+                name,
+                "The `#[soft_delete]` annotation must be placed over an `Option<NaiveDateTime>` \
+                or an `Option<DateTime<Utc>>` field",
+            ));
+        }
+
+        // The version is incremented on every update, so it must be a non nullable integer
+        if attributes
+            .iter()
+            .any(|attr| matches!(attr, EntityFieldAnnotation::Version))
+            && !["i16", "i32", "i64"].contains(&ty.to_token_stream().to_string().as_str())
+        {
+            return Err(syn::Error::new_spanned(
+                name,
+                "The `#[version]` annotation must be placed over an `i16`, `i32` or `i64` field",
+            ));
+        }
+
+        // The audit timestamps are generated in UTC, so only these types can hold them
+        if attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                EntityFieldAnnotation::CreatedAt(_) | EntityFieldAnnotation::UpdatedAt(_)
+            )
+        }) && !["NaiveDateTime", "DateTime<Utc>"]
+            .contains(&ty.to_token_stream().to_string().replace(' ', "").as_str())
+        {
+            return Err(syn::Error::new_spanned(
+                name,
+                "The `#[created_at]` and `#[updated_at]` annotations must be placed over \
+                a `NaiveDateTime` or a `DateTime<Utc>` field",
+            ));
+        }
+
+        // The value objects are flattened into several columns, so they can't hold
+        // the annotations of a single column
+        if attributes.len() > 1
+            && attributes
+                .iter()
+                .any(|attr| matches!(attr, EntityFieldAnnotation::Embedded(_)))
+        {
+            return Err(syn::Error::new_spanned(
+                name,
+                "The `#[embedded]` annotation can't be combined with other annotations",
+            ));
+        }
+
+        // The related entities are loaded into a collection, that isn't a column of the table
+        if attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                EntityFieldAnnotation::HasMany(..) | EntityFieldAnnotation::ManyToMany(..)
+            )
+        }) {
+            if attributes.len() > 1 {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "The `#[has_many]` and `#[many_to_many]` annotations can't be combined \
+                    with other annotations",
+                ));
+            }
+            if !ty.to_token_stream().to_string().starts_with("Vec") {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "The `#[has_many]` and `#[many_to_many]` annotations must be placed over \
+                    a `Vec` of the related entity",
+                ));
+            }
+        }
+
+        // The types of the fields are found on the register by their names, being checked
+        // at compile time that they're the types written on the paths of the fields
+        let field_type = ty.to_token_stream().to_string().replace(' ', "");
+        let mut embeddable = None;
+        let columns = match attributes.first() {
+            Some(EntityFieldAnnotation::HasMany(..) | EntityFieldAnnotation::ManyToMany(..)) => {
+                Vec::new()
+            }
+            Some(EntityFieldAnnotation::Embedded(prefix)) => {
+                let found = CanyonRegisterEmbeddable::find(&field_type)
+                    .map_err(|err| syn::Error::new_spanned(ty, err))?
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            ty,
+                            format!(
+                                "The type `{field_type}` of an `#[embedded]` field must derive \
+                                `CanyonEmbeddable`, and be declared before the types that embeds it"
+                            ),
+                        )
+                    })?;
+                let columns = found.prefixed_fields(prefix);
+                embeddable = Some(found);
+                columns
+            }
+            _ => vec![CanyonRegisterEntityField::new(
+                name.to_string(),
+                field_type,
+                attributes.iter().map(|attr| attr.get_as_string()).collect(),
+            )
+            .map_err(|err| syn::Error::new_spanned(ty, err))?],
+        };
+
         Ok(Self {
             name: name.clone(),
             field_type: ty.clone(),
             attributes,
+            embeddable,
+            columns,
         })
     }
 }
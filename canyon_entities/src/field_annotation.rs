@@ -1,6 +1,10 @@
 use proc_macro2::Ident;
+use quote::ToTokens;
 use std::{collections::HashMap, convert::TryFrom};
-use syn::{punctuated::Punctuated, Attribute, MetaNameValue, Token};
+use syn::{
+    parenthesized, parse::ParseStream, punctuated::Punctuated, Attribute, Expr, LitStr,
+    MetaNameValue, Token,
+};
 
 /// The available annotations for a field that belongs to any struct
 /// annotaded with `#[canyon_entity]`
@@ -8,6 +12,45 @@ use syn::{punctuated::Punctuated, Attribute, MetaNameValue, Token};
 pub enum EntityFieldAnnotation {
     PrimaryKey(bool),
     ForeignKey(String, String),
+    SoftDelete,
+    Unique,
+    // The value of the column is generated by the database, so it's never inserted
+    DbDefault,
+    Version,
+    // Holds if the timestamp is taken from the database clock instead of the app one
+    CreatedAt(bool),
+    UpdatedAt(bool),
+    Validate(Vec<ValidationRule>),
+    // Holds the prefix of the columns where the fields of the value object are flattened
+    Embedded(String),
+    // The related entity, and the column of its table that references this entity
+    HasMany(String, String),
+    // The related entity, the join table, and the columns of the join table that references
+    // this entity and the related one, when they aren't named after the tables of the entities
+    ManyToMany(String, String, Option<String>, Option<String>),
+}
+
+/// The rules that can be declared in a `#[validate(...)]` annotation, checked
+/// over the value of the field before writing it to the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationRule {
+    /// `length(min = 1, max = 50)`. The number of characters of a text
+    /// or the number of elements of a collection
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// `range(min = 0, max = 100)`. The bounds are kept as the Rust expressions
+    /// written by the user, so they are typed as the field
+    Range {
+        min: Option<String>,
+        max: Option<String>,
+    },
+    /// `regex = "^[a-z]+$"`. The pattern that the text must match
+    Regex(String),
+    /// `custom = "path::to::function"`. A function that receives a reference to the
+    /// value of the field, and returns a `Result<(), String>` with the error message
+    Custom(String),
 }
 
 impl EntityFieldAnnotation {
@@ -21,6 +64,24 @@ impl EntityFieldAnnotation {
             Self::ForeignKey(table, column) => {
                 format!("Annotation: ForeignKey, Table: {table}, Column: {column}")
             }
+            Self::SoftDelete => "Annotation: SoftDelete".to_string(),
+            Self::Unique => "Annotation: Unique".to_string(),
+            Self::DbDefault => "Annotation: DbDefault".to_string(),
+            Self::Version => "Annotation: Version".to_string(),
+            Self::CreatedAt(database_clock) => {
+                format!("Annotation: CreatedAt, DatabaseClock: {database_clock}")
+            }
+            Self::UpdatedAt(database_clock) => {
+                format!("Annotation: UpdatedAt, DatabaseClock: {database_clock}")
+            }
+            Self::Validate(rules) => format!("Annotation: Validate, Rules: {rules:?}"),
+            Self::Embedded(prefix) => format!("Annotation: Embedded, Prefix: {prefix}"),
+            Self::HasMany(entity, foreign_key) => {
+                format!("Annotation: HasMany, Entity: {entity}, ForeignKey: {foreign_key}")
+            }
+            Self::ManyToMany(entity, through, _, _) => {
+                format!("Annotation: ManyToMany, Entity: {entity}, Through: {through}")
+            }
         }
     }
 
@@ -70,6 +131,239 @@ impl EntityFieldAnnotation {
         }
     }
 
+    /// Retrieves the user defined clock in the `#[created_at]` and `#[updated_at]` attributes,
+    /// returning true if the database clock must be used
+    fn timestamp_clock_parser(
+        attr_args: &Result<Punctuated<MetaNameValue, Token![,]>, syn::Error>,
+    ) -> syn::Result<bool> {
+        match attr_args {
+            Ok(name_value) => {
+                let mut database_clock = false;
+                for nv in name_value {
+                    let attr_value_ident = nv.path.get_ident().unwrap().to_string();
+                    database_clock =
+                        match (attr_value_ident.as_str(), &nv.lit) {
+                            ("clock", syn::Lit::Str(v)) if v.value() == "app" => false,
+                            ("clock", syn::Lit::Str(v)) if v.value() == "database" => true,
+                            _ => return Err(syn::Error::new_spanned(
+                                nv.path.clone(),
+                                "Only `clock = \"app\"` or `clock = \"database\"` are supported \
+                                for the timestamp attributes",
+                            )),
+                        };
+                }
+                Ok(database_clock)
+            }
+            // Without arguments, the app clock is used
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Retrieves the rules declared in a `#[validate(...)]` attribute, that accepts
+    /// several comma separated rules, like `#[validate(length(max = 50), regex = "^[a-z]+$")]`
+    fn validation_rules_parser(attribute: &Attribute) -> syn::Result<Self> {
+        let rules = attribute.parse_args_with(|input: ParseStream| {
+            Punctuated::<ValidationRule, Token![,]>::parse_terminated_with(
+                input,
+                Self::validation_rule_parser,
+            )
+        })?;
+
+        if rules.is_empty() {
+            return Err(syn::Error::new_spanned(
+                attribute,
+                "The `#[validate]` annotation needs at least one rule",
+            ));
+        }
+
+        Ok(EntityFieldAnnotation::Validate(rules.into_iter().collect()))
+    }
+
+    fn validation_rule_parser(input: ParseStream) -> syn::Result<ValidationRule> {
+        let rule = input.parse::<Ident>()?;
+
+        match rule.to_string().as_str() {
+            "length" => {
+                let (mut min, mut max) = (None, None);
+                for (bound, value) in Self::validation_bounds_parser(&rule, input)? {
+                    let value = match value {
+                        Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(v),
+                            ..
+                        }) => v.base10_parse::<usize>()?,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "Only unsigned integer literals are supported as the bounds of `length`",
+                            ))
+                        }
+                    };
+                    if bound == "min" {
+                        min = Some(value)
+                    } else {
+                        max = Some(value)
+                    }
+                }
+                Ok(ValidationRule::Length { min, max })
+            }
+            "range" => {
+                let (mut min, mut max) = (None, None);
+                for (bound, value) in Self::validation_bounds_parser(&rule, input)? {
+                    let value = Some(value.to_token_stream().to_string());
+                    if bound == "min" {
+                        min = value
+                    } else {
+                        max = value
+                    }
+                }
+                Ok(ValidationRule::Range { min, max })
+            }
+            "regex" => {
+                input.parse::<Token![=]>()?;
+                let pattern = input.parse::<LitStr>()?;
+                regex::Regex::new(&pattern.value()).map_err(|err| {
+                    syn::Error::new_spanned(&pattern, format!("Invalid regular expression: {err}"))
+                })?;
+                Ok(ValidationRule::Regex(pattern.value()))
+            }
+            "custom" => {
+                input.parse::<Token![=]>()?;
+                let function = input.parse::<LitStr>()?;
+                function.parse::<syn::Path>()?;
+                Ok(ValidationRule::Custom(function.value()))
+            }
+            _ => Err(syn::Error::new_spanned(
+                &rule,
+                format!(
+                    "Unknown validation rule `{rule}`. The supported ones are: \
+                    `length`, `range`, `regex` and `custom`"
+                ),
+            )),
+        }
+    }
+
+    /// Parses the `(min = .., max = ..)` bounds of the `length` and `range` rules,
+    /// where at least one of them must be present
+    fn validation_bounds_parser(
+        rule: &Ident,
+        input: ParseStream,
+    ) -> syn::Result<Vec<(Ident, Expr)>> {
+        let content;
+        parenthesized!(content in input);
+        let bounds = Punctuated::<(Ident, Expr), Token![,]>::parse_terminated_with(
+            &content,
+            |input: ParseStream| {
+                let bound = input.parse::<Ident>()?;
+                if bound != "min" && bound != "max" {
+                    return Err(syn::Error::new_spanned(
+                        &bound,
+                        "Only the `min` and `max` bounds are supported",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                Ok((bound, input.parse::<Expr>()?))
+            },
+        )?;
+
+        if bounds.is_empty() {
+            return Err(syn::Error::new_spanned(
+                rule,
+                format!("The `{rule}` rule needs a `min` or a `max` bound"),
+            ));
+        }
+
+        Ok(bounds.into_iter().collect())
+    }
+
+    /// Retrieves the user defined prefix in the `#[embedded]` attribute. Without it,
+    /// the columns are named as the fields of the value object
+    fn embedded_prefix_parser(
+        attr_args: &Result<Punctuated<MetaNameValue, Token![,]>, syn::Error>,
+    ) -> syn::Result<Self> {
+        match attr_args {
+            Ok(name_value) => {
+                let mut prefix = String::new();
+                for nv in name_value {
+                    match &nv.lit {
+                        syn::Lit::Str(v) if nv.path.is_ident("prefix") => prefix = v.value(),
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                nv.path.clone(),
+                                "Only `prefix = \"...\"` is supported for the `embedded` attribute",
+                            ))
+                        }
+                    }
+                }
+                Ok(EntityFieldAnnotation::Embedded(prefix))
+            }
+            Err(_) => Ok(EntityFieldAnnotation::Embedded(String::new())),
+        }
+    }
+
+    /// Retrieves the related entity and the arguments of the `#[has_many(...)]` and
+    /// `#[many_to_many(...)]` attributes, like `#[has_many(Tournament, fk = "league")]`
+    /// or `#[many_to_many(Tag, through = "league_tag")]`
+    fn relation_parser(attribute: &Attribute) -> syn::Result<Self> {
+        let ident = &attribute.path.segments[0].ident;
+        let (entity, name_values) = attribute.parse_args_with(|input: ParseStream| {
+            let entity = input.parse::<syn::Path>()?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+            let name_values = Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?;
+            Ok((entity, name_values))
+        })?;
+        let entity = entity.segments.last().unwrap().ident.to_string();
+
+        let mut data: HashMap<String, String> = HashMap::new();
+        for nv in name_values {
+            let attr_value_ident = nv.path.get_ident().unwrap().to_string();
+            match &nv.lit {
+                syn::Lit::Str(v) => data.insert(attr_value_ident, v.value()),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nv.path.clone(),
+                        format!(
+                        "Only string literals are supported for the `{attr_value_ident}` argument"
+                    ),
+                    ))
+                }
+            };
+        }
+
+        let (required, optional): (&str, &[&str]) = if ident == "has_many" {
+            ("fk", &[])
+        } else {
+            ("through", &["column", "related_column"])
+        };
+        if let Some(unknown) = data
+            .keys()
+            .find(|arg| *arg != required && !optional.contains(&arg.as_str()))
+        {
+            return Err(syn::Error::new_spanned(
+                attribute,
+                format!("Unknown argument `{unknown}` on the `#[{ident}]` annotation"),
+            ));
+        }
+        let required_value = data.remove(required).ok_or_else(|| {
+            syn::Error::new_spanned(
+                attribute,
+                format!("Missed `{required}` argument on the `#[{ident}]` annotation"),
+            )
+        })?;
+
+        Ok(if ident == "has_many" {
+            EntityFieldAnnotation::HasMany(entity, required_value)
+        } else {
+            EntityFieldAnnotation::ManyToMany(
+                entity,
+                required_value,
+                data.remove("column"),
+                data.remove("related_column"),
+            )
+        })
+    }
+
     fn foreign_key_parser(
         ident: &Ident,
         attr_args: &Result<Punctuated<MetaNameValue, Token![,]>, syn::Error>,
@@ -139,6 +433,19 @@ impl TryFrom<&&Attribute> for EntityFieldAnnotation {
         Ok(match ident.to_string().as_str() {
             "primary_key" => EntityFieldAnnotation::primary_key_parser(&ident, &name_values)?,
             "foreign_key" => EntityFieldAnnotation::foreign_key_parser(&ident, &name_values)?,
+            "soft_delete" => EntityFieldAnnotation::SoftDelete,
+            "unique" => EntityFieldAnnotation::Unique,
+            "db_default" => EntityFieldAnnotation::DbDefault,
+            "version" => EntityFieldAnnotation::Version,
+            "created_at" => {
+                EntityFieldAnnotation::CreatedAt(Self::timestamp_clock_parser(&name_values)?)
+            }
+            "updated_at" => {
+                EntityFieldAnnotation::UpdatedAt(Self::timestamp_clock_parser(&name_values)?)
+            }
+            "validate" => Self::validation_rules_parser(attribute)?,
+            "embedded" => Self::embedded_prefix_parser(&name_values)?,
+            "has_many" | "many_to_many" => Self::relation_parser(attribute)?,
             _ => {
                 return Err(syn::Error::new_spanned(
                     ident.clone(),
@@ -1,3 +1,7 @@
+use std::fmt::Debug;
+
+use crate::{CANYON_REGISTER_EMBEDDABLES, CANYON_REGISTER_ENUMS, CANYON_REGISTER_TYPES};
+
 /// This file contains `Rust` types that represents an entry on the `CanyonRegister`
 /// where `Canyon` tracks the user types that has to manage
 
@@ -11,6 +15,21 @@ pub struct CanyonRegisterEntity<'a> {
     pub entity_db_table_name: &'a str,
     pub user_schema_name: Option<&'a str>,
     pub entity_fields: Vec<CanyonRegisterEntityField>,
+    /// The join tables of the `#[many_to_many]` relations declared on the entity
+    pub join_tables: Vec<CanyonRegisterJoinTable>,
+}
+
+/// The join table of a `#[many_to_many]` relation, whose columns references the primary
+/// keys of the entity that declares the relation and the related one, being both of them
+/// its composite primary key
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CanyonRegisterJoinTable {
+    pub table_name: String,
+    /// The column that references the entity that declares the relation
+    pub column: String,
+    pub related_entity_name: String,
+    /// The column that references the related entity
+    pub related_column: String,
 }
 
 /// Complementary type for a field that represents a struct field that maps
@@ -20,9 +39,53 @@ pub struct CanyonRegisterEntityField {
     pub field_name: String,
     pub field_type: String,
     pub annotations: Vec<String>,
+    /// The user defined type of the field, found when the field was registered
+    pub user_type: Option<CanyonRegisterType>,
+    /// The enum of the field, found when the field was registered
+    pub canyon_enum: Option<CanyonRegisterEnum>,
 }
 
 impl CanyonRegisterEntityField {
+    /// Creates the field, finding the user defined type or the enum that it holds
+    /// on the register, so they aren't searched again by their names when the
+    /// rest of the registered types are known.
+    ///
+    /// Returns an error if several different types are registered with the name
+    /// of the one of the field
+    pub fn new(
+        field_name: String,
+        field_type: String,
+        annotations: Vec<String>,
+    ) -> Result<Self, String> {
+        let user_type = CanyonRegisterType::find(&field_type)?;
+        let canyon_enum = CanyonRegisterEnum::find(&field_type)?;
+
+        Ok(Self {
+            field_name,
+            field_type,
+            annotations,
+            user_type,
+            canyon_enum,
+        })
+    }
+
+    /// Returns the Rust type of the field, without whitespaces, being its user defined
+    /// type replaced by the innermost type that it wraps, unless some of them declares
+    /// its own SQL type, that's also returned
+    pub fn resolved_type(&self) -> (String, Option<String>) {
+        let field_type = self.field_type.replace(' ', "");
+        match &self.user_type {
+            Some(user_type) if user_type.sql_type.is_some() => {
+                (field_type, user_type.sql_type.clone())
+            }
+            Some(user_type) if self.is_nullable() => {
+                (format!("Option<{}>", user_type.inner_type), None)
+            }
+            Some(user_type) => (user_type.inner_type.clone(), None),
+            None => (field_type, None),
+        }
+    }
+
     /// Return if the field is autoincremental
     pub fn is_autoincremental(&self) -> bool {
         let has_pk_annotation = self
@@ -35,11 +98,252 @@ impl CanyonRegisterEntityField {
             None => false,
         };
 
-        NUMERIC_PK_DATATYPE.contains(&self.field_type.as_str()) && pk_is_autoincremental
+        let (field_type, _) = self.resolved_type();
+        NUMERIC_PK_DATATYPE.contains(&field_type.as_str()) && pk_is_autoincremental
     }
 
     /// Return the nullability of a the field
     pub fn is_nullable(&self) -> bool {
         self.field_type.to_uppercase().starts_with("OPTION")
     }
+
+    /// Returns the default value of the column, for the fields whose annotations
+    /// requires one to create them as non nullable columns on already populated tables
+    pub fn column_default(&self) -> Option<ColumnDefault> {
+        self.annotations.iter().find_map(|a| {
+            if a.starts_with("Annotation: Version") {
+                Some(ColumnDefault::Value("0"))
+            } else if a.starts_with("Annotation: CreatedAt")
+                || a.starts_with("Annotation: UpdatedAt")
+            {
+                Some(ColumnDefault::CurrentTimestamp)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The default value of a column, that it's written by the migrations
+/// in the syntax of every database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnDefault {
+    /// A literal value, valid for every database
+    Value(&'static str),
+    /// The current `UTC` time of the database server
+    CurrentTimestamp,
+}
+
+/// A user defined type that derives `CanyonType`, so the migrations
+/// are able to declare the columns that holds its values
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterType {
+    pub type_name: String,
+    /// The innermost Rust type that it wraps, being the wrapped user defined types
+    /// replaced by their own inner type when they were registered
+    pub inner_type: String,
+    /// The SQL type declared for the columns that holds its values, by itself
+    /// or by the user defined type that it wraps, if any
+    pub sql_type: Option<String>,
+    /// See [`registry_key`]
+    pub registry_key: String,
+}
+
+impl CanyonRegisterType {
+    /// Finds the registered user defined type that's the given Rust type,
+    /// alone or wrapped in an [`Option`]
+    pub fn find(rust_type: &str) -> Result<Option<Self>, String> {
+        find_registered(
+            &CANYON_REGISTER_TYPES.lock().unwrap(),
+            &without_option(rust_type),
+            |registered| (&registered.type_name, &registered.registry_key),
+        )
+    }
+}
+
+/// A value object that derives `CanyonEmbeddable`, whose fields are flattened into
+/// prefixed columns of the entities that embeds it
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterEmbeddable {
+    pub embeddable_name: String,
+    /// The fields of the value object, in declaration order
+    pub fields: Vec<CanyonRegisterEntityField>,
+    /// See [`registry_key`]
+    pub registry_key: String,
+}
+
+impl CanyonRegisterEmbeddable {
+    /// Finds the registered value object that's the given Rust type
+    pub fn find(rust_type: &str) -> Result<Option<Self>, String> {
+        find_registered(
+            &CANYON_REGISTER_EMBEDDABLES.lock().unwrap(),
+            &rust_type.replace(' ', ""),
+            |registered| (&registered.embeddable_name, &registered.registry_key),
+        )
+    }
+
+    /// Returns the fields of the value object as the columns of an entity that
+    /// embeds it, named with the given prefix
+    pub fn prefixed_fields(&self, prefix: &str) -> Vec<CanyonRegisterEntityField> {
+        self.fields
+            .iter()
+            .map(|field| CanyonRegisterEntityField {
+                field_name: format!("{prefix}{}", field.field_name),
+                ..field.clone()
+            })
+            .collect()
+    }
+}
+
+/// How the values of an enum that derives `CanyonEnum` are stored on the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnumRepr {
+    /// The label of the variant, on a text column constrained to the known labels
+    #[default]
+    Text,
+    /// The discriminant of the variant, on an integer column constrained to the known ones
+    Int,
+    /// The label of the variant, on a column of a native `ENUM` type on `PostgreSQL`.
+    /// The other databases stores them as [`EnumRepr::Text`]
+    PgEnum,
+}
+
+/// A user defined enum that derives `CanyonEnum`, so the migrations are able
+/// to declare the database type or the constraint that restricts its values
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterEnum {
+    pub enum_name: String,
+    /// The name of the native `ENUM` type on `PostgreSQL`
+    pub db_type_name: String,
+    pub repr: EnumRepr,
+    /// The labels or the discriminants of the variants, in declaration order
+    pub variants: Vec<String>,
+    /// See [`registry_key`]. It's shared with the entry of the enum on the
+    /// user defined types
+    pub registry_key: String,
+}
+
+impl CanyonRegisterEnum {
+    /// Finds the registered enum that's the given Rust type, alone or wrapped in an [`Option`]
+    pub fn find(rust_type: &str) -> Result<Option<Self>, String> {
+        find_registered(
+            &CANYON_REGISTER_ENUMS.lock().unwrap(),
+            &without_option(rust_type),
+            |registered| (&registered.enum_name, &registered.registry_key),
+        )
+    }
+
+    /// The name of the `CHECK` constraint that restricts the values of the column
+    /// that holds the enum
+    pub fn check_constraint_name(table_name: &str, column_name: &str) -> String {
+        format!("{table_name}_{column_name}_enum_check")
+    }
+
+    /// The values of the variants as SQL literals, separated by commas
+    pub fn sql_values(&self) -> String {
+        self.variants
+            .iter()
+            .map(|variant| match self.repr {
+                EnumRepr::Int => variant.to_owned(),
+                EnumRepr::Text | EnumRepr::PgEnum => format!("'{}'", variant.replace('\'', "''")),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// Returns the key that identifies a new entry of the register, made of its content,
+/// so the entries of the types declared several times with the same content shares it.
+///
+/// The macros only knows the path written for the type of a field, so they find its
+/// registered type by the name of the last segment. The implementation of the trait
+/// of the registered type returns this key, so the macros asserts at compile time
+/// that the type written for the field is the one found by its name
+pub fn registry_key(entry: &impl Debug) -> String {
+    format!("{entry:?}")
+}
+
+/// Returns the given Rust type, without whitespaces and without the [`Option`] that may wrap it
+fn without_option(rust_type: &str) -> String {
+    let rust_type = rust_type.replace(' ', "");
+    match rust_type
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        Some(inner_type) => inner_type.to_owned(),
+        None => rust_type,
+    }
+}
+
+/// Finds the entry of the register that's the given Rust type, by the name of
+/// the last segment of its path, being an error if the register has several
+/// different entries with that name. `name_and_key` returns the name and the
+/// [`registry_key`] of an entry
+fn find_registered<T: Clone>(
+    registry: &[T],
+    rust_type: &str,
+    name_and_key: impl Fn(&T) -> (&str, &str),
+) -> Result<Option<T>, String> {
+    let type_name = rust_type.rsplit("::").next().unwrap_or_default();
+
+    let mut found: Option<&T> = None;
+    for registered in registry
+        .iter()
+        .filter(|registered| name_and_key(registered).0 == type_name)
+    {
+        match found {
+            Some(found) if name_and_key(found).1 != name_and_key(registered).1 => {
+                return Err(format!(
+                    "Several different types named `{type_name}` are registered, so it isn't \
+                    possible to know which one of them is `{rust_type}`. Rename one of them"
+                ));
+            }
+            _ => found = Some(registered),
+        }
+    }
+
+    Ok(found.cloned())
+}
+
+#[cfg(test)]
+mod register_types_tests {
+    use super::*;
+
+    fn registered_type(type_name: &str, inner_type: &str) -> CanyonRegisterType {
+        let mut registered = CanyonRegisterType {
+            type_name: type_name.to_owned(),
+            inner_type: inner_type.to_owned(),
+            ..Default::default()
+        };
+        registered.registry_key = registry_key(&registered);
+        registered
+    }
+
+    #[test]
+    fn test_find_registered_by_the_type_name() {
+        let registry = [
+            registered_type("UserId", "i64"),
+            registered_type("Email", "String"),
+            registered_type("Email", "String"),
+        ];
+        fn name_and_key(registered: &CanyonRegisterType) -> (&str, &str) {
+            (&registered.type_name, &registered.registry_key)
+        }
+
+        let found = find_registered(&registry, "crate::users::UserId", name_and_key).unwrap();
+        assert_eq!(found.unwrap().inner_type, "i64");
+        // The same type declared twice isn't ambiguous
+        assert!(find_registered(&registry, "Email", name_and_key)
+            .unwrap()
+            .is_some());
+        assert!(find_registered(&registry, "Id", name_and_key)
+            .unwrap()
+            .is_none());
+
+        let registry = [
+            registered_type("Email", "String"),
+            registered_type("Email", "i64"),
+        ];
+        assert!(find_registered(&registry, "users::Email", name_and_key).is_err());
+    }
 }
@@ -11,28 +11,41 @@ mod utils;
 use canyon_entity_macro::parse_canyon_entity_proc_macro_attr;
 use proc_macro::TokenStream as CompilerTokenStream;
 use proc_macro2::{Ident, TokenStream};
-use quote::{quote, ToTokens};
-use syn::{DeriveInput, Fields, Type, Visibility};
+use quote::{format_ident, quote, ToTokens};
+use syn::{DeriveInput, Fields, Type};
 
 use query_operations::{
-    delete::{generate_delete_query_tokens, generate_delete_tokens},
-    insert::{generate_insert_tokens, generate_multiple_insert_tokens},
+    delete::{generate_delete_by_pks_tokens, generate_delete_query_tokens, generate_delete_tokens},
+    insert::{
+        generate_insert_returning_tokens, generate_insert_tokens, generate_multiple_insert_tokens,
+    },
     select::{
         generate_count_tokens, generate_find_all_query_tokens, generate_find_all_tokens,
-        generate_find_all_unchecked_tokens, generate_find_by_foreign_key_tokens,
-        generate_find_by_pk_tokens, generate_find_by_reverse_foreign_key_tokens,
+        generate_find_all_unchecked_tokens, generate_find_by_field_tokens,
+        generate_find_by_foreign_key_tokens, generate_find_by_pk_tokens,
+        generate_find_by_pks_tokens, generate_find_by_reverse_foreign_key_tokens,
+        generate_relation_methods_tokens, generate_relation_tokens,
     },
-    update::{generate_update_query_tokens, generate_update_tokens},
+    update::{generate_trackable_tokens, generate_update_query_tokens, generate_update_tokens},
+    validate::generate_validate_tokens,
+};
+use utils::{
+    function_parser::FunctionParser,
+    helpers::{self, MappedColumn},
+    macro_tokens::{self, MacroTokens},
 };
-use utils::{function_parser::FunctionParser, helpers, macro_tokens::MacroTokens};
 
 use canyon_entities::{
     entity::CanyonEntity,
     manager_builder::{
         generate_enum_with_fields, generate_enum_with_fields_values, generate_user_struct,
     },
-    register_types::{CanyonRegisterEntity, CanyonRegisterEntityField},
-    CANYON_REGISTER_ENTITIES,
+    register_types::{
+        self, CanyonRegisterEmbeddable, CanyonRegisterEntity, CanyonRegisterEntityField,
+        CanyonRegisterEnum, CanyonRegisterType, EnumRepr,
+    },
+    CANYON_REGISTER_EMBEDDABLES, CANYON_REGISTER_ENTITIES, CANYON_REGISTER_ENUMS,
+    CANYON_REGISTER_TYPES,
 };
 
 /// Macro for handling the entry point to the program.
@@ -173,20 +186,15 @@ pub fn canyon_entity(
     ));
     new_entity.user_schema_name = schema_name;
 
-    // The entity fields
+    // The entity fields, with the `#[embedded]` ones flattened into their columns,
+    // and the join tables of its `#[many_to_many]` relations
     for field in entity.fields.iter() {
-        let mut new_entity_field = CanyonRegisterEntityField {
-            field_name: field.name.to_string(),
-            field_type: field.get_field_type_as_string().replace(' ', ""),
-            ..Default::default()
-        };
-
-        field
-            .attributes
-            .iter()
-            .for_each(|attr| new_entity_field.annotations.push(attr.get_as_string()));
-
-        new_entity.entity_fields.push(new_entity_field);
+        new_entity.entity_fields.extend(field.columns());
+        new_entity.join_tables.extend(
+            field.relation().and_then(|relation| {
+                helpers::join_table(new_entity.entity_db_table_name, relation)
+            }),
+        );
     }
 
     // Fill the register with the data of the attached struct
@@ -195,16 +203,37 @@ pub fn canyon_entity(
         .expect("Error acquiring Mutex guard on Canyon Entity macro")
         .push(new_entity);
 
+    // The registered types of the fields were found by their names, so the compiler
+    // checks that they're the types written on the fields
+    let registry_key_assertions =
+        entity
+            .fields
+            .iter()
+            .filter_map(|field| match (&field.embeddable, field.user_type()) {
+                (Some(embeddable), _) => Some(helpers::registry_key_assertion(
+                    &field.field_type,
+                    quote! { canyon_sql::crud::CanyonEmbeddable },
+                    &embeddable.registry_key,
+                )),
+                (None, Some(user_type)) => Some(helpers::registry_key_assertion(
+                    &field.field_type,
+                    quote! { canyon_sql::crud::CanyonType },
+                    &user_type.registry_key,
+                )),
+                (None, None) => None,
+            });
+
     // Assemble everything
     let tokens = quote! {
         #generated_user_struct
+        #(#registry_key_assertions)*
     };
 
     // Pass the result back to the compiler
     if let Some(macro_error) = parsing_attribute_error {
         quote! {
             #macro_error
-            #generated_user_struct
+            #tokens
         }
         .into()
     } else {
@@ -215,7 +244,14 @@ pub fn canyon_entity(
 /// Allows the implementors to auto-derive the `CrudOperations` trait, which defines the methods
 /// that will perform the database communication and the implementation of the queries for every
 /// type, as defined in the `CrudOperations` + `Transaction` traits.
-#[proc_macro_derive(CanyonCrud)]
+///
+/// It also implements the `CanyonHooks` trait with the default (empty) lifecycle hooks,
+/// unless the type is annotated with `#[canyon_crud(hooks)]`, which means that the
+/// user writes its own implementation.
+///
+/// The `Trackable` trait is only implemented if the type is annotated with
+/// `#[canyon_crud(tracked)]`, because it needs every field to be `Clone` and `PartialEq`.
+#[proc_macro_derive(CanyonCrud, attributes(canyon_crud))]
 pub fn crud_operations(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Construct a representation of Rust code as a syntax tree
     // that we can manipulate
@@ -233,16 +269,52 @@ pub fn crud_operations(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
         table_name_res.ok().unwrap()
     };
 
+    let crud_options = match helpers::crud_options(&macro_data) {
+        Ok(options) => options,
+        Err(err) => return err.into(),
+    };
+
+    let registry_key_assertions = match macro_data.check_embedded_fields() {
+        Ok(registry_key_assertions) => registry_key_assertions,
+        Err(err) => return err.into(),
+    };
+
     // Build the trait implementation
-    impl_crud_operations_trait_for_struct(&macro_data, table_schema_data)
+    let tokens: TokenStream =
+        impl_crud_operations_trait_for_struct(&macro_data, table_schema_data, crud_options).into();
+    quote! {
+        #tokens
+        #registry_key_assertions
+    }
+    .into()
 }
 
 fn impl_crud_operations_trait_for_struct(
     macro_data: &MacroTokens<'_>,
     table_schema_data: String,
+    crud_options: helpers::CrudOptions,
 ) -> proc_macro::TokenStream {
     let ty = macro_data.ty;
 
+    // Checks the rules of the `#[validate(...)]` annotations
+    let validate_tokens = generate_validate_tokens(macro_data);
+
+    // Allows to update only the modified columns, if the entity opts in
+    let trackable_tokens = if crud_options.tracked {
+        generate_trackable_tokens(macro_data, &table_schema_data)
+    } else {
+        quote! {}
+    };
+
+    // The default lifecycle hooks, if the user doesn't provide them
+    let hooks_tokens = if crud_options.user_defined_hooks {
+        quote! {}
+    } else {
+        quote! {
+            impl canyon_sql::crud::CanyonHooks for #ty {}
+        }
+    };
+
     // Builds the find_all() query
     let _find_all_unchecked_tokens =
         generate_find_all_unchecked_tokens(macro_data, &table_schema_data);
@@ -259,6 +331,8 @@ fn impl_crud_operations_trait_for_struct(
 
     // Builds the insert() query
     let _insert_tokens = generate_insert_tokens(macro_data, &table_schema_data);
+    // Builds the insert_returning() query
+    let _insert_returning_tokens = generate_insert_returning_tokens(macro_data, &table_schema_data);
     // Builds the insert_multi() query
     let _insert_multi_tokens = generate_multiple_insert_tokens(macro_data, &table_schema_data);
 
@@ -273,6 +347,23 @@ fn impl_crud_operations_trait_for_struct(
     // Builds the delete() query as a QueryBuilder
     let _delete_query_tokens = generate_delete_query_tokens(macro_data, &table_schema_data);
 
+    // The column of the primary key, and its value, if any
+    let _primary_key_tokens = match macro_data.get_primary_key_annotation() {
+        Some(primary_key) => {
+            let pk_field = Ident::new(&primary_key, proc_macro2::Span::call_site());
+            quote! {
+                fn primary_key() -> Option<&'static str> {
+                    Some(#primary_key)
+                }
+
+                fn primary_key_value(&self) -> Option<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> {
+                    Some(&self.#pk_field)
+                }
+            }
+        }
+        None => quote! {},
+    };
+
     // Search by foreign (d) key as Vec, cause Canyon supports multiple fields having FK annotation
     let _search_by_fk_tokens: Vec<(TokenStream, TokenStream)> =
         generate_find_by_foreign_key_tokens(macro_data);
@@ -287,6 +378,101 @@ fn impl_crud_operations_trait_for_struct(
     let rev_fk_method_implementations =
         _search_by_revese_fk_tokens.iter().map(|(_, m_impl)| m_impl);
 
+    // The finders by every field of the entity, that are held in their own trait
+    let _find_by_field_tokens: Vec<(TokenStream, TokenStream)> =
+        generate_find_by_field_tokens(macro_data, &table_schema_data);
+    let finders_tokens = if _find_by_field_tokens.is_empty() {
+        quote! {}
+    } else {
+        let finders_trait_ident = Ident::new(
+            &format!("{}Finders", &ty.to_string()),
+            proc_macro2::Span::call_site(),
+        );
+        let finder_signatures = _find_by_field_tokens.iter().map(|(sign, _)| sign);
+        let finder_implementations = _find_by_field_tokens.iter().map(|(_, f_impl)| f_impl);
+
+        quote! {
+            /// Generated trait that holds the `find_by`, `find_one_by`, `exists_by` and
+            /// `count_by` operations for every field of the entity
+            #[canyon_sql::macros::async_trait]
+            pub trait #finders_trait_ident {
+                #(#finder_signatures)*
+            }
+            #[canyon_sql::macros::async_trait]
+            impl #finders_trait_ident for #ty {
+                #(#finder_implementations)*
+            }
+        }
+    };
+
+    // The operations over several primary keys at once, held in their own trait
+    let mut _pk_operations_tokens: Vec<(TokenStream, TokenStream)> =
+        generate_find_by_pks_tokens(macro_data, &table_schema_data);
+    _pk_operations_tokens.extend(generate_delete_by_pks_tokens(
+        macro_data,
+        &table_schema_data,
+    ));
+    let pk_operations_tokens = if _pk_operations_tokens.is_empty() {
+        quote! {}
+    } else {
+        let pk_operations_trait_ident = Ident::new(
+            &format!("{}PkOperations", &ty.to_string()),
+            proc_macro2::Span::call_site(),
+        );
+        let pk_operation_signatures = _pk_operations_tokens.iter().map(|(sign, _)| sign);
+        let pk_operation_implementations = _pk_operations_tokens.iter().map(|(_, op_impl)| op_impl);
+
+        quote! {
+            /// Generated trait that holds the `find_by_pks`, `exists` and `delete_by_pks`
+            /// operations of the entity
+            #[canyon_sql::macros::async_trait]
+            pub trait #pk_operations_trait_ident {
+                #(#pk_operation_signatures)*
+            }
+            #[canyon_sql::macros::async_trait]
+            impl #pk_operations_trait_ident for #ty {
+                #(#pk_operation_implementations)*
+            }
+        }
+    };
+
+    // The relations that can be loaded along with the entity, one for every fk
+    let relation_tokens = generate_relation_tokens(macro_data);
+
+    // The operations over the related entities of the `#[has_many]` and `#[many_to_many]`
+    // fields, that are held in their own trait
+    let _relation_operations_tokens =
+        match generate_relation_methods_tokens(macro_data, &table_schema_data) {
+            Ok(tokens) => tokens,
+            Err(err) => return err.into(),
+        };
+    let relation_operations_tokens = if _relation_operations_tokens.is_empty() {
+        quote! {}
+    } else {
+        let relation_operations_trait_ident = Ident::new(
+            &format!("{}RelationOperations", &ty.to_string()),
+            proc_macro2::Span::call_site(),
+        );
+        let relation_operation_signatures =
+            _relation_operations_tokens.iter().map(|(sign, _)| sign);
+        let relation_operation_implementations = _relation_operations_tokens
+            .iter()
+            .map(|(_, op_impl)| op_impl);
+
+        quote! {
+            /// Generated trait that holds the `load`, `add`, `remove` and `set` operations
+            /// over the related entities of the `#[has_many]` and `#[many_to_many]` fields
+            #[canyon_sql::macros::async_trait]
+            pub trait #relation_operations_trait_ident {
+                #(#relation_operation_signatures)*
+            }
+            #[canyon_sql::macros::async_trait]
+            impl #relation_operations_trait_ident for #ty {
+                #(#relation_operation_implementations)*
+            }
+        }
+    };
+
     // The autogenerated name for the trait that holds the fk and rev fk searches
     let fk_trait_ident = Ident::new(
         &format!("{}FkOperations", &ty.to_string()),
@@ -312,6 +498,9 @@ fn impl_crud_operations_trait_for_struct(
         // The insert impl
         #_insert_tokens
 
+        // The insert that reloads the inserted row impl
+        #_insert_returning_tokens
+
         // The insert of multiple entities impl
         #_insert_multi_tokens
 
@@ -326,6 +515,9 @@ fn impl_crud_operations_trait_for_struct(
 
         // The delete as querybuilder impl
         #_delete_query_tokens
+
+        // The primary key impl
+        #_primary_key_tokens
     };
 
     let tokens = if !_search_by_fk_tokens.is_empty() {
@@ -337,6 +529,20 @@ fn impl_crud_operations_trait_for_struct(
 
             impl canyon_sql::crud::Transaction<#ty> for #ty {}
 
+            #hooks_tokens
+
+            #validate_tokens
+
+            #trackable_tokens
+
+            #finders_tokens
+
+            #pk_operations_tokens
+
+            #relation_operations_tokens
+
+            #relation_tokens
+
             /// Hidden trait for generate the foreign key operations available
             /// in Canyon without have to define them before hand in CrudOperations
             /// because it's just impossible with the actual system (where the methods
@@ -366,6 +572,18 @@ fn impl_crud_operations_trait_for_struct(
             }
 
             impl canyon_sql::crud::Transaction<#ty> for #ty {}
+
+            #hooks_tokens
+
+            #validate_tokens
+
+            #trackable_tokens
+
+            #finders_tokens
+
+            #pk_operations_tokens
+
+            #relation_operations_tokens
         }
     };
 
@@ -383,33 +601,53 @@ pub fn implement_foreignkeyable_for_type(
 ) -> proc_macro::TokenStream {
     // Gets the data from the AST
     let ast: DeriveInput = syn::parse(input).unwrap();
-    let ty = ast.ident;
+    let ty = &ast.ident;
 
-    // Recovers the identifiers of the structs members
-    let fields = filter_fields(match ast.data {
-        syn::Data::Struct(ref s) => &s.fields,
-        _ => {
-            return syn::Error::new(ty.span(), "ForeignKeyable only works with Structs")
-                .to_compile_error()
-                .into()
-        }
-    });
+    if !matches!(ast.data, syn::Data::Struct(_)) {
+        return syn::Error::new(ty.span(), "ForeignKeyable only works with Structs")
+            .to_compile_error()
+            .into();
+    }
 
-    let field_idents = fields.iter().map(|(_vis, ident)| {
-        let i = ident.to_string();
-        quote! {
-            #i => Some(&self.#ident as &dyn canyon_sql::crud::bounds::QueryParameter<'_>)
+    // Recovers the columns of the structs members, being the ones of the `#[embedded]`
+    // value objects found through their prefixes
+    let macro_data = MacroTokens::new(&ast);
+    let mut field_idents = Vec::new();
+    let mut embedded_columns = Vec::new();
+    for field in macro_data.fields.iter() {
+        if macro_tokens::relation_annotation(field).is_some() {
+            continue;
         }
-    });
-    let field_idents_cloned = field_idents.clone();
+        let ident = field.ident.as_ref().unwrap();
+        match macro_tokens::embedded_prefix(field) {
+            Some(prefix) => embedded_columns.push(quote! {
+                if let Some(embedded_column) = column.strip_prefix(#prefix) {
+                    if let Some(value) =
+                        canyon_sql::crud::CanyonEmbeddable::get_column(&self.#ident, embedded_column)
+                    {
+                        return Some(value);
+                    }
+                }
+            }),
+            None => {
+                let i = ident.to_string();
+                field_idents.push(quote! {
+                    #i => Some(&self.#ident as &dyn canyon_sql::crud::bounds::QueryParameter<'_>)
+                });
+            }
+        }
+    }
+    let field_idents_cloned = &field_idents;
+    let embedded_columns_cloned = &embedded_columns;
 
     quote! {
         /// Implementation of the trait `ForeignKeyable` for the type
         /// calling this derive proc macro
         impl canyon_sql::crud::bounds::ForeignKeyable<Self> for #ty {
             fn get_fk_column(&self, column: &str) -> Option<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> {
+                #(#embedded_columns)*
                 match column {
-                    #(#field_idents),*,
+                    #(#field_idents,)*
                     _ => None
                 }
             }
@@ -418,8 +656,9 @@ pub fn implement_foreignkeyable_for_type(
         /// calling this derive proc macro
         impl canyon_sql::crud::bounds::ForeignKeyable<&Self> for &#ty {
             fn get_fk_column<'a>(&self, column: &'a str) -> Option<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> {
+                #(#embedded_columns_cloned)*
                 match column {
-                    #(#field_idents_cloned),*,
+                    #(#field_idents_cloned,)*
                     _ => None
                 }
             }
@@ -427,165 +666,705 @@ pub fn implement_foreignkeyable_for_type(
     }.into()
 }
 
-#[proc_macro_derive(CanyonMapper)]
+/// Implements the `RowMapper` trait for the type, mapping every field against the
+/// column named as it.
+///
+/// The fields annotated with `#[embedded(prefix = "...")]` holds a value object that
+/// derives `CanyonEmbeddable`, mapped from a prefixed column for every field of it.
+///
+/// A field can be read from the column placed at some position with `#[column_index(n)]`,
+/// or from the first present of several columns with `#[column(alias = "...")]`. The types
+/// annotated with `#[canyon_mapper(lenient)]` maps their optional fields as `None` when
+/// their columns aren't present on the rows.
+///
+/// The fields that holds the related entities of a `#[has_many]` or a `#[many_to_many]`
+/// aren't columns, so they are mapped as an empty collection
+#[proc_macro_derive(
+    CanyonMapper,
+    attributes(embedded, canyon_mapper, column_index, column)
+)]
 pub fn implement_row_mapper_for_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Gets the data from the AST
     let ast: DeriveInput = syn::parse(input).unwrap();
 
     // Recovers the identifiers of the structs members
-    let fields = fields_with_types(match ast.data {
+    let fields = match ast.data {
         syn::Data::Struct(ref s) => &s.fields,
         _ => {
             return syn::Error::new(ast.ident.span(), "CanyonMapper only works with Structs")
                 .to_compile_error()
                 .into()
         }
-    });
+    };
+    let macro_data = MacroTokens::new(&ast);
+    let registry_key_assertions = match macro_data.check_embedded_fields() {
+        Ok(registry_key_assertions) => registry_key_assertions,
+        Err(err) => return err.into(),
+    };
+
+    // The type of the Struct
+    let ty = &ast.ident;
+    let entity_name = ty.to_string();
+
+    let lenient = match helpers::is_lenient_mapper(&ast.attrs) {
+        Ok(lenient) => lenient,
+        Err(err) => return err.into(),
+    };
+    let mut mapped_columns = Vec::new();
+    for field in fields.iter() {
+        let mapped_column = match helpers::mapped_column(field) {
+            Ok(mapped_column) => mapped_column,
+            Err(err) => return err.into(),
+        };
+        let is_renamed = !matches!(&mapped_column, MappedColumn::Named(names) if names.len() == 1);
+        if is_renamed && macro_tokens::embedded_prefix(field).is_some() {
+            return syn::Error::new_spanned(
+                field,
+                "The `#[embedded]` fields can't be annotated with `#[column_index]` or `#[column]`",
+            )
+            .into_compile_error()
+            .into();
+        }
+        mapped_columns.push(mapped_column);
+    }
 
     // Here it's where the incoming values of the DatabaseResult are wired into a new
     // instance, mapping the fields of the type against the columns
-    let init_field_values = fields.iter().map(|(_vis, ident, _ty)| {
-        let ident_name = ident.to_string();
-        quote! {
-            #ident: row.try_get(#ident_name)
-                .expect(format!("Failed to retrieve the {} field", #ident_name).as_ref())
+    let init_field_values = |backend: &str| {
+        fields
+            .iter()
+            .zip(&mapped_columns)
+            .map(|(field, mapped_column)| {
+                let ident = field.ident.as_ref().unwrap();
+                let field_ty = &field.ty;
+                let expected = get_field_type_as_string(field_ty).replace(' ', "");
+
+                // The related entities are loaded afterwards, with the generated relation methods
+                if macro_tokens::relation_annotation(field).is_some() {
+                    return quote! { #ident: Default::default() };
+                }
+
+                if let Some(prefix) = macro_tokens::embedded_prefix(field) {
+                    let try_from_row = format_ident!("try_from_{}_row", backend);
+                    return quote! {
+                        #ident: <#field_ty as canyon_sql::crud::CanyonEmbeddable>::#try_from_row(
+                            row, #entity_name, #prefix
+                        )?
+                    };
+                }
+
+                let value = match mapped_column {
+                    MappedColumn::Named(names) if names.len() == 1 => {
+                        let try_get = format_ident!("try_get_{}_column", backend);
+                        let name = &names[0];
+                        quote! { canyon_sql::crud::#try_get(row, #entity_name, #name, #expected) }
+                    }
+                    MappedColumn::Named(names) => {
+                        let try_get = format_ident!("try_get_{}_column_aliased", backend);
+                        quote! {
+                            canyon_sql::crud::#try_get(row, #entity_name, &[#(#names),*], #expected)
+                        }
+                    }
+                    MappedColumn::Index(idx) => {
+                        let try_get = format_ident!("try_get_{}_column_at", backend);
+                        quote! { canyon_sql::crud::#try_get(row, #entity_name, #idx, #expected) }
+                    }
+                };
+
+                if lenient && helpers::option_inner_type(field_ty).is_some() {
+                    quote! { #ident: canyon_sql::crud::default_if_missing(#value)? }
+                } else {
+                    quote! { #ident: #value? }
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let init_field_values_postgres = init_field_values("postgres");
+    let init_field_values_sqlserver = init_field_values("sqlserver");
+    let init_field_values_mysql = init_field_values("mysql");
+
+    // Only the columns that must be present on every row are reported, leaving out
+    // the ones read by position or through aliases, and the optional ones of the
+    // lenient mappers
+    let unreported_fields = fields
+        .iter()
+        .zip(&mapped_columns)
+        .filter(|(field, mapped_column)| match mapped_column {
+            MappedColumn::Named(names) if names.len() == 1 => {
+                lenient && helpers::option_inner_type(&field.ty).is_some()
+            }
+            _ => true,
+        })
+        .filter_map(|(field, _)| field.ident.as_ref())
+        .collect::<Vec<_>>();
+    let column_names = macro_data
+        .get_columns()
+        .into_iter()
+        .filter(|column| !unreported_fields.contains(&column.field))
+        .map(|column| column.name);
+
+    let tokens = quote! {
+        impl canyon_sql::crud::RowMapper<Self> for #ty {
+            fn columns() -> &'static [&'static str] {
+                &[#(#column_names),*]
+            }
+
+            #[cfg(feature="postgres")]
+            fn try_deserialize_postgresql(
+                row: &canyon_sql::db_clients::tokio_postgres::Row
+            ) -> Result<#ty, canyon_sql::crud::errors::RowMapperError> {
+                Ok(Self {
+                    #(#init_field_values_postgres),*
+                })
+            }
+            #[cfg(feature="mssql")]
+            fn try_deserialize_sqlserver(
+                row: &canyon_sql::db_clients::tiberius::Row
+            ) -> Result<#ty, canyon_sql::crud::errors::RowMapperError> {
+                Ok(Self {
+                    #(#init_field_values_sqlserver),*
+                })
+            }
+            #[cfg(feature="mysql")]
+            fn try_deserialize_mysql(
+                row: &canyon_sql::db_clients::mysql_async::Row
+            ) -> Result<#ty, canyon_sql::crud::errors::RowMapperError> {
+                Ok(Self {
+                    #(#init_field_values_mysql),*
+                })
+            }
+        }
+
+        #registry_key_assertions
+    };
+
+    tokens.into()
+}
+
+/// Implements the `CanyonType` trait for a newtype over some type already supported
+/// by Canyon, like `struct UserId(i64)`, so it can be used as the type of the fields
+/// of the entities, on the filters of the query builders and by the migrations.
+///
+/// The columns are declared with the SQL type of the wrapped type, unless another
+/// one is given with `#[canyon_type(sql_type = "...")]`.
+///
+/// The macros finds the type by its name, so it must be declared before the entities
+/// that uses it, and it can't share its name with another type that derives `CanyonType`
+/// or `CanyonEnum`. The types of other crates aren't known by the migrations
+#[proc_macro_derive(CanyonType, attributes(canyon_type))]
+pub fn implement_canyon_type_for_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let ty = ast.ident;
+
+    let sql_type = match helpers::declared_sql_type(&ast.attrs) {
+        Ok(sql_type) => sql_type,
+        Err(err) => return err.into(),
+    };
+
+    let field = match ast.data {
+        syn::Data::Struct(ref s) if s.fields.len() == 1 => s.fields.iter().next().unwrap(),
+        _ => {
+            return syn::Error::new(
+                ty.span(),
+                "CanyonType only works with structs that have exactly one field",
+            )
+            .to_compile_error()
+            .into()
         }
+    };
+    let inner_ty = &field.ty;
+    let (member, constructor) = match &field.ident {
+        Some(ident) => (quote! { #ident }, quote! { |value| Self { #ident: value } }),
+        None => (quote! { 0 }, quote! { Self }),
+    };
+
+    // The wrapped user defined type is resolved now, being checked by the compiler
+    // that it's the one found by its name
+    let inner_type = get_field_type_as_string(inner_ty).replace(' ', "");
+    let wrapped_type = match CanyonRegisterType::find(&inner_type) {
+        Ok(wrapped_type) => wrapped_type,
+        Err(err) => {
+            return syn::Error::new_spanned(inner_ty, err)
+                .into_compile_error()
+                .into()
+        }
+    };
+    let wrapped_type_assertion = wrapped_type.as_ref().map(|wrapped_type| {
+        helpers::registry_key_assertion(
+            inner_ty,
+            quote! { canyon_sql::crud::CanyonType },
+            &wrapped_type.registry_key,
+        )
     });
+    let mut registered_type = match wrapped_type {
+        Some(wrapped_type) if sql_type.is_none() => CanyonRegisterType {
+            type_name: ty.to_string(),
+            inner_type: wrapped_type.inner_type,
+            sql_type: wrapped_type.sql_type,
+            ..Default::default()
+        },
+        _ => CanyonRegisterType {
+            type_name: ty.to_string(),
+            inner_type,
+            sql_type: sql_type.clone(),
+            ..Default::default()
+        },
+    };
+    registered_type.registry_key = register_types::registry_key(&registered_type);
+    let registry_key = registered_type.registry_key.clone();
 
-    let init_field_values_sqlserver = fields.iter().map(|(_vis, ident, ty)| {
-        let ident_name = ident.to_string();
+    CANYON_REGISTER_TYPES
+        .lock()
+        .expect("Error acquiring Mutex guard on Canyon Type macro")
+        .push(registered_type);
 
-        if get_field_type_as_string(ty) == "String" {
-            quote! {
-                #ident: row.get::<&str, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
-                    .to_string()
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<i64>" {
-            quote! {
-                #ident: row.get::<i64, &str>(#ident_name)
+    let sql_type = match sql_type {
+        Some(sql_type) => quote! { Some(#sql_type) },
+        None => quote! { None },
+    };
+
+    quote! {
+        impl canyon_sql::crud::CanyonType for #ty {
+            fn encode(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+                &self.#member
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<i32>" {
-            quote! {
-                #ident: row.get::<i32, &str>(#ident_name)
+
+            fn encode_null() -> &'static dyn canyon_sql::crud::bounds::QueryParameter<'static> {
+                &None::<#inner_ty>
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<i16>" {
-            quote! {
-                #ident: row.get::<i16, &str>(#ident_name)
+
+            #[cfg(feature="postgres")]
+            fn decode_postgres(
+                row: &canyon_sql::db_clients::tokio_postgres::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<#inner_ty> as canyon_sql::crud::FromColumn>::from_postgres_column(row, idx)
+                    .map(|value| value.map(#constructor))
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<f32>" {
-            quote! {
-                #ident: row.get::<f32, &str>(#ident_name)
+            #[cfg(feature="mssql")]
+            fn decode_sqlserver(
+                row: &canyon_sql::db_clients::tiberius::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<#inner_ty> as canyon_sql::crud::FromColumn>::from_sqlserver_column(row, idx)
+                    .map(|value| value.map(#constructor))
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<f64>" {
-            quote! {
-                #ident: row.get::<f64, &str>(#ident_name)
+            #[cfg(feature="mysql")]
+            fn decode_mysql(
+                row: &canyon_sql::db_clients::mysql_async::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<#inner_ty> as canyon_sql::crud::FromColumn>::from_mysql_column(row, idx)
+                    .map(|value| value.map(#constructor))
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<String>" {
-            quote! {
-                #ident: row.get::<&str, &str>(#ident_name)
-                    .map( |x| x.to_owned() )
+
+            fn sql_type() -> Option<&'static str> {
+                #sql_type
             }
-        } else if get_field_type_as_string(ty) == "NaiveDate" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDate, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+
+            const REGISTRY_KEY: &'static str = #registry_key;
+        }
+
+        #wrapped_type_assertion
+    }
+    .into()
+}
+
+/// Implements the `CanyonType` trait for an enum whose variants doesn't hold any data,
+/// so it can be used as the type of the fields of the entities, on the filters of the
+/// query builders and by the migrations.
+///
+/// The way of storing the variants is chosen with `#[canyon_enum(repr = "...")]`:
+/// - `text` (the default) stores the labels of the variants, on a text column
+/// - `int` stores the discriminants of the variants, on an integer column
+/// - `pg_enum` stores the labels on a column of a native `ENUM` type on `PostgreSQL`,
+///   named with `type_name = "..."` or after the enum in snake case. The other
+///   databases stores them as `text`
+///
+/// The labels are the names of the variants in snake case, unless another one is
+/// given with `#[canyon_enum(rename = "...")]` on the variant. The migrations
+/// restricts the values of the text and integer columns with a `CHECK` constraint.
+///
+/// As the ones that derives `CanyonType`, the enum must be declared before the entities
+/// that uses it, with a name that isn't shared with another of those types
+#[proc_macro_derive(CanyonEnum, attributes(canyon_enum))]
+pub fn implement_canyon_enum_for_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let ty = ast.ident;
+    let enum_name = ty.to_string();
+
+    let data_enum = match ast.data {
+        syn::Data::Enum(ref e) => e,
+        _ => {
+            return syn::Error::new(ty.span(), "CanyonEnum only works with enums")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let mut repr = EnumRepr::Text;
+    let mut db_type_name = helpers::default_database_table_name_from_entity_name(&enum_name);
+    match helpers::canyon_enum_args(&ast.attrs, &["repr", "type_name"]) {
+        Ok(args) => {
+            for (name, value) in args {
+                if name == "type_name" {
+                    db_type_name = value.value();
+                    continue;
+                }
+                repr = match value.value().as_str() {
+                    "text" => EnumRepr::Text,
+                    "int" => EnumRepr::Int,
+                    "pg_enum" => EnumRepr::PgEnum,
+                    _ => {
+                        return syn::Error::new_spanned(
+                            value,
+                            "Valid representations are: `text`, `int` and `pg_enum`",
+                        )
+                        .into_compile_error()
+                        .into()
+                    }
+                };
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<NaiveDate>" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDate, &str>(#ident_name)
+        }
+        Err(err) => return err.into(),
+    }
+
+    // The variants with the value that represents them on the database
+    let mut variants = Vec::new();
+    let mut next_discriminant = 0i32;
+    for variant in data_enum.variants.iter() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return syn::Error::new_spanned(
+                variant,
+                "CanyonEnum only works with enums whose variants doesn't hold any data",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        let value = if repr == EnumRepr::Int {
+            if let Some((_, discriminant)) = &variant.discriminant {
+                next_discriminant = match helpers::parse_discriminant(discriminant) {
+                    Ok(discriminant) => discriminant,
+                    Err(err) => return err.into(),
+                };
             }
-        } else if get_field_type_as_string(ty) == "NaiveTime" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveTime, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+            next_discriminant += 1;
+            (next_discriminant - 1).to_string()
+        } else {
+            match helpers::canyon_enum_args(&variant.attrs, &["rename"]) {
+                Ok(args) => args
+                    .last()
+                    .map(|(_, label)| label.value())
+                    .unwrap_or_else(|| {
+                        helpers::default_database_table_name_from_entity_name(
+                            &variant.ident.to_string(),
+                        )
+                    }),
+                Err(err) => return err.into(),
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<NaiveTime>" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveTime, &str>(#ident_name)
+        };
+        variants.push((variant.ident.clone(), value));
+    }
+
+    let inner_type = if repr == EnumRepr::Int {
+        "i32"
+    } else {
+        "String"
+    };
+    let mut registered_enum = CanyonRegisterEnum {
+        enum_name: enum_name.clone(),
+        db_type_name,
+        repr,
+        variants: variants.iter().map(|(_, value)| value.clone()).collect(),
+        ..Default::default()
+    };
+    registered_enum.registry_key = register_types::registry_key(&registered_enum);
+    let registry_key = registered_enum.registry_key.clone();
+
+    CANYON_REGISTER_TYPES
+        .lock()
+        .expect("Error acquiring Mutex guard on Canyon Enum macro")
+        .push(CanyonRegisterType {
+            type_name: enum_name.clone(),
+            inner_type: inner_type.to_owned(),
+            sql_type: None,
+            registry_key: registry_key.clone(),
+        });
+    CANYON_REGISTER_ENUMS
+        .lock()
+        .expect("Error acquiring Mutex guard on Canyon Enum macro")
+        .push(registered_enum);
+
+    let variant_idents = variants.iter().map(|(ident, _)| ident);
+    let tokens = if repr == EnumRepr::Int {
+        let values = variants
+            .iter()
+            .map(|(_, value)| proc_macro2::Literal::i32_suffixed(value.parse().unwrap()))
+            .collect::<Vec<_>>();
+        let from_value = quote! {
+            |value: i32| match value {
+                #(#values => Ok(Self::#variant_idents),)*
+                other => Err(canyon_sql::crud::types::unknown_enum_variant(#enum_name, other)),
             }
-        } else if get_field_type_as_string(ty) == "NaiveDateTime" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDateTime, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+        };
+        let variant_idents = variants.iter().map(|(ident, _)| ident);
+
+        quote! {
+            fn encode(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+                match self {
+                    #(Self::#variant_idents => &#values,)*
+                }
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<NaiveDateTime>" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDateTime, &str>(#ident_name)
+
+            fn encode_null() -> &'static dyn canyon_sql::crud::bounds::QueryParameter<'static> {
+                &None::<i32>
             }
-        } else if get_field_type_as_string(ty) == "DateTime" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::DateTime, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+
+            #[cfg(feature="postgres")]
+            fn decode_postgres(
+                row: &canyon_sql::db_clients::tokio_postgres::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<i32> as canyon_sql::crud::FromColumn>::from_postgres_column(row, idx)?
+                    .map(#from_value)
+                    .transpose()
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<DateTime>" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::DateTime, &str>(#ident_name)
+            #[cfg(feature="mssql")]
+            fn decode_sqlserver(
+                row: &canyon_sql::db_clients::tiberius::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<i32> as canyon_sql::crud::FromColumn>::from_sqlserver_column(row, idx)?
+                    .map(#from_value)
+                    .transpose()
             }
-        } else {
-            quote! {
-                #ident: row.get::<#ty, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+            #[cfg(feature="mysql")]
+            fn decode_mysql(
+                row: &canyon_sql::db_clients::mysql_async::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<i32> as canyon_sql::crud::FromColumn>::from_mysql_column(row, idx)?
+                    .map(#from_value)
+                    .transpose()
             }
         }
-    });
+    } else {
+        let labels = variants.iter().map(|(_, label)| label).collect::<Vec<_>>();
+        let from_label = quote! {
+            |label: String| match label.as_str() {
+                #(#labels => Ok(Self::#variant_idents),)*
+                other => Err(canyon_sql::crud::types::unknown_enum_variant(#enum_name, other)),
+            }
+        };
+        let variant_idents = variants.iter().map(|(ident, _)| ident);
 
-    let init_field_values_mysql = fields.iter().map(|(_vis, ident, _ty)| {
-        let ident_name = ident.to_string();
         quote! {
-            #ident: row.get(#ident_name)
-                .expect(format!("Failed to retrieve the {} field", #ident_name).as_ref())
-        }
-    });
+            fn encode(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+                match self {
+                    #(Self::#variant_idents => &canyon_sql::crud::types::EnumLabel(#labels),)*
+                }
+            }
 
-    // The type of the Struct
-    let ty = ast.ident;
+            fn encode_null() -> &'static dyn canyon_sql::crud::bounds::QueryParameter<'static> {
+                &None::<canyon_sql::crud::types::EnumLabel>
+            }
 
-    let tokens = quote! {
-        impl canyon_sql::crud::RowMapper<Self> for #ty {
             #[cfg(feature="postgres")]
-            fn deserialize_postgresql(row: &canyon_sql::db_clients::tokio_postgres::Row) -> #ty {
-                Self {
-                    #(#init_field_values),*
-                }
+            fn decode_postgres(
+                row: &canyon_sql::db_clients::tokio_postgres::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                canyon_sql::crud::types::decode_postgres_enum_label(row, idx)?
+                    .map(#from_label)
+                    .transpose()
             }
             #[cfg(feature="mssql")]
-            fn deserialize_sqlserver(row: &canyon_sql::db_clients::tiberius::Row) -> #ty {
-                Self {
-                    #(#init_field_values_sqlserver),*
-                }
+            fn decode_sqlserver(
+                row: &canyon_sql::db_clients::tiberius::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<String> as canyon_sql::crud::FromColumn>::from_sqlserver_column(row, idx)?
+                    .map(#from_label)
+                    .transpose()
             }
             #[cfg(feature="mysql")]
-            fn deserialize_mysql(row: &canyon_sql::db_clients::mysql_async::Row) -> #ty {
-                Self {
-                    #(#init_field_values_mysql),*
-                }
+            fn decode_mysql(
+                row: &canyon_sql::db_clients::mysql_async::Row,
+                idx: usize,
+            ) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync>> {
+                <Option<String> as canyon_sql::crud::FromColumn>::from_mysql_column(row, idx)?
+                    .map(#from_label)
+                    .transpose()
             }
         }
     };
 
-    tokens.into()
-}
+    quote! {
+        impl canyon_sql::crud::CanyonType for #ty {
+            #tokens
 
-/// Helper for generate the fields data for the Custom Derives Macros
-fn filter_fields(fields: &Fields) -> Vec<(Visibility, Ident)> {
-    fields
-        .iter()
-        .map(|field| (field.vis.clone(), field.ident.as_ref().unwrap().clone()))
-        .collect::<Vec<_>>()
+            const REGISTRY_KEY: &'static str = #registry_key;
+        }
+    }
+    .into()
 }
 
-fn fields_with_types(fields: &Fields) -> Vec<(Visibility, Ident, Type)> {
-    fields
+/// Implements the `CanyonEmbeddable` trait for a struct with named fields, like an
+/// address or an amount of money, so it can be held on a field of the entities annotated
+/// with `#[embedded(prefix = "...")]`, being every field of it mapped against a column
+/// named with the prefix followed by the name of the field.
+///
+/// The struct must be declared before the types that embeds it, with a name that isn't
+/// shared with another value object
+#[proc_macro_derive(CanyonEmbeddable)]
+pub fn implement_canyon_embeddable_for_type(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let ty = &ast.ident;
+
+    let fields = match ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: Fields::Named(ref fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return syn::Error::new(
+                ty.span(),
+                "CanyonEmbeddable only works with structs that have named fields",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let embedded_fields = fields
         .iter()
         .map(|field| {
             (
-                field.vis.clone(),
-                field.ident.as_ref().unwrap().clone(),
-                field.ty.clone(),
+                field.ident.as_ref().unwrap(),
+                get_field_type_as_string(&field.ty).replace(' ', ""),
             )
         })
-        .collect::<Vec<_>>()
+        .collect::<Vec<_>>();
+
+    // The user defined types of the fields are resolved now, being checked by the
+    // compiler that they're the ones found by their names
+    let mut registered_fields = Vec::new();
+    let mut registry_key_assertions = Vec::new();
+    for (field, (ident, field_type)) in fields.iter().zip(&embedded_fields) {
+        let registered_field = match CanyonRegisterEntityField::new(
+            ident.to_string(),
+            field_type.to_owned(),
+            Vec::new(),
+        ) {
+            Ok(registered_field) => registered_field,
+            Err(err) => {
+                return syn::Error::new_spanned(&field.ty, err)
+                    .into_compile_error()
+                    .into()
+            }
+        };
+        if let Some(user_type) = &registered_field.user_type {
+            registry_key_assertions.push(helpers::registry_key_assertion(
+                &field.ty,
+                quote! { canyon_sql::crud::CanyonType },
+                &user_type.registry_key,
+            ));
+        }
+        registered_fields.push(registered_field);
+    }
+    let mut registered_embeddable = CanyonRegisterEmbeddable {
+        embeddable_name: ty.to_string(),
+        fields: registered_fields,
+        ..Default::default()
+    };
+    registered_embeddable.registry_key = register_types::registry_key(&registered_embeddable);
+    let registry_key = registered_embeddable.registry_key.clone();
+
+    CANYON_REGISTER_EMBEDDABLES
+        .lock()
+        .expect("Error acquiring Mutex guard on Canyon Embeddable macro")
+        .push(registered_embeddable);
+
+    let column_names = embedded_fields
+        .iter()
+        .map(|(ident, _)| ident.to_string())
+        .collect::<Vec<_>>();
+    let get_column_arms = embedded_fields.iter().map(|(ident, _)| {
+        let column = ident.to_string();
+        quote! {
+            #column => Some(&self.#ident as &dyn canyon_sql::crud::bounds::QueryParameter<'_>)
+        }
+    });
+
+    // The fields are mapped as the ones of the entities, but from the prefixed columns
+    let init_field_values = |try_get_column: TokenStream| {
+        embedded_fields.iter().map(move |(ident, expected)| {
+            let column = ident.to_string();
+            quote! {
+                #ident: #try_get_column(row, entity, &format!("{}{}", prefix, #column), #expected)?
+            }
+        })
+    };
+    let init_field_values_postgres =
+        init_field_values(quote! { canyon_sql::crud::try_get_postgres_column });
+    let init_field_values_sqlserver =
+        init_field_values(quote! { canyon_sql::crud::try_get_sqlserver_column });
+    let init_field_values_mysql =
+        init_field_values(quote! { canyon_sql::crud::try_get_mysql_column });
+
+    quote! {
+        impl canyon_sql::crud::CanyonEmbeddable for #ty {
+            fn columns() -> &'static [&'static str] {
+                &[#(#column_names),*]
+            }
+
+            fn get_column(&self, column: &str) -> Option<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> {
+                match column {
+                    #(#get_column_arms,)*
+                    _ => None
+                }
+            }
+
+            const REGISTRY_KEY: &'static str = #registry_key;
+
+            #[cfg(feature="postgres")]
+            fn try_from_postgres_row(
+                row: &canyon_sql::db_clients::tokio_postgres::Row,
+                entity: &'static str,
+                prefix: &str,
+            ) -> Result<Self, canyon_sql::crud::errors::RowMapperError> {
+                Ok(Self {
+                    #(#init_field_values_postgres),*
+                })
+            }
+            #[cfg(feature="mssql")]
+            fn try_from_sqlserver_row(
+                row: &canyon_sql::db_clients::tiberius::Row,
+                entity: &'static str,
+                prefix: &str,
+            ) -> Result<Self, canyon_sql::crud::errors::RowMapperError> {
+                Ok(Self {
+                    #(#init_field_values_sqlserver),*
+                })
+            }
+            #[cfg(feature="mysql")]
+            fn try_from_mysql_row(
+                row: &canyon_sql::db_clients::mysql_async::Row,
+                entity: &'static str,
+                prefix: &str,
+            ) -> Result<Self, canyon_sql::crud::errors::RowMapperError> {
+                Ok(Self {
+                    #(#init_field_values_mysql),*
+                })
+            }
+        }
+
+        #(#registry_key_assertions)*
+    }
+    .into()
 }
 
 fn get_field_type_as_string(typ: &Type) -> String {
@@ -1,15 +1,46 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::utils::macro_tokens::MacroTokens;
+use crate::utils::{helpers::option_inner_type, macro_tokens::MacroTokens};
+
+use super::hooks::generate_hooked_transaction_tokens;
+
+/// Returns the tokens that writes the `stmt` with the SQL expression that obtains the
+/// current `UTC` time on the database of the `datasource_name` in place of its `{}`,
+/// of the type of the `#[soft_delete]` field, or [`None`] if the entity hasn't got one
+fn soft_delete_stmt(
+    macro_data: &MacroTokens,
+    stmt: impl FnOnce(&str) -> String,
+    datasource_name: &TokenStream,
+) -> Option<TokenStream> {
+    let soft_delete_column = macro_data.get_soft_delete_annotation()?;
+    let (_, ty) = macro_data
+        ._fields_with_types()
+        .into_iter()
+        .find(|(field, _)| *field == soft_delete_column)?;
+    let timestamp_ty = option_inner_type(&ty).unwrap_or(&ty);
+    let stmt = stmt(&soft_delete_column);
+
+    Some(quote! {
+        format!(
+            #stmt,
+            canyon_sql::crud::timestamps::database_now::<#timestamp_ty>(#datasource_name)
+        )
+    })
+}
 
 /// Generates the TokenStream for the __delete() CRUD operation
 /// returning a result, indicating a possible failure querying the database
+///
+/// If the entity has a `#[soft_delete]` field, the `delete` operation just sets
+/// the current timestamp on that column, while the `hard_delete` one
+/// removes the row from the database
 pub fn generate_delete_tokens(macro_data: &MacroTokens, table_schema_data: &String) -> TokenStream {
     let ty = macro_data.ty;
 
     let fields = macro_data.get_struct_fields();
     let pk = macro_data.get_primary_key_annotation();
+    let soft_delete = macro_data.get_soft_delete_annotation();
 
     if let Some(primary_key) = pk {
         let pk_field = fields
@@ -20,62 +51,214 @@ pub fn generate_delete_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
             );
         let pk_field_value =
             quote! { &self.#pk_field as &dyn canyon_sql::crud::bounds::QueryParameter<'_> };
+        // Executes the statement and the `after_delete` hook within the same transaction
+        let delete_transaction = |stmt: TokenStream, datasource_name: TokenStream| {
+            generate_hooked_transaction_tokens(
+                &datasource_name,
+                quote! {
+                    let affected_rows = transaction.execute(&#stmt, &[#pk_field_value]).await?;
 
-        quote! {
+                    canyon_sql::crud::CanyonHooks::after_delete(self).await?;
+                    Ok(affected_rows)
+                },
+            )
+        };
+        let hard_delete_stmt =
+            quote! { format!("DELETE FROM {} WHERE {:?} = $1", #table_schema_data, #primary_key) };
+        let (delete, delete_datasource) = (
+            delete_transaction(hard_delete_stmt.clone(), quote! { "" }),
+            delete_transaction(hard_delete_stmt, quote! { datasource_name }),
+        );
+
+        let hard_delete_tokens = quote! {
             /// Deletes from a database entity the row that matches
             /// the current instance of a T type, returning a result
-            /// indicating a possible failure querying the database.
-            async fn delete(&self) -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>> {
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    format!("DELETE FROM {} WHERE {:?} = $1", #table_schema_data, #primary_key),
-                    &[#pk_field_value],
-                    ""
-                ).await?;
-
-                Ok(())
+            /// with the number of affected rows, or a possible failure
+            /// querying the database.
+            ///
+            /// Unlike `delete`, it always removes the row, even when the
+            /// entity has a `#[soft_delete]` field.
+            async fn hard_delete(&self) -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                canyon_sql::crud::CanyonHooks::before_delete(self).await?;
+
+                #delete
             }
 
             /// Deletes from a database entity the row that matches
             /// the current instance of a T type, returning a result
-            /// indicating a possible failure querying the database with the specified datasource.
-            async fn delete_datasource<'a>(&self, datasource_name: &'a str)
-                -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            /// with the number of affected rows, or a possible failure
+            /// querying the database with the specified datasource.
+            ///
+            /// Unlike `delete_datasource`, it always removes the row, even when the
+            /// entity has a `#[soft_delete]` field.
+            async fn hard_delete_datasource<'a>(&self, datasource_name: &'a str)
+                -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
             {
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    format!("DELETE FROM {} WHERE {:?} = $1", #table_schema_data, #primary_key),
-                    &[#pk_field_value],
-                    datasource_name
-                ).await?;
+                canyon_sql::crud::CanyonHooks::before_delete(self).await?;
 
-                Ok(())
+                #delete_datasource
             }
+        };
+
+        if let Some(soft_delete_column) = soft_delete {
+            let delete_stmt = |datasource_name| {
+                soft_delete_stmt(
+                    macro_data,
+                    |column| {
+                        format!(
+                            "UPDATE {table_schema_data} SET {column} = {{}} \
+                            WHERE {primary_key} = $1 AND {column} IS NULL"
+                        )
+                    },
+                    &datasource_name,
+                )
+            };
+            let (delete_stmt, delete_stmt_datasource) = (
+                delete_stmt(quote! { "" }),
+                delete_stmt(quote! { datasource_name }),
+            );
+            let (soft_delete, soft_delete_datasource) = (
+                delete_transaction(quote! { #delete_stmt }, quote! { "" }),
+                delete_transaction(
+                    quote! { #delete_stmt_datasource },
+                    quote! { datasource_name },
+                ),
+            );
+            let restore_stmt = format!(
+                "UPDATE {table_schema_data} SET {soft_delete_column} = NULL WHERE {primary_key} = $1"
+            );
+
+            return quote! {
+                /// Soft deletes the row that matches the current instance of a T type,
+                /// setting the current timestamp on the column annotated with
+                /// `#[soft_delete]`, returning the number of affected rows
+                /// or a possible failure querying the database.
+                async fn delete(&self) -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                    canyon_sql::crud::CanyonHooks::before_delete(self).await?;
+
+                    #soft_delete
+                }
+
+                /// Soft deletes the row that matches the current instance of a T type,
+                /// setting the current timestamp on the column annotated with
+                /// `#[soft_delete]`, returning the number of affected rows
+                /// or a possible failure querying the database with the
+                /// specified datasource.
+                async fn delete_datasource<'a>(&self, datasource_name: &'a str)
+                    -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+                {
+                    canyon_sql::crud::CanyonHooks::before_delete(self).await?;
+
+                    #soft_delete_datasource
+                }
+
+                #hard_delete_tokens
+
+                /// Restores the soft deleted row that matches the current instance
+                /// of a T type, returning the number of affected rows or a possible
+                /// failure querying the database.
+                async fn restore(&self) -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                    <#ty as canyon_sql::crud::Transaction<#ty>>::execute(
+                        #restore_stmt,
+                        &[#pk_field_value],
+                        ""
+                    ).await
+                }
+
+                /// Restores the soft deleted row that matches the current instance
+                /// of a T type, returning the number of affected rows or a possible
+                /// failure querying the database with the specified datasource.
+                async fn restore_datasource<'a>(&self, datasource_name: &'a str)
+                    -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+                {
+                    <#ty as canyon_sql::crud::Transaction<#ty>>::execute(
+                        #restore_stmt,
+                        &[#pk_field_value],
+                        datasource_name
+                    ).await
+                }
+            };
         }
-    } else {
-        // Delete operation over an instance isn't available without declaring a primary key.
-        // The delete querybuilder variant must be used for the case when there's no pk declared
+
         quote! {
-            async fn delete(&self)
-                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
-            {
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Unsupported,
-                    "You can't use the 'delete' method on a \
-                    CanyonEntity that does not have a #[primary_key] annotation. \
-                    If you need to perform an specific search, use the Querybuilder instead."
-                ).into_inner().unwrap())
+            /// Deletes from a database entity the row that matches
+            /// the current instance of a T type, returning a result
+            /// with the number of affected rows, or a possible failure
+            /// querying the database.
+            async fn delete(&self) -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                canyon_sql::crud::CanyonHooks::before_delete(self).await?;
+
+                #delete
             }
 
+            /// Deletes from a database entity the row that matches
+            /// the current instance of a T type, returning a result
+            /// with the number of affected rows, or a possible failure
+            /// querying the database with the specified datasource.
             async fn delete_datasource<'a>(&self, datasource_name: &'a str)
-                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+                -> Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            {
+                canyon_sql::crud::CanyonHooks::before_delete(self).await?;
+
+                #delete_datasource
+            }
+
+            #hard_delete_tokens
+
+            async fn restore(&self)
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                Err(
+                    "You can't use the 'restore' method on a \
+                    CanyonEntity that does not have a #[soft_delete] annotation."
+                        .into(),
+                )
+            }
+
+            async fn restore_datasource<'a>(&self, datasource_name: &'a str)
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Unsupported,
-                    "You can't use the 'delete_datasource' method on a \
-                    CanyonEntity that does not have a #[primary_key] annotation. \
-                    If you need to perform an specific search, use the Querybuilder instead."
-                ).into_inner().unwrap())
+                Err(
+                    "You can't use the 'restore_datasource' method on a \
+                    CanyonEntity that does not have a #[soft_delete] annotation."
+                        .into(),
+                )
             }
         }
+    } else {
+        // Delete operation over an instance isn't available without declaring a primary key.
+        // The delete querybuilder variant must be used for the case when there's no pk declared
+        let unsupported_operations = [
+            "delete",
+            "delete_datasource",
+            "hard_delete",
+            "hard_delete_datasource",
+            "restore",
+            "restore_datasource",
+        ]
+        .iter()
+        .map(|operation| {
+            let operation_ident =
+                proc_macro2::Ident::new(operation, proc_macro2::Span::call_site());
+            let signature = if operation.ends_with("_datasource") {
+                quote! { async fn #operation_ident<'a>(&self, datasource_name: &'a str) }
+            } else {
+                quote! { async fn #operation_ident(&self) }
+            };
+            let err_msg = format!(
+                "You can't use the '{operation}' method on a \
+                CanyonEntity that does not have a #[primary_key] annotation. \
+                If you need to perform an specific search, use the Querybuilder instead."
+            );
+
+            quote! {
+                #signature -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>> {
+                    Err(#err_msg.into())
+                }
+            }
+        });
+
+        quote! { #(#unsupported_operations)* }
     }
 }
 
@@ -115,3 +298,88 @@ pub fn generate_delete_query_tokens(
         }
     }
 }
+
+/// Generates the TokenStream for the `delete_by_pks` operation and its `_datasource` variant,
+/// that (soft) deletes the rows whose primary key is one of the given ones.
+///
+/// Returns a collection with the signature and the implementation of every operation,
+/// or an empty one if the entity does not have a primary key
+pub fn generate_delete_by_pks_tokens(
+    macro_data: &MacroTokens,
+    table_schema_data: &String,
+) -> Vec<(TokenStream, TokenStream)> {
+    let mut pk_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
+    let ty = macro_data.ty;
+
+    let pk = macro_data.get_primary_key_annotation().unwrap_or_default();
+    let Some((_, pk_ty)) = macro_data
+        ._fields_with_types()
+        .into_iter()
+        .find(|(field, _)| *field == pk)
+    else {
+        return pk_quotes;
+    };
+
+    for datasource in [false, true] {
+        let (datasource_param, datasource_name, suffix, datasource_doc) = if datasource {
+            (
+                quote! { , datasource_name: &'a str },
+                quote! { datasource_name },
+                "_datasource",
+                " with the specified datasource",
+            )
+        } else {
+            (quote! {}, quote! { "" }, "", "")
+        };
+
+        // The soft deleted rows are just marked, as the `delete` operation does
+        let delete_stmt = soft_delete_stmt(
+            macro_data,
+            |column| {
+                format!(
+                    "UPDATE {table_schema_data} SET {column} = {{}} \
+                    WHERE {column} IS NULL AND {pk}"
+                )
+            },
+            &datasource_name,
+        )
+        .unwrap_or_else(|| {
+            let stmt = format!("DELETE FROM {table_schema_data} WHERE {pk}");
+            quote! { #stmt }
+        });
+
+        let delete_by_pks = proc_macro2::Ident::new(
+            &format!("delete_by_pks{suffix}"),
+            proc_macro2::Span::call_site(),
+        );
+        let delete_by_pks_sign = quote! {
+            async fn #delete_by_pks<'a>(pks: &'a [#pk_ty] #datasource_param) ->
+                Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+        };
+        let delete_by_pks_doc = format!(
+            "Deletes the rows whose primary key is one of the `pks`{datasource_doc}, \
+            returning the number of affected rows.\n\n\
+            The `pks` are sent in as many statements as needed to not exceed the limit of \
+            parameters of the database, all of them executed in the same database transaction. \
+            As the entities aren't loaded, the lifecycle hooks aren't called, like on the \
+            `delete_query` operation"
+        );
+        pk_quotes.push((
+            quote! { #delete_by_pks_sign; },
+            quote! {
+                #[doc = #delete_by_pks_doc]
+                #delete_by_pks_sign {
+                    let pks = canyon_sql::crud::batch::unique_pks(pks);
+                    let stmts = canyon_sql::crud::batch::in_chunks(&#delete_stmt, &pks, #datasource_name);
+
+                    let affected_rows = <#ty as canyon_sql::crud::Transaction<#ty>>::execute_all(
+                        stmts, #datasource_name
+                    ).await?;
+                    Ok(affected_rows.into_iter().sum())
+                }
+            },
+        ));
+    }
+
+    pk_quotes
+}
@@ -0,0 +1,28 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generates the TokenStream that runs the `operation` within a database transaction
+/// opened over the connection of the `datasource_name`, available on the `operation`
+/// as the `transaction` variable.
+///
+/// The `operation` writes the instance and calls its `after_*` hook, evaluating to a
+/// result, so the transaction it's only committed if both of them succeed. Otherwise,
+/// it's rolled back and the error it's returned
+pub fn generate_hooked_transaction_tokens(
+    datasource_name: &TokenStream,
+    operation: TokenStream,
+) -> TokenStream {
+    quote! {
+        let mut guarded_cache = canyon_sql::crud::lock_connections().await;
+        let mut transaction = canyon_sql::crud::DatabaseTransaction::begin_datasource(
+            #datasource_name,
+            &mut guarded_cache
+        ).await?;
+
+        let result: Result<_, Box<dyn std::error::Error + Sync + std::marker::Send>> = async {
+            #operation
+        }.await;
+
+        transaction.finish(result).await
+    }
+}
@@ -1,34 +1,68 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::utils::macro_tokens::MacroTokens;
+use crate::utils::macro_tokens::{EntityColumn, MacroTokens};
+
+use super::hooks::generate_hooked_transaction_tokens;
+use super::timestamps::generate_timestamps_tokens;
+
+/// Returns the columns written by the insert of a single instance, along with their
+/// names and the placeholders of their values, both comma separated.
+///
+/// The values of the primary key and of the `#[db_default]` fields are generated
+/// by the database, so they're skipped
+fn insert_fields<'a>(macro_data: &'a MacroTokens) -> (Vec<EntityColumn<'a>>, String, String) {
+    let primary_key = macro_data.get_primary_key_annotation();
+    let db_defaults = macro_data.get_db_default_annotations();
+
+    let insert_fields = macro_data
+        .get_columns()
+        .into_iter()
+        .filter(|column| {
+            let field = column.field.to_string();
+            Some(&field) != primary_key.as_ref() && !db_defaults.contains(&field)
+        })
+        .collect::<Vec<_>>();
+    let insert_columns = insert_fields
+        .iter()
+        .map(|column| format!("\"{}\"", column.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = (1..=insert_fields.len())
+        .map(|n| format!("${n}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    (insert_fields, insert_columns, placeholders)
+}
 
 /// Generates the TokenStream for the _insert_result() CRUD operation
 pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &String) -> TokenStream {
     let ty = macro_data.ty;
 
-    // Retrieves the fields of the Struct as a collection of Strings, already parsed
-    // the condition of remove the primary key if it's present and it's autoincremental
-    let insert_columns = macro_data.get_column_names_pk_parsed().join(", ");
-
-    // Returns a String with the generic $x placeholder for the query parameters.
-    let placeholders = macro_data.placeholders_generator();
-
-    // Retrieves the fields of the Struct
-    let fields = macro_data.get_struct_fields();
+    let primary_key = macro_data.get_primary_key_annotation();
+    let (insert_fields, insert_columns, placeholders) = insert_fields(macro_data);
 
-    let insert_values = fields.iter().map(|ident| {
-        quote! { &self.#ident }
-    });
+    let insert_values = insert_fields
+        .iter()
+        .map(|column| column.value(&quote! { self }));
     let insert_values_cloned = insert_values.clone();
 
-    let primary_key = macro_data.get_primary_key_annotation();
-
-    let remove_pk_value_from_fn_entry = if let Some(pk_index) = macro_data.get_pk_index() {
-        quote! { values.remove(#pk_index) }
-    } else {
-        quote! {}
+    let (timestamps_now, timestamps_assign) = generate_timestamps_tokens(
+        macro_data,
+        true,
+        quote! { self },
+        quote! { datasource_name },
+    );
+
+    // The lifecycle hooks. The `after_insert` one it's the last expression of the operation,
+    // called within the transaction of the insert. The fields are validated once the
+    // hook may have changed them
+    let before_insert = quote! {
+        canyon_sql::crud::CanyonHooks::before_insert(self).await?;
+        canyon_sql::crud::validation::Validate::validate(self)?;
     };
+    let after_insert = quote! { canyon_sql::crud::CanyonHooks::after_insert(self).await };
 
     let pk_ident_type = macro_data
         ._fields_with_types()
@@ -39,8 +73,6 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
         let pk_type = &pk_data.1;
 
         quote! {
-            #remove_pk_value_from_fn_entry;
-
             let stmt = format!(
                 "INSERT INTO {} ({}) VALUES ({}) RETURNING {}",
                 #table_schema_data,
@@ -49,41 +81,34 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
                 #primary_key
             );
 
-            let rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                stmt,
-                values,
-                datasource_name
-            ).await?;
+            let rows = transaction.query::<#ty>(&stmt, &values).await?;
 
            match rows {
                 #[cfg(feature = "postgres")]
                 canyon_sql::crud::CanyonRows::Postgres(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for an insert")?
-                        .get::<&str, #pk_type>(#primary_key);
-                    Ok(())
+                    self.#pk_ident = <#pk_type as canyon_sql::crud::FromColumn>::from_postgres_column(
+                        v.get(0).ok_or("Failed getting the returned IDs for an insert")?,
+                        0
+                    )?;
                 },
                 #[cfg(feature = "mssql")]
                 canyon_sql::crud::CanyonRows::Tiberius(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for a multi insert")?
-                        .get::<#pk_type, &str>(#primary_key)
-                        .ok_or("SQL Server primary key type failed to be set as value")?;
-                    Ok(())
+                    self.#pk_ident = <#pk_type as canyon_sql::crud::FromColumn>::from_sqlserver_column(
+                        v.get(0).ok_or("Failed getting the returned IDs for a multi insert")?,
+                        0
+                    )?;
                 },
                 #[cfg(feature = "mysql")]
                 canyon_sql::crud::CanyonRows::MySQL(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for a multi insert")?
-                        .get::<#pk_type,usize>(0)
-                        .ok_or("MYSQL primary key type failed to be set as value")?;
-                    Ok(())
+                    self.#pk_ident = <#pk_type as canyon_sql::crud::FromColumn>::from_mysql_column(
+                        v.get(0).ok_or("Failed getting the returned IDs for a multi insert")?,
+                        0
+                    )?;
                 },
                 _ => panic!("Reached the panic match arm of insert for the DatabaseConnection type") // TODO remove when the generics will be refactored
             }
+
+            #after_insert
         }
     } else {
         quote! {
@@ -95,15 +120,26 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
                 #primary_key
             );
 
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                stmt,
-                values,
-                datasource_name
-            ).await?;
+            transaction.query::<#ty>(&stmt, &values).await?;
 
-            Ok(())
+            #after_insert
         }
     };
+    // The values borrows the instance, so they're taken within the transaction,
+    // before the primary key is assigned to it
+    let insert_transaction = |values: TokenStream| {
+        generate_hooked_transaction_tokens(
+            &quote! { datasource_name },
+            quote! {
+                let values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#values];
+                #insert_transaction
+            },
+        )
+    };
+    let (insert_transaction, insert_transaction_cloned) = (
+        insert_transaction(quote! { #(#insert_values),* }),
+        insert_transaction(quote! { #(#insert_values_cloned),* }),
+    );
 
     quote! {
         /// Inserts into a database entity the current data in `self`, generating a new
@@ -148,7 +184,9 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
             -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
         {
             let datasource_name = "";
-            let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#insert_values),*];
+            #before_insert
+            #timestamps_now
+            #timestamps_assign
             #insert_transaction
         }
 
@@ -193,10 +231,141 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
         async fn insert_datasource<'a>(&mut self, datasource_name: &'a str)
             -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
         {
-            let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#insert_values_cloned),*];
-            #insert_transaction
+            #before_insert
+            #timestamps_now
+            #timestamps_assign
+            #insert_transaction_cloned
+        }
+
+    }
+}
+
+/// Generates the TokenStream for the insert_returning() CRUD operation, that inserts
+/// the instance and then refreshes every field of it with the values of the inserted
+/// row, so the ones generated by the database (like the `#[db_default]` fields, or the
+/// columns filled by triggers) are available.
+///
+/// Postgres and SqlServer returns the whole row on the insert statement, while on MySQL
+/// it's selected by the primary key just after the insert
+pub fn generate_insert_returning_tokens(
+    macro_data: &MacroTokens,
+    table_schema_data: &String,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let primary_key = macro_data.get_primary_key_annotation();
+
+    let pk_type = macro_data
+        ._fields_with_types()
+        .into_iter()
+        .find(|(field, _)| Some(field.to_string()) == primary_key)
+        .map(|(_, pk_type)| pk_type);
+    let (Some(primary_key), Some(pk_type)) = (primary_key, pk_type) else {
+        return quote! {
+            async fn insert_returning<'a>(&mut self)
+                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                Err(
+                    "You can't use the 'insert_returning' method on a \
+                    CanyonEntity that does not have a #[primary_key] annotation."
+                        .into(),
+                )
+            }
+
+            async fn insert_returning_datasource<'a>(&mut self, datasource_name: &'a str)
+                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                Err(
+                    "You can't use the 'insert_returning_datasource' method on a \
+                    CanyonEntity that does not have a #[primary_key] annotation."
+                        .into(),
+                )
+            }
+        };
+    };
+
+    let (insert_fields, insert_columns, placeholders) = insert_fields(macro_data);
+    let insert_values = insert_fields
+        .iter()
+        .map(|column| column.value(&quote! { self }));
+    let insert_stmt = format!(
+        "INSERT INTO {table_schema_data} ({insert_columns}) VALUES ({placeholders}) RETURNING *"
+    );
+    let select_stmt = format!("SELECT * FROM {table_schema_data} WHERE {primary_key} = $1");
+
+    let (timestamps_now, timestamps_assign) = generate_timestamps_tokens(
+        macro_data,
+        true,
+        quote! { self },
+        quote! { datasource_name },
+    );
+
+    let insert_returning = generate_hooked_transaction_tokens(
+        &quote! { datasource_name },
+        quote! {
+            let values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> =
+                vec![#(#insert_values),*];
+            let rows = transaction.query::<#ty>(#insert_stmt, &values).await?;
+
+            // MySQL only returns the generated primary key
+            #[cfg(feature = "mysql")]
+            let rows = match rows {
+                canyon_sql::crud::CanyonRows::MySQL(v) => {
+                    let pk = <#pk_type as canyon_sql::crud::FromColumn>::from_mysql_column(
+                        v.first().ok_or("Failed getting the returned ID for an insert")?,
+                        0
+                    )?;
+
+                    transaction.query::<#ty>(
+                        #select_stmt,
+                        &[&pk as &dyn canyon_sql::crud::bounds::QueryParameter<'_>]
+                    ).await?
+                }
+                rows => rows,
+            };
+
+            *self = rows
+                .into_results::<#ty>()?
+                .into_iter()
+                .next()
+                .ok_or("Failed getting the inserted row")?;
+
+            canyon_sql::crud::CanyonHooks::after_insert(self).await
+        },
+    );
+    let insert_returning_transaction = quote! {
+        canyon_sql::crud::CanyonHooks::before_insert(self).await?;
+        canyon_sql::crud::validation::Validate::validate(self)?;
+        #timestamps_now
+        #timestamps_assign
+
+        #insert_returning
+    };
+
+    quote! {
+        /// Inserts into a database entity the current data in `self`, like the `insert`
+        /// operation, but refreshing every field of `self` with the values of the inserted
+        /// row, so the ones generated by the database (the primary key, the `#[db_default]`
+        /// fields or the columns filled by triggers) are available
+        async fn insert_returning<'a>(&mut self)
+            -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+        {
+            let datasource_name = "";
+            #insert_returning_transaction
         }
 
+        /// Inserts into a database entity the current data in `self`, like the `insert`
+        /// operation, but refreshing every field of `self` with the values of the inserted
+        /// row, so the ones generated by the database (the primary key, the `#[db_default]`
+        /// fields or the columns filled by triggers) are available.
+        ///
+        /// The query it's made against the database with the configured datasource
+        /// described in the configuration file, and selected with the [`&str`]
+        /// passed as parameter.
+        async fn insert_returning_datasource<'a>(&mut self, datasource_name: &'a str)
+            -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+        {
+            #insert_returning_transaction
+        }
     }
 }
 
@@ -212,14 +381,62 @@ pub fn generate_multiple_insert_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
 
-    // Retrieves the fields of the Struct as continuous String
-    let column_names = macro_data.get_struct_fields_as_strings();
+    // Retrieves the columns of the Struct, but the `#[db_default]` ones, whose
+    // values are generated by the database
+    let db_defaults = macro_data.get_db_default_annotations();
+    let columns = macro_data
+        .get_columns()
+        .into_iter()
+        .filter(|column| !db_defaults.contains(&column.field.to_string()))
+        .collect::<Vec<_>>();
+
+    // The columns of the Struct as continuous String
+    let column_names = columns
+        .iter()
+        .map(|column| column.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let macro_fields = columns
+        .iter()
+        .map(|column| column.value(&quote! { instance }));
+    let macro_fields_cloned = macro_fields.clone();
 
-    // Retrieves the fields of the Struct
-    let fields = macro_data.get_struct_fields();
+    let (timestamps_now, timestamps_assign) = generate_timestamps_tokens(
+        macro_data,
+        true,
+        quote! { instance },
+        quote! { datasource_name },
+    );
+    let fill_timestamps = if timestamps_assign.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #timestamps_now
+            for instance in instances.iter_mut() {
+                #timestamps_assign
+            }
+        }
+    };
 
-    let macro_fields = fields.iter().map(|field| quote! { &instance.#field });
-    let macro_fields_cloned = macro_fields.clone();
+    // The lifecycle hooks, called for every instance. The `after_insert` ones are
+    // called within the transaction of the insert
+    let before_insert = quote! {
+        for instance in instances.iter_mut() {
+            canyon_sql::crud::CanyonHooks::before_insert(&mut **instance).await?;
+        }
+        // The fields are validated once the hooks may have changed them
+        for (index, instance) in instances.iter().enumerate() {
+            canyon_sql::crud::validation::Validate::validate(&**instance)
+                .map_err(|errors| errors.at(index))?;
+        }
+    };
+    let after_insert = quote! {
+        for instance in instances.iter_mut() {
+            canyon_sql::crud::CanyonHooks::after_insert(&mut **instance).await?;
+        }
+        Ok(())
+    };
 
     let pk = macro_data.get_primary_key_annotation().unwrap_or_default();
 
@@ -294,46 +511,40 @@ pub fn generate_multiple_insert_tokens(
                 }
             }
 
-            let multi_insert_result = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                stmt,
-                v_arr,
-                datasource_name
-            ).await?;
+            let multi_insert_result = transaction.query::<#ty>(&stmt, &v_arr).await?;
 
             match multi_insert_result {
                 #[cfg(feature="postgres")]
                 canyon_sql::crud::CanyonRows::Postgres(mut v) => {
                     for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<&str, #pk_type>(#pk);
+                        instance.#pk_ident = <#pk_type as canyon_sql::crud::FromColumn>::from_postgres_column(
+                            v.get(idx).expect("Failed getting the returned IDs for a multi insert"),
+                            0
+                        )?;
                     }
 
-                    Ok(())
+                    #after_insert
                 },
                 #[cfg(feature="mssql")]
                 canyon_sql::crud::CanyonRows::Tiberius(mut v) => {
                     for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<#pk_type, &str>(#pk)
-                            .expect("SQL Server primary key type failed to be set as value");
+                        instance.#pk_ident = <#pk_type as canyon_sql::crud::FromColumn>::from_sqlserver_column(
+                            v.get(idx).expect("Failed getting the returned IDs for a multi insert"),
+                            0
+                        )?;
                     }
 
-                    Ok(())
+                    #after_insert
                 },
                 #[cfg(feature="mysql")]
                 canyon_sql::crud::CanyonRows::MySQL(mut v) => {
                     for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<#pk_type,usize>(0)
-                            .expect("MYSQL primary key type failed to be set as value");
+                        instance.#pk_ident = <#pk_type as canyon_sql::crud::FromColumn>::from_mysql_column(
+                            v.get(idx).expect("Failed getting the returned IDs for a multi insert"),
+                            0
+                        )?;
                     }
-                    Ok(())
+                    #after_insert
                 },
                 _ => panic!() // TODO remove when the generics will be refactored
             }
@@ -393,16 +604,41 @@ pub fn generate_multiple_insert_tokens(
                 }
             }
 
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                stmt,
-                v_arr,
-                datasource_name
-            ).await?;
+            transaction.query::<#ty>(&stmt, &v_arr).await?;
 
-            Ok(())
+            #after_insert
         }
     };
 
+    // The values borrows the instances, so they're taken within the transaction,
+    // before the primary keys are assigned to them
+    let multi_insert = |macro_fields: TokenStream| {
+        generate_hooked_transaction_tokens(
+            &quote! { datasource_name },
+            quote! {
+                let mut final_values: Vec<Vec<&dyn QueryParameter<'_>>> = Vec::new();
+                for instance in instances.iter() {
+                    let intermediate: &[&dyn QueryParameter<'_>] = &[#macro_fields];
+
+                    let mut longer_lived: Vec<&dyn QueryParameter<'_>> = Vec::new();
+                    for value in intermediate.into_iter() {
+                        longer_lived.push(*value)
+                    }
+
+                    final_values.push(longer_lived)
+                }
+
+                let mut mapped_fields: String = String::new();
+
+                #multi_insert_transaction
+            },
+        )
+    };
+    let (multi_insert, multi_insert_cloned) = (
+        multi_insert(quote! { #(#macro_fields),* }),
+        multi_insert(quote! { #(#macro_fields_cloned),* }),
+    );
+
     quote! {
         /// Inserts multiple instances of some type `T` into its related table.
         ///
@@ -442,22 +678,10 @@ pub fn generate_multiple_insert_tokens(
         ) {
             use canyon_sql::crud::bounds::QueryParameter;
             let datasource_name = "";
+            #before_insert
+            #fill_timestamps
 
-            let mut final_values: Vec<Vec<&dyn QueryParameter<'_>>> = Vec::new();
-            for instance in instances.iter() {
-                let intermediate: &[&dyn QueryParameter<'_>] = &[#(#macro_fields),*];
-
-                let mut longer_lived: Vec<&dyn QueryParameter<'_>> = Vec::new();
-                for value in intermediate.into_iter() {
-                    longer_lived.push(*value)
-                }
-
-                final_values.push(longer_lived)
-            }
-
-            let mut mapped_fields: String = String::new();
-
-            #multi_insert_transaction
+            #multi_insert
         }
 
         /// Inserts multiple instances of some type `T` into its related table with the specified
@@ -498,22 +722,10 @@ pub fn generate_multiple_insert_tokens(
             Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
         ) {
             use canyon_sql::crud::bounds::QueryParameter;
+            #before_insert
+            #fill_timestamps
 
-            let mut final_values: Vec<Vec<&dyn QueryParameter<'_>>> = Vec::new();
-            for instance in instances.iter() {
-                let intermediate: &[&dyn QueryParameter<'_>] = &[#(#macro_fields_cloned),*];
-
-                let mut longer_lived: Vec<&dyn QueryParameter<'_>> = Vec::new();
-                for value in intermediate.into_iter() {
-                    longer_lived.push(*value)
-                }
-
-                final_values.push(longer_lived)
-            }
-
-            let mut mapped_fields: String = String::new();
-
-            #multi_insert_transaction
+            #multi_insert_cloned
         }
     }
 }
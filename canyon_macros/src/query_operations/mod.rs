@@ -1,4 +1,7 @@
 pub mod delete;
+pub mod hooks;
 pub mod insert;
 pub mod select;
+pub mod timestamps;
 pub mod update;
+pub mod validate;
@@ -6,6 +6,36 @@ use quote::quote;
 use crate::utils::helpers::*;
 use crate::utils::macro_tokens::MacroTokens;
 
+/// Returns the SQL condition that excludes the soft deleted rows, preceded by
+/// the `clause` that joins it with the rest of the statement, or an empty
+/// [`String`] if the entity does not have a `#[soft_delete]` field
+fn soft_delete_filter(macro_data: &MacroTokens<'_>, clause: &str) -> String {
+    macro_data
+        .get_soft_delete_annotation()
+        .map(|column| format!("{clause}{column} IS NULL"))
+        .unwrap_or_default()
+}
+
+/// Returns the tokens that builds a `QueryBuilder` over the `stmt` with a single
+/// parameter, that only retrieves the first row by appending the clause that limits
+/// the number of returned rows on the dialect of the database of the `datasource_name`
+fn first_row_query(
+    ty: &syn::Ident,
+    stmt: &str,
+    query_parameter: &TokenStream,
+    datasource_name: &TokenStream,
+) -> TokenStream {
+    quote! {
+        {
+            let mut query = canyon_sql::query::Query::<#ty>::new(#stmt.to_owned());
+            query.params.push(#query_parameter);
+            let mut query_builder = canyon_sql::query::QueryBuilder::new(query, #datasource_name);
+            query_builder.limit(1);
+            query_builder
+        }
+    }
+}
+
 /// Generates the TokenStream for build the __find_all() CRUD
 /// associated function
 pub fn generate_find_all_unchecked_tokens(
@@ -13,7 +43,10 @@ pub fn generate_find_all_unchecked_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
-    let stmt = format!("SELECT * FROM {table_schema_data}");
+    let stmt = format!(
+        "SELECT * FROM {table_schema_data}{}",
+        soft_delete_filter(macro_data, " WHERE ")
+    );
 
     quote! {
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -21,13 +54,17 @@ pub fn generate_find_all_unchecked_tokens(
         /// database convention. P.ej. PostgreSQL prefers table names declared
         /// with snake_case identifiers.
         async fn find_all_unchecked<'a>() -> Vec<#ty> {
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+            let mut results = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                 #stmt,
                 &[],
                 ""
             ).await
             .unwrap()
             .into_results::<#ty>()
+            .unwrap();
+
+            canyon_sql::crud::hooks::after_load_all(&mut results).await.unwrap();
+            results
         }
 
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -39,13 +76,17 @@ pub fn generate_find_all_unchecked_tokens(
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         async fn find_all_unchecked_datasource<'a>(datasource_name: &'a str) -> Vec<#ty> {
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+            let mut results = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                 #stmt,
                 &[],
                 datasource_name
             ).await
             .unwrap()
             .into_results::<#ty>()
+            .unwrap();
+
+            canyon_sql::crud::hooks::after_load_all(&mut results).await.unwrap();
+            results
         }
     }
 }
@@ -57,7 +98,10 @@ pub fn generate_find_all_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
-    let stmt = format!("SELECT * FROM {table_schema_data}");
+    let stmt = format!(
+        "SELECT * FROM {table_schema_data}{}",
+        soft_delete_filter(macro_data, " WHERE ")
+    );
 
     quote! {
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -67,14 +111,15 @@ pub fn generate_find_all_tokens(
         async fn find_all<'a>() ->
             Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
         {
-            Ok(
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    #stmt,
-                    &[],
-                    ""
-                ).await?
-                .into_results::<#ty>()
-            )
+            let mut results = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                #stmt,
+                &[],
+                ""
+            ).await?
+            .into_results::<#ty>()?;
+
+            canyon_sql::crud::hooks::after_load_all(&mut results).await?;
+            Ok(results)
         }
 
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -92,14 +137,15 @@ pub fn generate_find_all_tokens(
         async fn find_all_datasource<'a>(datasource_name: &'a str) ->
             Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
         {
-            Ok(
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    #stmt,
-                    &[],
-                    datasource_name
-                ).await?
-                .into_results::<#ty>()
-            )
+            let mut results = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                #stmt,
+                &[],
+                datasource_name
+            ).await?
+            .into_results::<#ty>()?;
+
+            canyon_sql::crud::hooks::after_load_all(&mut results).await?;
+            Ok(results)
         }
     }
 }
@@ -111,6 +157,21 @@ pub fn generate_find_all_query_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
 
+    // The soft deleted rows are excluded by default from the results
+    let new_select_query_builder =
+        |datasource_name: TokenStream| match macro_data.get_soft_delete_annotation() {
+            Some(soft_delete_column) => quote! {
+                canyon_sql::query::SelectQueryBuilder::new_with_soft_delete(
+                    #table_schema_data, #soft_delete_column, #datasource_name
+                )
+            },
+            None => quote! {
+                canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, #datasource_name)
+            },
+        };
+    let select_query_builder = new_select_query_builder(quote! { "" });
+    let select_query_builder_datasource = new_select_query_builder(quote! { datasource_name });
+
     quote! {
         /// Generates a [`canyon_sql::query::SelectQueryBuilder`]
         /// that allows you to customize the query by adding parameters and constrains dynamically.
@@ -120,7 +181,7 @@ pub fn generate_find_all_query_tokens(
         /// unless concrete values are set on the available parameters of the
         /// `canyon_macro(table_name = "table_name", schema = "schema")`
         fn select_query<'a>() -> canyon_sql::query::SelectQueryBuilder<'a, #ty> {
-            canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, "")
+            #select_query_builder
         }
 
         /// Generates a [`canyon_sql::query::SelectQueryBuilder`]
@@ -135,7 +196,7 @@ pub fn generate_find_all_query_tokens(
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         fn select_query_datasource<'a>(datasource_name: &'a str) -> canyon_sql::query::SelectQueryBuilder<'a, #ty> {
-            canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, datasource_name)
+            #select_query_builder_datasource
         }
     }
 }
@@ -147,55 +208,32 @@ pub fn generate_count_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
-    let ty_str = &ty.to_string();
-    let stmt = format!("SELECT COUNT(*) FROM {table_schema_data}");
-
-    let result_handling = quote! {
-        #[cfg(feature="postgres")]
-        canyon_sql::crud::CanyonRows::Postgres(mut v) => Ok(
-                v.remove(0).get::<&str, i64>("count")
-            ),
-        #[cfg(feature="mssql")]
-        canyon_sql::crud::CanyonRows::Tiberius(mut v) =>
-                v.remove(0)
-                    .get::<i32, usize>(0)
-                    .map(|c| c as i64)
-                    .ok_or(format!("Failure in the COUNT query for MSSQL for: {}", #ty_str).into())
-                    .into(),
-        #[cfg(feature="mysql")]
-        canyon_sql::crud::CanyonRows::MySQL(mut v) => v.remove(0)
-                .get::<i64, usize>(0)
-                .ok_or(format!("Failure in the COUNT query for MYSQL for: {}", #ty_str).into()),
-            _ => panic!() // TODO remove when the generics will be refactored
-    };
+    let stmt = format!(
+        "SELECT COUNT(*) FROM {table_schema_data}{}",
+        soft_delete_filter(macro_data, " WHERE ")
+    );
 
     quote! {
         /// Performs a COUNT(*) query over some table, returning a [`Result`] rather than panicking,
         /// wrapping a possible success or error coming from the database
         async fn count() -> Result<i64, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
-            let count = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                 #stmt,
                 &[],
                 ""
-            ).await?;
-
-            match count {
-                #result_handling
-            }
+            ).await?
+            .into_scalar::<i64>()
         }
 
         /// Performs a COUNT(*) query over some table, returning a [`Result`] rather than panicking,
         /// wrapping a possible success or error coming from the database with the specified datasource
         async fn count_datasource<'a>(datasource_name: &'a str) -> Result<i64, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
-            let count = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                 #stmt,
                 &[],
                 datasource_name
-            ).await?;
-
-            match count {
-                #result_handling
-            }
+            ).await?
+            .into_scalar::<i64>()
         }
     }
 }
@@ -207,7 +245,10 @@ pub fn generate_find_by_pk_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
     let pk = macro_data.get_primary_key_annotation().unwrap_or_default();
-    let stmt = format!("SELECT * FROM {table_schema_data} WHERE {pk} = $1");
+    let stmt = format!(
+        "SELECT * FROM {table_schema_data} WHERE {pk} = $1{}",
+        soft_delete_filter(macro_data, " AND ")
+    );
 
     // Disabled if there's no `primary_key` annotation
     if pk.is_empty() {
@@ -216,12 +257,10 @@ pub fn generate_find_by_pk_tokens(
                 -> Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
             {
                 Err(
-                    std::io::Error::new(
-                        std::io::ErrorKind::Unsupported,
-                        "You can't use the 'find_by_pk' associated function on a \
-                        CanyonEntity that does not have a #[primary_key] annotation. \
-                        If you need to perform an specific search, use the Querybuilder instead."
-                    ).into_inner().unwrap()
+                    "You can't use the 'find_by_pk' associated function on a \
+                    CanyonEntity that does not have a #[primary_key] annotation. \
+                    If you need to perform an specific search, use the Querybuilder instead."
+                        .into(),
                 )
             }
 
@@ -230,12 +269,10 @@ pub fn generate_find_by_pk_tokens(
                 datasource_name: &'a str
             ) -> Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
                 Err(
-                    std::io::Error::new(
-                        std::io::ErrorKind::Unsupported,
-                        "You can't use the 'find_by_pk_datasource' associated function on a \
-                        CanyonEntity that does not have a #[primary_key] annotation. \
-                        If you need to perform an specific search, use the Querybuilder instead."
-                    ).into_inner().unwrap()
+                    "You can't use the 'find_by_pk_datasource' associated function on a \
+                    CanyonEntity that does not have a #[primary_key] annotation. \
+                    If you need to perform an specific search, use the Querybuilder instead."
+                        .into(),
                 )
             }
         };
@@ -244,9 +281,11 @@ pub fn generate_find_by_pk_tokens(
     let result_handling = quote! {
         match result {
             n if n.len() == 0 => Ok(None),
-            _ => Ok(
-                Some(result.into_results::<#ty>().remove(0))
-            )
+            _ => {
+                let mut instance = result.into_results::<#ty>()?.remove(0);
+                canyon_sql::crud::CanyonHooks::after_load(&mut instance).await?;
+                Ok(Some(instance))
+            }
         }
     };
 
@@ -315,9 +354,15 @@ pub fn generate_find_by_foreign_key_tokens(
     macro_data: &MacroTokens<'_>,
 ) -> Vec<(TokenStream, TokenStream)> {
     let mut fk_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
+    // The parent is retrieved through the `Relation` generated for the field,
+    // so its own `#[soft_delete]` column (if any) is taken in consideration
+    let relation_ty = proc_macro2::Ident::new(
+        &format!("{}Relation", macro_data.ty),
+        proc_macro2::Span::call_site(),
+    );
 
     for (field_ident, fk_annot) in macro_data.get_fk_annotations().iter() {
-        if let EntityFieldAnnotation::ForeignKey(table, column) = fk_annot {
+        if let EntityFieldAnnotation::ForeignKey(table, _) = fk_annot {
             let method_name = "search_".to_owned() + table;
 
             // TODO this is not a good implementation. We must try to capture the
@@ -341,34 +386,14 @@ pub fn generate_find_by_foreign_key_tokens(
                     Result<Option<#fk_ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
             };
 
-            let stmt = format!(
-                "SELECT * FROM {} WHERE {} = $1",
-                table,
-                format!("\"{column}\"").as_str(),
-            );
-            let result_handler = quote! {
-                match result {
-                    n if n.len() == 0 => Ok(None),
-                    _ => Ok(Some(
-                        result.into_results::<#fk_ty>().remove(0)
-                    ))
-                }
-            };
-
             fk_quotes.push((
                 quote! { #quoted_method_signature; },
                 quote! {
                     /// Searches the parent entity (if exists) for this type
                     #quoted_method_signature {
-                        let result = <#fk_ty as canyon_sql::crud::Transaction<#fk_ty>>::query(
-                            #stmt,
-                            &[&self.#field_ident as &dyn canyon_sql::crud::bounds::QueryParameter<'_>],
-                            ""
-                        ).await?;
-
-                        #result_handler
+                        #relation_ty::#field_ident.load(self, "").await
                     }
-                }
+                },
             ));
 
             fk_quotes.push((
@@ -376,15 +401,9 @@ pub fn generate_find_by_foreign_key_tokens(
                 quote! {
                     /// Searches the parent entity (if exists) for this type with the specified datasource
                     #quoted_datasource_method_signature {
-                        let result = <#fk_ty as canyon_sql::crud::Transaction<#fk_ty>>::query(
-                            #stmt,
-                            &[&self.#field_ident as &dyn canyon_sql::crud::bounds::QueryParameter<'_>],
-                            datasource_name
-                        ).await?;
-
-                        #result_handler
+                        #relation_ty::#field_ident.load(self, datasource_name).await
                     }
-                }
+                },
             ));
         }
     }
@@ -392,6 +411,184 @@ pub fn generate_find_by_foreign_key_tokens(
     fk_quotes
 }
 
+/// Generates a type named as the entity plus `Relation`, that holds the [`Relation`]
+/// of every field annotated with `#[foreign_key]` as an associated constant, so the
+/// referenced entities can be loaded along with the queried ones by the `with` method
+/// of the `SelectQueryBuilder`
+pub fn generate_relation_tokens(macro_data: &MacroTokens<'_>) -> TokenStream {
+    let ty = macro_data.ty;
+    let vis = macro_data.vis;
+    let relation_ty =
+        proc_macro2::Ident::new(&format!("{}Relation", ty), proc_macro2::Span::call_site());
+
+    let relations = macro_data
+        .get_fk_annotations()
+        .into_iter()
+        .filter_map(|(field_ident, fk_annot)| match fk_annot {
+            EntityFieldAnnotation::ForeignKey(table, column) => {
+                let field = macro_data
+                    .fields
+                    .iter()
+                    .find(|field| field.ident.as_ref() == Some(field_ident))?;
+                let fk_ty = database_table_name_to_struct_ident(&table);
+                let column_name = field_ident.to_string();
+                let doc = format!(
+                    "The relation with the `{fk_ty}` entity referenced by the `{field_ident}` field"
+                );
+
+                let (key_ty, foreign_key) = match option_inner_type(&field.ty) {
+                    Some(inner_ty) => (inner_ty, quote! { entity.#field_ident.clone() }),
+                    None => (&field.ty, quote! { Some(entity.#field_ident.clone()) }),
+                };
+
+                Some(quote! {
+                    #[doc = #doc]
+                    #vis const #field_ident: canyon_sql::crud::Relation<#ty, #fk_ty, #key_ty> =
+                        canyon_sql::crud::Relation::new(#column_name, #column, |entity| #foreign_key);
+                })
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        /// Auto-generated type that holds the relations declared with the `#[foreign_key]`
+        /// annotation on the entity, as associated constants named as their fields
+        #vis struct #relation_ty;
+
+        #[allow(non_upper_case_globals)]
+        impl #relation_ty {
+            #(#relations)*
+        }
+    }
+}
+
+/// Generates the `load_*`, `add_*`, `remove_*` and `set_*` methods of every field annotated
+/// with `#[has_many]` or `#[many_to_many]`, that loads the related entities into the field
+/// or modifies the ones related with the entity, reloading them into the field afterwards.
+///
+/// Returns the error to report if the entity doesn't have a primary key to be related with
+pub fn generate_relation_methods_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &str,
+) -> Result<Vec<(TokenStream, TokenStream)>, TokenStream> {
+    let mut relation_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
+    let relation_fields = macro_data.get_relation_fields();
+    let Some((field, _, _)) = relation_fields.first() else {
+        return Ok(relation_quotes);
+    };
+    let Some(pk) = macro_data.get_primary_key_annotation() else {
+        return Err(syn::Error::new_spanned(
+            field,
+            "The entities with `#[has_many]` or `#[many_to_many]` fields must have \
+            a `#[primary_key]` field",
+        )
+        .into_compile_error());
+    };
+    let pk_field = proc_macro2::Ident::new(&pk, proc_macro2::Span::call_site());
+
+    for (field, field_ty, annotation) in relation_fields {
+        let related_ty = vec_inner_type(field_ty).expect("The relation fields are always a Vec");
+        let relation = match join_table(table_schema_data, &annotation) {
+            Some(join_table) => {
+                let (through, column, related_column) = (
+                    join_table.table_name,
+                    join_table.column,
+                    join_table.related_column,
+                );
+                quote! { canyon_sql::crud::ManyToMany::new(#through, #column, #related_column) }
+            }
+            None => match &annotation {
+                EntityFieldAnnotation::HasMany(_, foreign_key) => {
+                    quote! { canyon_sql::crud::HasMany::new(#foreign_key) }
+                }
+                _ => continue,
+            },
+        };
+
+        for datasource in [false, true] {
+            let (datasource_param, datasource_name, suffix, datasource_doc) = if datasource {
+                (
+                    quote! { , datasource_name: &'a str },
+                    quote! { datasource_name },
+                    "_datasource",
+                    " with the specified datasource",
+                )
+            } else {
+                (quote! {}, quote! { "" }, "", "")
+            };
+            let method_ident = |operation: &str| {
+                proc_macro2::Ident::new(
+                    &format!("{operation}_{field}{suffix}"),
+                    proc_macro2::Span::call_site(),
+                )
+            };
+            let reload = quote! {
+                self.#field = #relation
+                    .load::<#related_ty, _>(&self.#pk_field, #datasource_name)
+                    .await?;
+            };
+
+            let load = method_ident("load");
+            let load_sign = quote! {
+                async fn #load<'a>(&mut self #datasource_param) ->
+                    Result<&[#related_ty], Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            };
+            let load_doc = format!(
+                "Loads into the `{field}` field the entities related with this one{datasource_doc}"
+            );
+            relation_quotes.push((
+                quote! { #load_sign; },
+                quote! {
+                    #[doc = #load_doc]
+                    #load_sign {
+                        #reload
+                        Ok(&self.#field)
+                    }
+                },
+            ));
+
+            for (operation, operation_doc) in [
+                ("add", "Relates the `related` entities with this one"),
+                (
+                    "remove",
+                    "Removes the relation of the `related` entities with this one",
+                ),
+                (
+                    "set",
+                    "Replaces the entities related with this one by the `related` ones",
+                ),
+            ] {
+                let method = method_ident(operation);
+                let operation = proc_macro2::Ident::new(operation, proc_macro2::Span::call_site());
+                let sign = quote! {
+                    async fn #method<'a>(&mut self, related: &'a [#related_ty] #datasource_param) ->
+                        Result<u64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+                };
+                let doc = format!(
+                    "{operation_doc}{datasource_doc}, reloading the `{field}` field afterwards. \
+                    Returns the number of affected rows"
+                );
+                relation_quotes.push((
+                    quote! { #sign; },
+                    quote! {
+                        #[doc = #doc]
+                        #sign {
+                            let affected_rows = #relation
+                                .#operation(&self.#pk_field, related, #datasource_name)
+                                .await?;
+                            #reload
+                            Ok(affected_rows)
+                        }
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(relation_quotes)
+}
+
 /// Generates the TokenStream for build the __search_by_foreign_key() CRUD
 /// associated function, but wrapped as a Result<T, Err>, representing
 /// a possible failure querying the database, a bad or missing FK annotation or a missed ForeignKeyable
@@ -426,6 +623,7 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
             };
 
             let f_ident = field_ident.to_string();
+            let soft_delete_filter = soft_delete_filter(macro_data, " AND ");
 
             rev_fk_quotes.push((
                 quote! { #quoted_method_signature; },
@@ -440,16 +638,20 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
                             ).as_str());
 
                         let stmt = format!(
-                            "SELECT * FROM {} WHERE {} = $1",
+                            "SELECT * FROM {} WHERE {} = $1{}",
                             #table_schema_data,
-                            format!("\"{}\"", #f_ident).as_str()
+                            format!("\"{}\"", #f_ident).as_str(),
+                            #soft_delete_filter
                         );
 
-                        Ok(<#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        let mut results = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                             stmt,
                             &[lookage_value],
                             ""
-                        ).await?.into_results::<#ty>())
+                        ).await?.into_results::<#ty>()?;
+
+                        canyon_sql::crud::hooks::after_load_all(&mut results).await?;
+                        Ok(results)
                     }
                 },
             ));
@@ -468,16 +670,20 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
                             ).as_str());
 
                         let stmt = format!(
-                            "SELECT * FROM {} WHERE {} = $1",
+                            "SELECT * FROM {} WHERE {} = $1{}",
                             #table_schema_data,
-                            format!("\"{}\"", #f_ident).as_str()
+                            format!("\"{}\"", #f_ident).as_str(),
+                            #soft_delete_filter
                         );
 
-                        Ok(<#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        let mut results = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                             stmt,
                             &[lookage_value],
                             datasource_name
-                        ).await?.into_results::<#ty>())
+                        ).await?.into_results::<#ty>()?;
+
+                        canyon_sql::crud::hooks::after_load_all(&mut results).await?;
+                        Ok(results)
                     }
                 },
             ));
@@ -486,3 +692,324 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
 
     rev_fk_quotes
 }
+
+/// Returns the type of the value that the finders of a field receives, that's the
+/// type of the field without the [`Option`] wrapper, a [`str`] for the [`String`]s and
+/// a slice for the bytes, along with the tokens that converts the value into a query parameter
+fn finder_value_type(ty: &syn::Type) -> (TokenStream, TokenStream) {
+    let ty = option_inner_type(ty).unwrap_or(ty);
+
+    match quote! { #ty }.to_string().replace(' ', "").as_str() {
+        "String" => (
+            quote! { str },
+            quote! { &value as &dyn canyon_sql::crud::bounds::QueryParameter<'_> },
+        ),
+        "Vec<u8>" => (
+            quote! { [u8] },
+            quote! { &value as &dyn canyon_sql::crud::bounds::QueryParameter<'_> },
+        ),
+        _ => (
+            quote! { #ty },
+            quote! { value as &dyn canyon_sql::crud::bounds::QueryParameter<'_> },
+        ),
+    }
+}
+
+/// Generates the TokenStream for the finders of every field that isn't the primary key,
+/// that are the `find_by_<field>`, `find_one_by_<field>`, `exists_by_<field>` and
+/// `count_by_<field>` operations, along with their `_datasource` variants.
+///
+/// The `find_by_<field>` of a field annotated with `#[unique]` returns an [`Option`]
+/// instead of a [`Vec`], so there's no `find_one_by_<field>` for it.
+///
+/// Returns a collection with the signature and the implementation of every operation
+pub fn generate_find_by_field_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &String,
+) -> Vec<(TokenStream, TokenStream)> {
+    let mut finder_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
+    let ty = macro_data.ty;
+    let pk = macro_data.get_primary_key_annotation();
+    let soft_delete = macro_data.get_soft_delete_annotation();
+    let unique_fields = macro_data.get_unique_annotations();
+    let embedded_fields = macro_data.get_embedded_fields();
+    let relation_fields = macro_data
+        .get_relation_fields()
+        .into_iter()
+        .map(|(field, _, _)| field.clone())
+        .collect::<Vec<_>>();
+
+    for (field, field_ty) in macro_data._fields_with_types() {
+        let field_name = field.to_string();
+        // The primary key already has its own finder, and the soft deleted rows
+        // are always excluded, so there's nothing to find by the soft delete column.
+        // The `#[embedded]` value objects spans several columns, so they can't be
+        // compared against a single value, and the related entities aren't columns
+        if Some(&field_name) == pk.as_ref()
+            || Some(&field_name) == soft_delete.as_ref()
+            || embedded_fields.contains(&field)
+            || relation_fields.contains(&field)
+        {
+            continue;
+        }
+
+        let (value_ty, query_parameter) = finder_value_type(&field_ty);
+        let filter = format!(
+            "WHERE \"{field_name}\" = $1{}",
+            soft_delete_filter(macro_data, " AND ")
+        );
+        let select_stmt = format!("SELECT * FROM {table_schema_data} {filter}");
+        let exists_stmt = format!("SELECT 1 FROM {table_schema_data} {filter}");
+        let count_stmt = format!("SELECT COUNT(*) FROM {table_schema_data} {filter}");
+        let is_unique = unique_fields.contains(&field_name);
+
+        for datasource in [false, true] {
+            let (datasource_param, datasource_name, suffix, datasource_doc) = if datasource {
+                (
+                    quote! { , datasource_name: &'a str },
+                    quote! { datasource_name },
+                    "_datasource",
+                    " with the specified datasource",
+                )
+            } else {
+                (quote! {}, quote! { "" }, "", "")
+            };
+            let method_ident = |prefix: &str| {
+                proc_macro2::Ident::new(
+                    &format!("{prefix}_{field_name}{suffix}"),
+                    proc_macro2::Span::call_site(),
+                )
+            };
+            let query = quote! {
+                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    #select_stmt,
+                    &[#query_parameter],
+                    #datasource_name
+                ).await?
+            };
+
+            let find_by = method_ident("find_by");
+            let find_one_by = method_ident("find_one_by");
+            let find_by_sign = quote! {
+                async fn #find_by<'a>(value: &'a #value_ty #datasource_param) ->
+                    Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            };
+            let find_one_by_sign = quote! {
+                async fn #find_one_by<'a>(value: &'a #value_ty #datasource_param) ->
+                    Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            };
+            let find_one_query =
+                first_row_query(ty, &select_stmt, &query_parameter, &datasource_name);
+            let find_one_by_impl = quote! {
+                let mut query_builder = #find_one_query;
+                let Some(mut instance) = query_builder.query().await?.pop() else {
+                    return Ok(None);
+                };
+                canyon_sql::crud::CanyonHooks::after_load(&mut instance).await?;
+                Ok(Some(instance))
+            };
+
+            if is_unique {
+                let doc = format!(
+                    "Finds the entity whose `{field_name}` column, declared as unique, \
+                    matches the `value`{datasource_doc}"
+                );
+                let find_by_unique_sign = quote! {
+                    async fn #find_by<'a>(value: &'a #value_ty #datasource_param) ->
+                        Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+                };
+                finder_quotes.push((
+                    quote! { #find_by_unique_sign; },
+                    quote! {
+                        #[doc = #doc]
+                        #find_by_unique_sign {
+                            #find_one_by_impl
+                        }
+                    },
+                ));
+            } else {
+                let find_by_doc = format!(
+                    "Finds the entities whose `{field_name}` column matches the `value`{datasource_doc}"
+                );
+                let find_one_by_doc = format!(
+                    "Finds the first entity whose `{field_name}` column matches the `value`{datasource_doc}"
+                );
+                finder_quotes.push((
+                    quote! { #find_by_sign; },
+                    quote! {
+                        #[doc = #find_by_doc]
+                        #find_by_sign {
+                            let mut results = #query.into_results::<#ty>()?;
+                            canyon_sql::crud::hooks::after_load_all(&mut results).await?;
+                            Ok(results)
+                        }
+                    },
+                ));
+                finder_quotes.push((
+                    quote! { #find_one_by_sign; },
+                    quote! {
+                        #[doc = #find_one_by_doc]
+                        #find_one_by_sign {
+                            #find_one_by_impl
+                        }
+                    },
+                ));
+            }
+
+            let count_by = method_ident("count_by");
+            let exists_by = method_ident("exists_by");
+            let count_by_sign = quote! {
+                async fn #count_by<'a>(value: &'a #value_ty #datasource_param) ->
+                    Result<i64, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            };
+            let exists_by_sign = quote! {
+                async fn #exists_by<'a>(value: &'a #value_ty #datasource_param) ->
+                    Result<bool, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            };
+            let count_by_doc = format!(
+                "Counts the entities whose `{field_name}` column matches the `value`{datasource_doc}"
+            );
+            let exists_by_doc = format!(
+                "Returns true if some entity has a `{field_name}` column that matches the `value`{datasource_doc}"
+            );
+            let exists_query =
+                first_row_query(ty, &exists_stmt, &query_parameter, &datasource_name);
+            finder_quotes.push((
+                quote! { #count_by_sign; },
+                quote! {
+                    #[doc = #count_by_doc]
+                    #count_by_sign {
+                        <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                            #count_stmt,
+                            &[#query_parameter],
+                            #datasource_name
+                        ).await?
+                        .into_scalar::<i64>()
+                    }
+                },
+            ));
+            finder_quotes.push((
+                quote! { #exists_by_sign; },
+                quote! {
+                    #[doc = #exists_by_doc]
+                    #exists_by_sign {
+                        let mut query_builder = #exists_query;
+                        Ok(!query_builder.query_as::<(i32,)>().await?.is_empty())
+                    }
+                },
+            ));
+        }
+    }
+
+    finder_quotes
+}
+
+/// Generates the TokenStream for the operations that works over the primary key, that are
+/// the `find_by_pks` and the `exists` operations, along with their `_datasource` variants.
+///
+/// Returns a collection with the signature and the implementation of every operation,
+/// or an empty one if the entity does not have a primary key
+pub fn generate_find_by_pks_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &String,
+) -> Vec<(TokenStream, TokenStream)> {
+    let mut pk_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
+    let ty = macro_data.ty;
+    let field_enum = proc_macro2::Ident::new(&format!("{ty}Field"), proc_macro2::Span::call_site());
+
+    let pk = macro_data.get_primary_key_annotation().unwrap_or_default();
+    let Some((pk_field, pk_ty)) = macro_data
+        ._fields_with_types()
+        .into_iter()
+        .find(|(field, _)| *field == pk)
+    else {
+        return pk_quotes;
+    };
+    let exists_stmt = format!(
+        "SELECT 1 FROM {table_schema_data} WHERE {pk} = $1{}",
+        soft_delete_filter(macro_data, " AND ")
+    );
+
+    for datasource in [false, true] {
+        let (datasource_param, datasource_name, suffix, datasource_doc) = if datasource {
+            (
+                quote! { , datasource_name: &'a str },
+                quote! { datasource_name },
+                "_datasource",
+                " with the specified datasource",
+            )
+        } else {
+            (quote! {}, quote! { "" }, "", "")
+        };
+        let method_ident = |name: &str| {
+            proc_macro2::Ident::new(&format!("{name}{suffix}"), proc_macro2::Span::call_site())
+        };
+
+        let find_by_pks = method_ident("find_by_pks");
+        let find_by_pks_sign = quote! {
+            async fn #find_by_pks<'a>(pks: &'a [#pk_ty] #datasource_param) -> Result<
+                canyon_sql::crud::FoundByPks<#ty, #pk_ty>,
+                Box<(dyn std::error::Error + Send + Sync + 'static)>
+            >
+        };
+        let find_by_pks_doc = format!(
+            "Finds the entities whose primary key is one of the `pks`{datasource_doc}.\n\n\
+            The entities are returned in the same order that the `pks`, along with the \
+            ones that weren't found. The repeated primary keys are only searched once, and \
+            the `pks` are sent in as many statements as needed to not exceed the limit of \
+            parameters of the database"
+        );
+        pk_quotes.push((
+            quote! { #find_by_pks_sign; },
+            quote! {
+                #[doc = #find_by_pks_doc]
+                #find_by_pks_sign {
+                    let pks = canyon_sql::crud::batch::unique_pks(pks);
+                    let chunk_size = canyon_sql::crud::batch::max_params_per_statement(#datasource_name);
+
+                    let mut loaded = Vec::with_capacity(pks.len());
+                    for chunk in pks.chunks(chunk_size) {
+                        let mut select_query =
+                            <#ty as canyon_sql::crud::CrudOperations<#ty>>::select_query_datasource(
+                                #datasource_name
+                            );
+                        canyon_sql::query::ops::QueryBuilder::and_values_in(
+                            &mut select_query, #field_enum::#pk_field, chunk
+                        );
+                        loaded.extend(select_query.query().await?);
+                    }
+
+                    Ok(canyon_sql::crud::batch::sort_by_pks(
+                        &pks, loaded, |instance| instance.#pk_field.clone()
+                    ))
+                }
+            },
+        ));
+
+        let exists = method_ident("exists");
+        let exists_sign = quote! {
+            async fn #exists<'a>(pk: &'a #pk_ty #datasource_param) ->
+                Result<bool, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+        };
+        let exists_doc =
+            format!("Returns true if there's an entity whose primary key is `pk`{datasource_doc}");
+        let exists_query = first_row_query(
+            ty,
+            &exists_stmt,
+            &quote! { pk as &dyn canyon_sql::crud::bounds::QueryParameter<'_> },
+            &datasource_name,
+        );
+        pk_quotes.push((
+            quote! { #exists_sign; },
+            quote! {
+                #[doc = #exists_doc]
+                #exists_sign {
+                    let mut query_builder = #exists_query;
+                    Ok(!query_builder.query_as::<(i32,)>().await?.is_empty())
+                }
+            },
+        ));
+    }
+
+    pk_quotes
+}
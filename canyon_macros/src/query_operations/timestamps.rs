@@ -0,0 +1,80 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+use canyon_entities::field_annotation::EntityFieldAnnotation;
+
+use crate::utils::macro_tokens::MacroTokens;
+
+/// Generates the TokenStreams that fills the fields annotated with `#[created_at]`
+/// and `#[updated_at]` with the current time, before writing them to the database.
+///
+/// Returns a tuple with the tokens that obtains the current time from the desired clock,
+/// and the tokens that assigns it to the fields of the `target` instance, so the
+/// time can be obtained just once for several instances.
+///
+/// * `on_insert` - If true, both kind of fields are filled. Otherwise, only the
+///   `#[updated_at]` ones
+/// * `target` - The instance whose fields will be filled
+/// * `datasource_name` - The datasource to ask for the time if the database clock is used
+pub fn generate_timestamps_tokens(
+    macro_data: &MacroTokens,
+    on_insert: bool,
+    target: TokenStream,
+    datasource_name: TokenStream,
+) -> (TokenStream, TokenStream) {
+    let (now_tokens, timestamps) =
+        generate_timestamps_values(macro_data, on_insert, datasource_name);
+    let assign_tokens = timestamps
+        .iter()
+        .map(|(field, now)| quote! { #target.#field = #now; });
+
+    (now_tokens, quote! { #(#assign_tokens)* })
+}
+
+/// Same as [`generate_timestamps_tokens`], but instead of the tokens that assigns
+/// the current time to the fields, returns the pairs of every filled field along
+/// with the variable that holds its value, for the operations that writes the
+/// timestamps without modifying the instance
+pub fn generate_timestamps_values<'a>(
+    macro_data: &'a MacroTokens,
+    on_insert: bool,
+    datasource_name: TokenStream,
+) -> (TokenStream, Vec<(&'a Ident, Ident)>) {
+    let mut now_tokens = Vec::new();
+    let mut timestamps = Vec::new();
+    // The fields with the same type and clock shares the same value
+    let mut already_obtained: Vec<(String, bool, Ident)> = Vec::new();
+
+    for (field, ty, annotation) in macro_data.get_timestamp_annotations() {
+        let database_clock = match annotation {
+            EntityFieldAnnotation::CreatedAt(database_clock) if on_insert => database_clock,
+            EntityFieldAnnotation::UpdatedAt(database_clock) => database_clock,
+            _ => continue,
+        };
+        let ty_str = quote! { #ty }.to_string();
+
+        let now = match already_obtained
+            .iter()
+            .find(|(t, clock, _)| *t == ty_str && *clock == database_clock)
+        {
+            Some((_, _, now)) => now.clone(),
+            None => {
+                let now = format_ident!("{}_now", field);
+                let clock = if database_clock {
+                    quote! { canyon_sql::crud::timestamps::Clock::Database }
+                } else {
+                    quote! { canyon_sql::crud::timestamps::Clock::App }
+                };
+                now_tokens.push(quote! {
+                    let #now: #ty = canyon_sql::crud::timestamps::now(#clock, #datasource_name).await?;
+                });
+                already_obtained.push((ty_str, database_clock, now.clone()));
+                now
+            }
+        };
+
+        timestamps.push((field, now));
+    }
+
+    (quote! { #(#now_tokens)* }, timestamps)
+}
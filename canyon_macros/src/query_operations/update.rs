@@ -1,9 +1,20 @@
-use proc_macro2::TokenStream;
+use canyon_entities::field_annotation::EntityFieldAnnotation;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
 use crate::utils::macro_tokens::MacroTokens;
 
+use super::hooks::generate_hooked_transaction_tokens;
+use super::timestamps::{generate_timestamps_tokens, generate_timestamps_values};
+
 /// Generates the TokenStream for the __update() CRUD operation
+///
+/// If the entity has a `#[version]` field, the update only matches the row
+/// if it still holds the same version that the instance, returning a
+/// [`StaleObjectError`] otherwise. The `update` operation leaves the version
+/// of the instance stale, while the `update_refreshing` one increments it on
+/// success, along with refreshing the `#[updated_at]` fields, once the transaction
+/// of the update and its `after_update` hook is committed
 pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &String) -> TokenStream {
     let ty = macro_data.ty;
 
@@ -11,62 +22,166 @@ pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
 
     // Retrieves the fields of the Struct
     let fields = macro_data.get_struct_fields();
+    let version = macro_data.get_version_annotation();
 
     let mut vec_columns_values: Vec<String> = Vec::new();
+    let mut version_clause = String::new();
     for (i, column_name) in update_columns.iter().enumerate() {
-        let column_equal_value = format!("{} = ${}", column_name.to_owned(), i + 2);
+        let column_equal_value = if version
+            .as_ref()
+            .is_some_and(|v| *column_name == format!("\"{v}\""))
+        {
+            // The old version is only used to match the row, and it's incremented by the database
+            version_clause = format!(" AND {} = ${}", column_name, i + 2);
+            format!("{column_name} = {column_name} + 1")
+        } else {
+            format!("{} = ${}", column_name.to_owned(), i + 2)
+        };
         vec_columns_values.push(column_equal_value)
     }
 
     let str_columns_values = vec_columns_values.join(", ");
 
-    let update_values = fields.iter().map(|ident| {
-        quote! { &self.#ident }
-    });
-    let update_values_cloned = update_values.clone();
+    let columns = macro_data.get_columns();
+    let update_values = columns
+        .iter()
+        .map(|column| column.value(&quote! { self }))
+        .collect::<Vec<_>>();
 
     if let Some(primary_key) = macro_data.get_primary_key_annotation() {
         let pk_index = macro_data
             .get_pk_index()
             .expect("Update method failed to retrieve the index of the primary key");
+        let pk_field = fields
+            .iter()
+            .find(|f| *f.to_string() == primary_key)
+            .expect("Update method failed to retrieve the Ident of the primary key");
+        let version_field = version.as_ref().map(|version_column| {
+            fields
+                .iter()
+                .find(|f| f.to_string() == *version_column)
+                .expect("Update method failed to retrieve the Ident of the version field")
+        });
 
-        quote! {
-            /// Updates a database record that matches
-            /// the current instance of a T type, returning a result
-            /// indicating a possible failure querying the database.
-            async fn update(&self) -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> {
+        let stale_check = version_field.map(|version_field| {
+            quote! {
+                if affected_rows == 0 {
+                    return Err(canyon_sql::crud::errors::StaleObjectError::new(
+                        stringify!(#ty),
+                        format!("{:?}", self.#pk_field),
+                        self.#version_field.to_string(),
+                    ).into());
+                }
+            }
+        });
+        let version_increment = version_field.map(|version_field| {
+            quote! { self.#version_field += 1; }
+        });
+
+        // The `update` operation can't modify the instance, so the timestamps are
+        // written without assigning them, unlike on the `update_refreshing` one
+        let update_transaction = |datasource_name: TokenStream, refreshing: bool| {
+            let (timestamps_now, timestamps_values) =
+                generate_timestamps_values(macro_data, false, datasource_name.clone());
+            let (timestamps_assign, update_values) = if refreshing {
+                let assign = timestamps_values
+                    .iter()
+                    .map(|(field, now)| quote! { self.#field = #now; });
+                (quote! { #(#assign)* }, update_values.clone())
+            } else {
+                let values = columns
+                    .iter()
+                    .zip(&update_values)
+                    .map(|(column, value)| {
+                        match timestamps_values
+                            .iter()
+                            .find(|(field, _)| *field == column.field)
+                        {
+                            Some((_, now)) => quote! { &#now },
+                            None => value.clone(),
+                        }
+                    })
+                    .collect();
+                (quote! {}, values)
+            };
+            let version_increment = version_increment.as_ref().filter(|_| refreshing);
+            let update = generate_hooked_transaction_tokens(
+                &datasource_name,
+                quote! {
+                    let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values),*];
+
+                    let affected_rows = transaction.execute(&stmt, update_values).await?;
+                    #stale_check
+
+                    canyon_sql::crud::CanyonHooks::after_update(self).await?;
+                    Ok(affected_rows)
+                },
+            );
+            let update = match version_increment {
+                Some(version_increment) => quote! {
+                    let affected_rows = { #update }?;
+                    #version_increment
+                    Ok(affected_rows)
+                },
+                None => update,
+            };
+
+            quote! {
+                canyon_sql::crud::CanyonHooks::before_update(self).await?;
+                canyon_sql::crud::validation::Validate::validate(self)?;
+                #timestamps_now
+                #timestamps_assign
                 let stmt = format!(
-                    "UPDATE {} SET {} WHERE {} = ${:?}",
-                    #table_schema_data, #str_columns_values, #primary_key, #pk_index + 1
+                    "UPDATE {} SET {} WHERE {} = ${:?}{}",
+                    #table_schema_data, #str_columns_values, #primary_key, #pk_index + 1, #version_clause
                 );
-                let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values),*];
-
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    stmt, update_values, ""
-                ).await?;
+                #update
+            }
+        };
+        let update_default_ds = update_transaction(quote! { "" }, false);
+        let update_with_ds = update_transaction(quote! { datasource_name }, false);
+        let update_refreshing_default_ds = update_transaction(quote! { "" }, true);
+        let update_refreshing_with_ds = update_transaction(quote! { datasource_name }, true);
 
-                Ok(())
+        quote! {
+            /// Updates a database record that matches
+            /// the current instance of a T type, returning a result
+            /// with the number of affected rows, or a possible failure
+            /// querying the database.
+            ///
+            /// The instance isn't modified, so its `#[version]` field (if any) is left
+            /// stale, and a second `update` over it fails with a `StaleObjectError`.
+            /// Use `update_refreshing` to keep updating the same instance
+            async fn update(&self) -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>> {
+                #update_default_ds
             }
 
 
             /// Updates a database record that matches
             /// the current instance of a T type, returning a result
-            /// indicating a possible failure querying the database with the
-            /// specified datasource
+            /// with the number of affected rows, or a possible failure
+            /// querying the database with the specified datasource.
+            ///
+            /// The instance isn't modified, so its `#[version]` field (if any) is left
+            /// stale, like on the `update` operation
             async fn update_datasource<'a>(&self, datasource_name: &'a str)
-                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
-                let stmt = format!(
-                    "UPDATE {} SET {} WHERE {} = ${:?}",
-                    #table_schema_data, #str_columns_values, #primary_key, #pk_index + 1
-                );
-                let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values_cloned),*];
+                #update_with_ds
+            }
 
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    stmt, update_values, datasource_name
-                ).await?;
+            /// Same as `update`, but refreshing the `#[version]` and the
+            /// `#[updated_at]` fields of the instance with the values written
+            async fn update_refreshing(&mut self) -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>> {
+                #update_refreshing_default_ds
+            }
 
-                Ok(())
+            /// Same as `update_datasource`, but refreshing the `#[version]` and the
+            /// `#[updated_at]` fields of the instance with the values written
+            async fn update_refreshing_datasource<'a>(&mut self, datasource_name: &'a str)
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                #update_refreshing_with_ds
             }
         }
     } else {
@@ -74,31 +189,43 @@ pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
         // Use instead the update associated function of the querybuilder
 
         // TODO Returning an error should be a provisional way of doing this
+        let unsupported = |method: &str| {
+            format!(
+                "You can't use the '{method}' method on a \
+                CanyonEntity that does not have a #[primary_key] annotation. \
+                If you need to perform an specific search, use the Querybuilder instead."
+            )
+        };
+        let (update_err, update_ds_err, refreshing_err, refreshing_ds_err) = (
+            unsupported("update"),
+            unsupported("update_datasource"),
+            unsupported("update_refreshing"),
+            unsupported("update_refreshing_datasource"),
+        );
+
         quote! {
             async fn update(&self)
-                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
-                Err(
-                    std::io::Error::new(
-                        std::io::ErrorKind::Unsupported,
-                        "You can't use the 'update' method on a \
-                        CanyonEntity that does not have a #[primary_key] annotation. \
-                        If you need to perform an specific search, use the Querybuilder instead."
-                    ).into_inner().unwrap()
-                )
+                Err(#update_err.into())
             }
 
             async fn update_datasource<'a>(&self, datasource_name: &'a str)
-                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
-                Err(
-                    std::io::Error::new(
-                        std::io::ErrorKind::Unsupported,
-                        "You can't use the 'update_datasource' method on a \
-                        CanyonEntity that does not have a #[primary_key] annotation. \
-                        If you need to perform an specific search, use the Querybuilder instead."
-                    ).into_inner().unwrap()
-                )
+                Err(#update_ds_err.into())
+            }
+
+            async fn update_refreshing(&mut self)
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                Err(#refreshing_err.into())
+            }
+
+            async fn update_refreshing_datasource<'a>(&mut self, datasource_name: &'a str)
+                -> Result<u64, Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                Err(#refreshing_ds_err.into())
             }
         }
     }
@@ -112,6 +239,35 @@ pub fn generate_update_query_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
 
+    // The builder refreshes the `#[updated_at]` column (if present) on the `SET` clause
+    let updated_at = macro_data.get_timestamp_annotations().into_iter().find_map(
+        |(field, field_ty, annotation)| match annotation {
+            EntityFieldAnnotation::UpdatedAt(database_clock) => {
+                Some((field.to_string(), field_ty, database_clock))
+            }
+            _ => None,
+        },
+    );
+    let new_update_query_builder = |datasource_name: TokenStream| match &updated_at {
+        Some((column, field_ty, database_clock)) => {
+            let clock = if *database_clock {
+                quote! { canyon_sql::crud::timestamps::Clock::Database }
+            } else {
+                quote! { canyon_sql::crud::timestamps::Clock::App }
+            };
+            quote! {
+                canyon_sql::query::UpdateQueryBuilder::new_with_updated_at::<#field_ty>(
+                    #table_schema_data, #column, #clock, #datasource_name
+                )
+            }
+        }
+        None => quote! {
+            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, #datasource_name)
+        },
+    };
+    let update_query_builder = new_update_query_builder(quote! { "" });
+    let update_query_builder_with_ds = new_update_query_builder(quote! { datasource_name });
+
     quote! {
         /// Generates a [`canyon_sql::query::UpdateQueryBuilder`]
         /// that allows you to customize the query by adding parameters and constrains dynamically.
@@ -121,7 +277,7 @@ pub fn generate_update_query_tokens(
         /// unless concrete values are set on the available parameters of the
         /// `canyon_macro(table_name = "table_name", schema = "schema")`
         fn update_query<'a>() -> canyon_sql::query::UpdateQueryBuilder<'a, #ty> {
-            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, "")
+            #update_query_builder
         }
 
         /// Generates a [`canyon_sql::query::UpdateQueryBuilder`]
@@ -136,7 +292,194 @@ pub fn generate_update_query_tokens(
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         fn update_query_datasource<'a>(datasource_name: &'a str) -> canyon_sql::query::UpdateQueryBuilder<'a, #ty> {
-            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, datasource_name)
+            #update_query_builder_with_ds
+        }
+    }
+}
+
+/// Generates the implementation of the `Trackable` trait, that allows to wrap the
+/// entity in a `Tracked`, whose update only writes the columns that were modified
+/// since the entity was retrieved
+pub fn generate_trackable_tokens(
+    macro_data: &MacroTokens,
+    table_schema_data: &String,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let fields = macro_data.get_struct_fields();
+    let primary_key = macro_data.get_primary_key_annotation();
+    let version = macro_data.get_version_annotation();
+
+    // The related entities aren't columns, so the snapshot doesn't keep them
+    let relation_fields = macro_data
+        .get_relation_fields()
+        .into_iter()
+        .map(|(field, _, _)| field)
+        .collect::<Vec<_>>();
+    let snapshot_fields = fields
+        .iter()
+        .filter(|field| !relation_fields.contains(field))
+        .collect::<Vec<_>>();
+
+    // The primary key identifies the row, and the version it's incremented by the database
+    let tracked_fields = snapshot_fields
+        .iter()
+        .copied()
+        .filter(|field| {
+            let field = field.to_string();
+            Some(&field) != primary_key.as_ref() && Some(&field) != version.as_ref()
+        })
+        .collect::<Vec<_>>();
+    // The `#[embedded]` fields are compared as a whole, changing all their columns
+    let columns = macro_data.get_columns();
+    let columns_of = |field: &Ident| {
+        columns
+            .iter()
+            .filter(|column| column.field == field)
+            .collect::<Vec<_>>()
+    };
+    let tracked_columns = tracked_fields.iter().map(|field| {
+        columns_of(field)
+            .into_iter()
+            .map(|column| column.name.as_str())
+            .collect::<Vec<_>>()
+    });
+
+    let snapshot_and_changes = quote! {
+        fn snapshot(&self) -> Self {
+            Self {
+                #(#snapshot_fields: self.#snapshot_fields.clone(),)*
+                #(#relation_fields: Default::default(),)*
+            }
+        }
+
+        fn changed_columns(&self, snapshot: &Self) -> Vec<&'static str> {
+            let mut columns = Vec::new();
+            #(
+                if self.#tracked_fields != snapshot.#tracked_fields {
+                    columns.extend([#(#tracked_columns),*]);
+                }
+            )*
+            columns
+        }
+    };
+
+    let Some(primary_key) = primary_key else {
+        return quote! {
+            #[canyon_sql::macros::async_trait]
+            impl canyon_sql::crud::Trackable for #ty {
+                #snapshot_and_changes
+
+                async fn update_changed<'a>(&mut self, _snapshot: &Self, _datasource_name: &'a str)
+                    -> Result<u64, Box<dyn std::error::Error + Send + Sync>>
+                {
+                    Err(
+                        "You can't update a Tracked CanyonEntity that does not have \
+                        a #[primary_key] annotation."
+                            .into(),
+                    )
+                }
+            }
+        };
+    };
+    let pk_field = fields
+        .iter()
+        .find(|f| *f.to_string() == primary_key)
+        .expect("Update method failed to retrieve the Ident of the primary key");
+
+    let (timestamps_now, timestamps_assign) = generate_timestamps_tokens(
+        macro_data,
+        false,
+        quote! { self },
+        quote! { datasource_name },
+    );
+
+    let changed_values = tracked_fields.iter().map(|field| {
+        let changed_columns = columns_of(field).into_iter().map(|column| {
+            let name = &column.name;
+            let value = column.value(&quote! { self });
+            quote! {
+                values.push(#value);
+                columns.push(format!("\"{}\" = ${}", #name, values.len()));
+            }
+        });
+        quote! {
+            if self.#field != snapshot.#field {
+                #(#changed_columns)*
+            }
+        }
+    });
+
+    let (version_tokens, stale_check, version_increment) = match &version {
+        Some(version_column) => {
+            let version_field = fields
+                .iter()
+                .find(|f| f.to_string() == *version_column)
+                .expect("Update method failed to retrieve the Ident of the version field");
+            let version_increment = format!("\"{version_column}\" = \"{version_column}\" + 1");
+
+            (
+                quote! {
+                    values.push(&self.#version_field);
+                    columns.push(#version_increment.to_string());
+                    let version_clause = format!(" AND \"{}\" = ${}", #version_column, values.len());
+                },
+                quote! {
+                    if affected_rows == 0 {
+                        return Err(canyon_sql::crud::errors::StaleObjectError::new(
+                            stringify!(#ty),
+                            format!("{:?}", self.#pk_field),
+                            self.#version_field.to_string(),
+                        ).into());
+                    }
+                },
+                quote! { self.#version_field += 1; },
+            )
+        }
+        None => (quote! { let version_clause = ""; }, quote! {}, quote! {}),
+    };
+    let update_changed = generate_hooked_transaction_tokens(
+        &quote! { datasource_name },
+        quote! {
+            let affected_rows = transaction.execute(&stmt, &values).await?;
+            #stale_check
+
+            canyon_sql::crud::CanyonHooks::after_update(self).await?;
+            Ok(affected_rows)
+        },
+    );
+
+    quote! {
+        #[canyon_sql::macros::async_trait]
+        impl canyon_sql::crud::Trackable for #ty {
+            #snapshot_and_changes
+
+            async fn update_changed<'a>(&mut self, snapshot: &Self, datasource_name: &'a str)
+                -> Result<u64, Box<dyn std::error::Error + Send + Sync>>
+            {
+                if canyon_sql::crud::Trackable::changed_columns(self, snapshot).is_empty() {
+                    return Ok(0);
+                }
+
+                canyon_sql::crud::CanyonHooks::before_update(self).await?;
+                canyon_sql::crud::validation::Validate::validate(self)?;
+                #timestamps_now
+                #timestamps_assign
+
+                // The hooks and the timestamps may have modified more fields
+                let mut columns: Vec<String> = Vec::new();
+                let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> =
+                    vec![&self.#pk_field];
+                #(#changed_values)*
+                #version_tokens
+
+                let stmt = format!(
+                    "UPDATE {} SET {} WHERE {} = $1{}",
+                    #table_schema_data, columns.join(", "), #primary_key, version_clause
+                );
+                let affected_rows = { #update_changed }?;
+                #version_increment
+                Ok(affected_rows)
+            }
         }
     }
 }
@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use canyon_entities::field_annotation::ValidationRule;
+
+use crate::utils::macro_tokens::MacroTokens;
+
+/// Generates the implementation of the `Validate` trait, that checks the rules
+/// declared in the `#[validate(...)]` annotations of the fields of the entity
+pub fn generate_validate_tokens(macro_data: &MacroTokens) -> TokenStream {
+    let ty = macro_data.ty;
+    let rules = macro_data.get_validation_rules();
+
+    if rules.is_empty() {
+        return quote! {
+            impl canyon_sql::crud::validation::Validate for #ty {
+                fn validate(&self) -> Result<(), canyon_sql::crud::errors::ValidationErrors> {
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    let checks = rules.iter().map(|(field, rule)| {
+        let field_name = field.to_string();
+        let (rule_name, check) = match rule {
+            ValidationRule::Length { min, max } => {
+                let (min, max) = (optional_tokens(min), optional_tokens(max));
+                (
+                    "length",
+                    quote! { canyon_sql::crud::validation::check_length(&self.#field, #min, #max) },
+                )
+            }
+            ValidationRule::Range { min, max } => {
+                let (min, max) = (optional_expr(min), optional_expr(max));
+                (
+                    "range",
+                    quote! { canyon_sql::crud::validation::check_range(&self.#field, #min, #max) },
+                )
+            }
+            ValidationRule::Regex(pattern) => (
+                "regex",
+                // The regular expression is compiled just once, and it's already
+                // known that is valid, because it was checked by the annotation parser
+                quote! {{
+                    static REGEX: std::sync::OnceLock<canyon_sql::crud::validation::Regex> =
+                        std::sync::OnceLock::new();
+                    canyon_sql::crud::validation::check_regex(
+                        &self.#field,
+                        REGEX.get_or_init(|| canyon_sql::crud::validation::Regex::new(#pattern).unwrap())
+                    )
+                }},
+            ),
+            ValidationRule::Custom(function) => {
+                let function = syn::parse_str::<syn::Path>(function)
+                    .expect("The path of the custom validation function was already checked");
+                ("custom", quote! { #function(&self.#field) })
+            }
+        };
+
+        quote! {
+            if let Err(message) = #check {
+                errors.add(#field_name, #rule_name, message);
+            }
+        }
+    });
+
+    quote! {
+        impl canyon_sql::crud::validation::Validate for #ty {
+            fn validate(&self) -> Result<(), canyon_sql::crud::errors::ValidationErrors> {
+                let mut errors = canyon_sql::crud::errors::ValidationErrors::new(stringify!(#ty));
+                #(#checks)*
+                errors.into_result()
+            }
+        }
+    }
+}
+
+fn optional_tokens(value: &Option<usize>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+fn optional_expr(value: &Option<String>) -> TokenStream {
+    match value {
+        Some(value) => {
+            let expr = syn::parse_str::<syn::Expr>(value)
+                .expect("The bounds of the range rule were already parsed as expressions");
+            quote! { Some(#expr) }
+        }
+        None => quote! { None },
+    }
+}
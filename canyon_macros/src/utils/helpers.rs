@@ -1,5 +1,9 @@
+use canyon_entities::{
+    field_annotation::EntityFieldAnnotation, register_types::CanyonRegisterJoinTable,
+};
 use proc_macro2::{Ident, Span, TokenStream};
-use syn::{punctuated::Punctuated, MetaNameValue, Token};
+use quote::{quote_spanned, ToTokens};
+use syn::{punctuated::Punctuated, spanned::Spanned, MetaNameValue, Token};
 
 use super::macro_tokens::MacroTokens;
 
@@ -77,6 +81,249 @@ pub fn table_schema_parser(macro_data: &MacroTokens<'_>) -> Result<String, Token
     Ok(macro_data.ty.to_string())
 }
 
+/// The options given to the `CanyonCrud` derive macro with the `#[canyon_crud(...)]` attribute
+#[derive(Default)]
+pub struct CrudOptions {
+    /// `hooks` means that the user provides its own implementation of the `CanyonHooks`
+    /// trait, so the derive macro must not generate the default one
+    pub user_defined_hooks: bool,
+    /// `tracked` implements the `Trackable` trait, which requires every field to be
+    /// `Clone` and `PartialEq`, so the entity can be wrapped in a `Tracked`
+    pub tracked: bool,
+}
+
+/// Returns the options of the entity given with the `#[canyon_crud(...)]` attribute
+pub fn crud_options(macro_data: &MacroTokens<'_>) -> Result<CrudOptions, TokenStream> {
+    let mut options = CrudOptions::default();
+
+    for attr in macro_data
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("canyon_crud"))
+    {
+        let args: Punctuated<Ident, Token![,]> = attr
+            .parse_args_with(Punctuated::parse_terminated)
+            .map_err(|err| err.into_compile_error())?;
+
+        for arg in args {
+            if arg == "hooks" {
+                options.user_defined_hooks = true
+            } else if arg == "tracked" {
+                options.tracked = true
+            } else {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "Unknown argument for the `canyon_crud` attribute. \
+                    Valid ones are: `hooks`, `tracked`",
+                )
+                .into_compile_error());
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+/// Returns the SQL type declared with `#[canyon_type(sql_type = "...")]` on a type
+/// that derives `CanyonType`, if any
+pub fn declared_sql_type(attrs: &[syn::Attribute]) -> Result<Option<String>, TokenStream> {
+    let mut sql_type = None;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("canyon_type"))
+    {
+        let args: Punctuated<MetaNameValue, Token![,]> = attr
+            .parse_args_with(Punctuated::parse_terminated)
+            .map_err(|err| err.into_compile_error())?;
+
+        for arg in args {
+            match &arg.lit {
+                syn::Lit::Str(s) if arg.path.is_ident("sql_type") => sql_type = Some(s.value()),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "Unknown argument for the `canyon_type` attribute. \
+                        Valid ones are: `sql_type = \"...\"`",
+                    )
+                    .into_compile_error())
+                }
+            }
+        }
+    }
+
+    Ok(sql_type)
+}
+
+/// Returns the arguments of the `#[canyon_enum(...)]` attributes, for the enums that
+/// derives `CanyonEnum` and its variants, failing on the ones that aren't in `valid_args`
+pub fn canyon_enum_args(
+    attrs: &[syn::Attribute],
+    valid_args: &[&str],
+) -> Result<Vec<(String, syn::LitStr)>, TokenStream> {
+    let mut parsed = Vec::new();
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("canyon_enum"))
+    {
+        let args: Punctuated<MetaNameValue, Token![,]> = attr
+            .parse_args_with(Punctuated::parse_terminated)
+            .map_err(|err| err.into_compile_error())?;
+
+        for arg in args {
+            let name = arg
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            match &arg.lit {
+                syn::Lit::Str(s) if valid_args.contains(&name.as_str()) => {
+                    parsed.push((name, s.clone()))
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        format!(
+                            "Unknown argument for the `canyon_enum` attribute. Valid ones are: {}",
+                            valid_args
+                                .iter()
+                                .map(|valid| format!("`{valid} = \"...\"`"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    )
+                    .into_compile_error())
+                }
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parses the explicit discriminant of a variant of an enum that derives `CanyonEnum`,
+/// that must be an integer literal
+pub fn parse_discriminant(discriminant: &syn::Expr) -> Result<i32, TokenStream> {
+    let (literal, negative) = match discriminant {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(literal),
+            ..
+        }) => (literal, false),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(literal),
+                ..
+            }) => (literal, true),
+            _ => return Err(invalid_discriminant(discriminant)),
+        },
+        _ => return Err(invalid_discriminant(discriminant)),
+    };
+
+    literal
+        .base10_parse::<i32>()
+        .map(|value| if negative { -value } else { value })
+        .map_err(|err| err.into_compile_error())
+}
+
+fn invalid_discriminant(discriminant: &syn::Expr) -> TokenStream {
+    syn::Error::new_spanned(
+        discriminant,
+        "The discriminants of a `CanyonEnum` must be integer literals",
+    )
+    .into_compile_error()
+}
+
+/// Returns true if the type that derives `CanyonMapper` is annotated with
+/// `#[canyon_mapper(lenient)]`, which means that its optional fields are mapped
+/// as `None` when their columns aren't present on the rows
+pub fn is_lenient_mapper(attrs: &[syn::Attribute]) -> Result<bool, TokenStream> {
+    let mut lenient = false;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("canyon_mapper"))
+    {
+        let args: Punctuated<Ident, Token![,]> = attr
+            .parse_args_with(Punctuated::parse_terminated)
+            .map_err(|err| err.into_compile_error())?;
+
+        for arg in args {
+            if arg == "lenient" {
+                lenient = true
+            } else {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "Unknown argument for the `canyon_mapper` attribute. Valid ones are: `lenient`",
+                )
+                .into_compile_error());
+            }
+        }
+    }
+
+    Ok(lenient)
+}
+
+/// The column, or columns, that a field of a type that derives `CanyonMapper` is read from
+pub enum MappedColumn {
+    /// The name of the field, followed by the aliases declared with `#[column(alias = "...")]`
+    Named(Vec<String>),
+    /// The position declared with `#[column_index(n)]`
+    Index(usize),
+}
+
+/// Returns the column that a field of a type that derives `CanyonMapper` is read from,
+/// being the one named as the field unless the `#[column_index(n)]` or the
+/// `#[column(alias = "...")]` attributes says otherwise
+pub fn mapped_column(field: &syn::Field) -> Result<MappedColumn, TokenStream> {
+    let mut names = vec![field.ident.as_ref().unwrap().to_string()];
+    let mut index = None;
+
+    for attr in &field.attrs {
+        if attr.path.is_ident("column_index") {
+            let literal: syn::LitInt = attr.parse_args().map_err(|err| err.into_compile_error())?;
+            index = Some(
+                literal
+                    .base10_parse::<usize>()
+                    .map_err(|err| err.into_compile_error())?,
+            );
+        } else if attr.path.is_ident("column") {
+            let args: Punctuated<MetaNameValue, Token![,]> = attr
+                .parse_args_with(Punctuated::parse_terminated)
+                .map_err(|err| err.into_compile_error())?;
+
+            for arg in args {
+                match &arg.lit {
+                    syn::Lit::Str(s) if arg.path.is_ident("alias") => names.push(s.value()),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            arg,
+                            "Unknown argument for the `column` attribute. \
+                            Valid ones are: `alias = \"...\"`",
+                        )
+                        .into_compile_error())
+                    }
+                }
+            }
+        }
+    }
+
+    match index {
+        Some(_) if names.len() > 1 => Err(syn::Error::new_spanned(
+            field,
+            "A field can't be read by position with `#[column_index]` and by name \
+            with `#[column(alias)]` at the same time",
+        )
+        .into_compile_error()),
+        Some(idx) => Ok(MappedColumn::Index(idx)),
+        None => Ok(MappedColumn::Named(names)),
+    }
+}
+
 /// Parses a syn::Identifier to get a snake case database name from the type identifier
 pub fn _database_table_name_from_struct(ty: &Ident) -> String {
     let struct_name: String = ty.to_string();
@@ -151,6 +398,92 @@ pub fn default_database_table_name_from_entity_name(ty: &str) -> String {
     table_name
 }
 
+/// Returns the join table of a `#[many_to_many]` relation declared on an entity whose
+/// table is `table_schema_data`, where the columns that aren't declared by the annotation
+/// are named after the tables (without the schema) of the entities that they references
+pub fn join_table(
+    table_schema_data: &str,
+    annotation: &EntityFieldAnnotation,
+) -> Option<CanyonRegisterJoinTable> {
+    let EntityFieldAnnotation::ManyToMany(related_entity, through, column, related_column) =
+        annotation
+    else {
+        return None;
+    };
+    let table_name = table_schema_data
+        .rsplit('.')
+        .next()
+        .unwrap_or(table_schema_data);
+
+    Some(CanyonRegisterJoinTable {
+        table_name: through.to_owned(),
+        column: column.clone().unwrap_or_else(|| table_name.to_owned()),
+        related_entity_name: related_entity.to_owned(),
+        related_column: related_column
+            .clone()
+            .unwrap_or_else(|| default_database_table_name_from_entity_name(related_entity)),
+    })
+}
+
+/// Returns the assertion that fails the compilation if the type isn't the one registered
+/// with the given key, that implements `canyon_trait` through its derive.
+///
+/// The registered types are found by the last segment of the path written for the type
+/// of the fields, so the assertion checks that the full path leads to the same type
+pub fn registry_key_assertion(
+    ty: &syn::Type,
+    canyon_trait: TokenStream,
+    registry_key: &str,
+) -> TokenStream {
+    let ty = option_inner_type(ty).unwrap_or(ty);
+    let message = format!(
+        "The type `{}` isn't the one with that name that derives `{}` before it's used here",
+        ty.to_token_stream().to_string().replace(' ', ""),
+        canyon_trait
+            .to_string()
+            .rsplit(' ')
+            .next()
+            .unwrap_or_default(),
+    );
+    quote_spanned! {ty.span()=>
+        const _: () = assert!(
+            canyon_sql::crud::types::same_registry_key(
+                <#ty as #canyon_trait>::REGISTRY_KEY,
+                #registry_key
+            ),
+            #message
+        );
+    }
+}
+
+/// Returns the type wrapped by an [`Option`], or `None` if the type isn't an [`Option`]
+pub fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    wrapped_type(ty, "Option")
+}
+
+/// Returns the type of the elements of a [`Vec`], or `None` if the type isn't a [`Vec`]
+pub fn vec_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    wrapped_type(ty, "Vec")
+}
+
+/// Returns the first generic argument of the type, if it's the `wrapper` type
+fn wrapped_type<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != wrapper {
+        return None;
+    }
+    match &last_segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner_ty)) => Some(inner_ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Parses the content of an &str to get the related identifier of a type
 pub fn database_table_name_to_struct_ident(name: &str) -> Ident {
     let mut struct_name: String = String::new();
@@ -169,7 +502,8 @@ pub fn database_table_name_to_struct_ident(name: &str) -> Ident {
                 }
                 char if char.is_ascii_lowercase() => {
                     if previous_was_underscore {
-                        struct_name.push(char.to_ascii_lowercase())
+                        struct_name.push(char.to_ascii_uppercase());
+                        previous_was_underscore = false;
                     } else {
                         struct_name.push(char)
                     }
@@ -181,3 +515,12 @@ pub fn database_table_name_to_struct_ident(name: &str) -> Ident {
 
     Ident::new(&struct_name, proc_macro2::Span::call_site())
 }
+
+#[test]
+fn test_database_table_name_to_struct_ident() {
+    assert_eq!(database_table_name_to_struct_ident("league"), "League");
+    assert_eq!(
+        database_table_name_to_struct_ident("soft_deleted_league"),
+        "SoftDeletedLeague"
+    );
+}
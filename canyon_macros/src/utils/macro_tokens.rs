@@ -1,8 +1,77 @@
 use std::convert::TryFrom;
 
-use canyon_entities::field_annotation::EntityFieldAnnotation;
-use proc_macro2::Ident;
-use syn::{Attribute, DeriveInput, Fields, Generics, Type, Visibility};
+use canyon_entities::{
+    field_annotation::{EntityFieldAnnotation, ValidationRule},
+    register_types::CanyonRegisterEmbeddable,
+};
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Attribute, DeriveInput, Field, Fields, Generics, Type, Visibility};
+
+use super::helpers;
+
+/// A column of the table of an entity, that maps a field of the struct, or one of the
+/// fields of the value object held by an `#[embedded]` field
+pub struct EntityColumn<'a> {
+    pub name: String,
+    pub field: &'a Ident,
+    /// The name of the field of the value object, for the `#[embedded]` fields
+    pub embedded_field: Option<String>,
+}
+
+impl EntityColumn<'_> {
+    /// Returns the expression that borrows the value of the column from `instance`
+    /// as a `QueryParameter`
+    pub fn value(&self, instance: &TokenStream) -> TokenStream {
+        let field = self.field;
+        match &self.embedded_field {
+            Some(embedded_field) => quote! {
+                canyon_sql::crud::CanyonEmbeddable::get_column(&#instance.#field, #embedded_field)
+                    .expect("The embedded value object doesn't have the mapped field")
+            },
+            None => quote! { &#instance.#field },
+        }
+    }
+}
+
+/// Returns the prefix of the `#[embedded]` annotation of the field, if it's a value object
+pub fn embedded_prefix(field: &Field) -> Option<String> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("embedded"))
+        .find_map(|attr| match EntityFieldAnnotation::try_from(&attr) {
+            Ok(EntityFieldAnnotation::Embedded(prefix)) => Some(prefix),
+            _ => None,
+        })
+}
+
+/// Returns the `#[has_many]` or `#[many_to_many]` annotation of the field, if it holds
+/// a collection of related entities instead of a column of the table
+pub fn relation_annotation(field: &Field) -> Option<EntityFieldAnnotation> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("has_many") || attr.path.is_ident("many_to_many"))
+        .find_map(|attr| EntityFieldAnnotation::try_from(&attr).ok())
+}
+
+/// Returns the registered value object of an `#[embedded]` field, or the error to report
+/// if its type doesn't derive `CanyonEmbeddable` (or it isn't declared before), or if
+/// several value objects are registered with its name
+pub fn embeddable_of(field: &Field) -> Result<CanyonRegisterEmbeddable, TokenStream> {
+    let embeddable_type = field.ty.to_token_stream().to_string();
+    CanyonRegisterEmbeddable::find(&embeddable_type)
+        .and_then(|embeddable| {
+            embeddable.ok_or_else(|| {
+                format!(
+                    "The type `{embeddable_type}` of an `#[embedded]` field must derive \
+                    `CanyonEmbeddable`, and be declared before the types that embeds it"
+                )
+            })
+        })
+        .map_err(|err| syn::Error::new_spanned(&field.ty, err).into_compile_error())
+}
 
 /// Provides a convenient way of store the data for the TokenStream
 /// received on a macro
@@ -60,6 +129,83 @@ impl<'a> MacroTokens<'a> {
             .collect::<Vec<_>>()
     }
 
+    /// Gives a Vec with the columns of the table of the entity, in the order of the
+    /// fields of the Struct, where the `#[embedded]` fields are flattened into a
+    /// prefixed column for every field of their value object, and the fields that
+    /// holds the related entities of a `#[has_many]` or a `#[many_to_many]` are skipped
+    pub fn get_columns(&self) -> Vec<EntityColumn<'_>> {
+        let mut columns = Vec::new();
+        for field in self.fields.iter() {
+            if relation_annotation(field).is_some() {
+                continue;
+            }
+            let ident = field.ident.as_ref().unwrap();
+            match embedded_prefix(field) {
+                Some(prefix) => {
+                    let embedded_fields = embeddable_of(field)
+                        .map(|embeddable| embeddable.fields)
+                        .unwrap_or_default();
+                    columns.extend(
+                        embedded_fields
+                            .into_iter()
+                            .map(|embedded_field| EntityColumn {
+                                name: format!("{prefix}{}", embedded_field.field_name),
+                                field: ident,
+                                embedded_field: Some(embedded_field.field_name),
+                            }),
+                    )
+                }
+                None => columns.push(EntityColumn {
+                    name: ident.to_string(),
+                    field: ident,
+                    embedded_field: None,
+                }),
+            }
+        }
+        columns
+    }
+
+    /// Returns the error to report if some `#[embedded]` field of the Struct
+    /// holds a type that isn't a registered value object. Otherwise, returns the
+    /// assertions that checks at compile time that the value objects found on the
+    /// register by their names are the types of the fields
+    pub fn check_embedded_fields(&self) -> Result<TokenStream, TokenStream> {
+        self.fields
+            .iter()
+            .filter(|field| embedded_prefix(field).is_some())
+            .map(|field| {
+                embeddable_of(field).map(|embeddable| {
+                    helpers::registry_key_assertion(
+                        &field.ty,
+                        quote! { canyon_sql::crud::CanyonEmbeddable },
+                        &embeddable.registry_key,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Gives a Vec with the fields of the Struct that holds an `#[embedded]` value object
+    pub fn get_embedded_fields(&self) -> Vec<Ident> {
+        self.fields
+            .iter()
+            .filter(|field| embedded_prefix(field).is_some())
+            .map(|field| field.ident.as_ref().unwrap().clone())
+            .collect()
+    }
+
+    /// Gives a Vec with the fields of the Struct that holds the related entities of a
+    /// `#[has_many]` or a `#[many_to_many]`, along with their type and the parsed annotation
+    pub fn get_relation_fields(&self) -> Vec<(&Ident, &Type, EntityFieldAnnotation)> {
+        self.fields
+            .iter()
+            .filter_map(|field| {
+                relation_annotation(field)
+                    .map(|annotation| (field.ident.as_ref().unwrap(), &field.ty, annotation))
+            })
+            .collect()
+    }
+
     /// Gives a Vec populated with the name of the fields of the struct
     pub fn _get_struct_fields_as_collection_strings(&self) -> Vec<String> {
         self.get_struct_fields()
@@ -79,52 +225,33 @@ impl<'a> MacroTokens<'a> {
     ///
     /// Returns every field if there's no PK, or if it's present but autoincremental = false
     pub fn get_column_names_pk_parsed(&self) -> Vec<String> {
-        self.fields
+        let skipped_fields = self
+            .fields
             .iter()
             .filter(|field| {
-                if !field.attrs.is_empty() {
-                    field.attrs.iter().any(|attr| {
+                !field.attrs.is_empty()
+                    && !field.attrs.iter().any(|attr| {
                         let a = attr.path.segments[0].clone().ident;
                         let b = attr.tokens.to_string();
                         !(a == "primary_key" || b.contains("false"))
                     })
-                } else {
-                    true
-                }
             })
-            .map(|c| format!("\"{}\"", c.ident.as_ref().unwrap()))
-            .collect::<Vec<String>>()
-    }
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect::<Vec<_>>();
 
-    /// Retrieves the fields of the Struct as continuous String, comma separated
-    pub fn get_struct_fields_as_strings(&self) -> String {
-        let column_names: String = self
-            .get_struct_fields()
-            .iter()
-            .map(|ident| ident.to_owned().to_string())
+        self.get_columns()
+            .into_iter()
+            .filter(|column| !skipped_fields.contains(&column.field))
+            .map(|c| format!("\"{}\"", c.name))
             .collect::<Vec<String>>()
-            .iter()
-            .map(|column| column.to_owned() + ", ")
-            .collect::<String>();
-
-        let mut column_names_as_chars = column_names.chars();
-        column_names_as_chars.next_back();
-        column_names_as_chars.next_back();
-
-        column_names_as_chars.as_str().to_owned()
     }
 
-    ///
+    /// Returns the position of the column of the primary key among the columns of the entity
     pub fn get_pk_index(&self) -> Option<usize> {
-        let mut pk_index = None;
-        for (idx, field) in self.fields.iter().enumerate() {
-            for attr in &field.attrs {
-                if attr.path.segments[0].clone().ident == "primary_key" {
-                    pk_index = Some(idx);
-                }
-            }
-        }
-        pk_index
+        let primary_key = self.get_primary_key_annotation()?;
+        self.get_columns()
+            .iter()
+            .position(|column| column.embedded_field.is_none() && column.name == primary_key)
     }
 
     /// Utility for find the primary key attribute (if exists) and the
@@ -163,45 +290,109 @@ impl<'a> MacroTokens<'a> {
         foreign_key_annotations
     }
 
-    /// Boolean that returns true if the type contains a `#[primary_key]`
-    /// annotation. False otherwise.
-    pub fn type_has_primary_key(&self) -> bool {
-        self.fields.iter().any(|field| {
-            field
-                .attrs
-                .iter()
-                .map(|attr| attr.path.segments[0].clone().ident)
-                .map(|ident| ident.to_string())
-                .find(|a| a == "primary_key")
-                == Some("primary_key".to_string())
-        })
+    /// Utility for find the field annotated with the `soft_delete` attribute (if exists),
+    /// returning the name of the column which belongs
+    pub fn get_soft_delete_annotation(&self) -> Option<String> {
+        self.fields
+            .iter()
+            .find(|field| {
+                field
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.segments[0].ident == "soft_delete")
+            })
+            .map(|field| field.ident.as_ref().unwrap().to_string())
     }
 
-    /// Returns an String ready to be inserted on the VALUES Sql clause
-    /// representing generic query parameters ($x).
-    ///
-    /// Already returns the correct number of placeholders, skipping one
-    /// entry in the type contains a `#[primary_key]`
-    pub fn placeholders_generator(&self) -> String {
-        let mut placeholders = String::new();
-        if self.type_has_primary_key() {
-            for num in 1..self.fields.len() {
-                if num < self.fields.len() - 1 {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string() + ", "));
-                } else {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string()));
-                }
-            }
-        } else {
-            for num in 1..self.fields.len() + 1 {
-                if num < self.fields.len() {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string() + ", "));
-                } else {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string()));
+    /// Utility for find the fields annotated with the `unique` attribute, returning
+    /// the names of the columns which belongs
+    pub fn get_unique_annotations(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|field| {
+                field
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.segments[0].ident == "unique")
+            })
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect()
+    }
+
+    /// Utility for find the fields annotated with the `db_default` attribute, returning
+    /// the names of the columns which belongs
+    pub fn get_db_default_annotations(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|field| {
+                field
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.segments[0].ident == "db_default")
+            })
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect()
+    }
+
+    /// Utility for find the field annotated with the `version` attribute (if exists),
+    /// returning the name of the column which belongs
+    pub fn get_version_annotation(&self) -> Option<String> {
+        self.fields
+            .iter()
+            .find(|field| {
+                field
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.segments[0].ident == "version")
+            })
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+    }
+
+    /// Utility for find the fields annotated with the `created_at` and `updated_at`
+    /// attributes (if exists), along with their type and the parsed annotation
+    pub fn get_timestamp_annotations(&self) -> Vec<(&Ident, &Type, EntityFieldAnnotation)> {
+        let mut timestamp_annotations = Vec::new();
+
+        self.fields.iter().for_each(|field| {
+            let attrs = field.attrs.iter().filter(|attr| {
+                let ident = &attr.path.segments[0].ident;
+                ident == "created_at" || ident == "updated_at"
+            });
+            attrs.for_each(|attr| {
+                if let Ok(annotation) = EntityFieldAnnotation::try_from(&attr) {
+                    timestamp_annotations.push((
+                        field.ident.as_ref().unwrap(),
+                        &field.ty,
+                        annotation,
+                    ))
                 }
-            }
-        }
+            });
+        });
+
+        timestamp_annotations
+    }
+
+    /// Utility for find the rules of the `validate` attributes (if exists), along with
+    /// the field which belongs. A field may have several `validate` attributes
+    pub fn get_validation_rules(&self) -> Vec<(&Ident, ValidationRule)> {
+        let mut validation_rules = Vec::new();
+
+        self.fields.iter().for_each(|field| {
+            field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.segments[0].ident == "validate")
+                .for_each(|attr| {
+                    if let Ok(EntityFieldAnnotation::Validate(rules)) =
+                        EntityFieldAnnotation::try_from(&attr)
+                    {
+                        for rule in rules {
+                            validation_rules.push((field.ident.as_ref().unwrap(), rule))
+                        }
+                    }
+                });
+        });
 
-        placeholders
+        validation_rules
     }
 }
@@ -32,6 +32,23 @@ pub mod postgresql_queries {
             gi.column_name = split_part(split_part(CAST(pg_catalog.pg_get_constraintdef(oid) AS TEXT),')',1),'(',2)
         WHERE
             table_schema = 'public';";
+
+    pub static FETCH_ENUM_TYPES: &str = "SELECT
+            CAST(t.typname AS TEXT) AS type_name,
+            CAST(e.enumlabel AS TEXT) AS label
+        FROM pg_catalog.pg_type AS t
+        JOIN pg_catalog.pg_enum AS e ON e.enumtypid = t.oid
+        JOIN pg_catalog.pg_namespace AS n ON n.oid = t.typnamespace
+        WHERE n.nspname = 'public'
+        ORDER BY t.typname, e.enumsortorder;";
+
+    pub static FETCH_CHECK_CONSTRAINTS: &str = "SELECT
+            CAST(con.conrelid::regclass AS TEXT) AS table_name,
+            CAST(con.conname AS TEXT) AS constraint_name,
+            CAST(pg_catalog.pg_get_constraintdef(con.oid) AS TEXT) AS definition
+        FROM pg_catalog.pg_constraint AS con
+        JOIN pg_catalog.pg_namespace AS n ON n.oid = con.connamespace
+        WHERE con.contype = 'c' AND n.nspname = 'public';";
 }
 
 #[cfg(feature = "mssql")]
@@ -88,11 +105,30 @@ pub mod mssql_queries {
             ) AS pk
                 ON pk.TABLE_NAME = gi.TABLE_NAME AND pk.CONSTRAINT_SCHEMA = gi.TABLE_SCHEMA AND pk.COLUMN_NAME = gi.COLUMN_NAME
             WHERE gi.TABLE_SCHEMA = 'dbo'";
+
+    pub static FETCH_CHECK_CONSTRAINTS: &str = "SELECT
+            OBJECT_NAME(cc.parent_object_id) AS table_name,
+            cc.name AS constraint_name,
+            cc.definition
+        FROM sys.check_constraints AS cc
+        WHERE SCHEMA_NAME(cc.schema_id) = 'dbo'";
+}
+
+#[cfg(feature = "mysql")]
+pub mod mysql_queries {
+    pub static FETCH_CHECK_CONSTRAINTS: &str = "SELECT
+            tc.TABLE_NAME AS table_name,
+            cc.CONSTRAINT_NAME AS constraint_name,
+            cc.CHECK_CLAUSE AS definition
+        FROM information_schema.CHECK_CONSTRAINTS AS cc
+        JOIN information_schema.TABLE_CONSTRAINTS AS tc
+            ON tc.CONSTRAINT_SCHEMA = cc.CONSTRAINT_SCHEMA AND tc.CONSTRAINT_NAME = cc.CONSTRAINT_NAME
+        WHERE tc.CONSTRAINT_TYPE = 'CHECK' AND cc.CONSTRAINT_SCHEMA = DATABASE()";
 }
 
 /// Constant string values that holds regex patterns
 pub mod regex_patterns {
-    pub const EXTRACT_RUST_OPT_REGEX: &str = r"[Oo][Pp][Tt][Ii][Oo][Nn]<(?P<rust_type>[\w<>]+)>";
+    pub const EXTRACT_RUST_OPT_REGEX: &str = r"[Oo][Pp][Tt][Ii][Oo][Nn]<(?P<rust_type>[\w<>:]+)>";
     pub const EXTRACT_FOREIGN_KEY_INFO: &str =
         r"\w+\s\w+\s\((?P<current_column>\w+)\)\s\w+\s(?P<ref_table>\w+)\((?P<ref_column>\w+)\)";
 }
@@ -140,6 +176,23 @@ pub mod rust_type {
 
     pub const NAIVE_DATE_TIME: &str = "NaiveDateTime";
     pub const OPT_NAIVE_DATE_TIME: &str = "Option<NaiveDateTime>";
+    pub const DATE_TIME_UTC: &str = "DateTime<Utc>";
+    pub const OPT_DATE_TIME_UTC: &str = "Option<DateTime<Utc>>";
+
+    pub const BYTES: &str = "Vec<u8>";
+    pub const OPT_BYTES: &str = "Option<Vec<u8>>";
+
+    // The types of the external crates are matched without the path of their crate,
+    // except the JSON one, whose name (`Value`) is too generic to be matched alone
+    pub const UUID: &str = "Uuid";
+    pub const OPT_UUID: &str = "Option<Uuid>";
+    pub const DECIMAL: &str = "Decimal";
+    pub const OPT_DECIMAL: &str = "Option<Decimal>";
+    pub const JSON: &str = "serde_json::Value";
+    pub const OPT_JSON: &str = "Option<serde_json::Value>";
+
+    /// The paths of the external crates whose types are matched without them
+    pub const EXTERNAL_CRATE_PATHS: [&str; 2] = ["uuid::", "rust_decimal::"];
 }
 
 #[cfg(feature = "postgres")]
@@ -153,6 +206,11 @@ pub mod postgresql_type {
     pub const DATE: &str = "date";
     pub const TIME: &str = "time";
     pub const DATETIME: &str = "timestamp without time zone";
+    pub const DATETIME_TZ: &str = "timestamp with time zone";
+    pub const BYTEA: &str = "bytea";
+    pub const UUID: &str = "uuid";
+    pub const NUMERIC: &str = "numeric";
+    pub const JSONB: &str = "jsonb";
 }
 
 #[cfg(feature = "mssql")]
@@ -167,6 +225,13 @@ pub mod sqlserver_type {
     pub const DATE: &str = "DATE";
     pub const TIME: &str = "TIME";
     pub const DATETIME: &str = "DATETIME2";
+    pub const DATETIME_TZ: &str = "DATETIMEOFFSET";
+    pub const VARBINARY: &str = "VARBINARY(MAX)";
+    pub const UNIQUEIDENTIFIER: &str = "UNIQUEIDENTIFIER";
+    // The maximum precision of the `rust_decimal` values
+    pub const DECIMAL: &str = "DECIMAL(28, 10)";
+    // SQL Server doesn't have a native JSON type, so the documents are stored as text
+    pub const JSON: &str = "nvarchar(max)";
 }
 
 pub mod mocked_data {
@@ -3,6 +3,8 @@ use canyon_crud::rows::CanyonRows;
 use canyon_entities::CANYON_REGISTER_ENTITIES;
 use partialdebug::placeholder::PartialDebug;
 
+#[cfg(feature = "postgres")]
+use crate::migrations::information_schema::EnumTypeMetadata;
 use crate::{
     canyon_crud::{
         bounds::{Column, Row, RowOperations},
@@ -11,7 +13,10 @@ use crate::{
     },
     constants,
     migrations::{
-        information_schema::{ColumnMetadata, ColumnMetadataTypeValue, TableMetadata},
+        information_schema::{
+            CheckConstraintMetadata, ColumnMetadata, ColumnMetadataTypeValue, EnumsMetadata,
+            TableMetadata,
+        },
         memory::CanyonMemory,
         processor::MigrationsProcessor,
     },
@@ -72,11 +77,15 @@ impl Migrations {
                 }
             }
 
+            let database_enums =
+                Self::fetch_enums(&datasource.name, datasource.get_db_type()).await;
+
             migrations_processor
                 .process(
                     canyon_memory,
                     canyon_entities,
                     user_database_tables,
+                    &database_enums,
                     datasource,
                 )
                 .await;
@@ -107,6 +116,108 @@ impl Migrations {
             })
     }
 
+    /// Fetches the native `ENUM` types and the `CHECK` constraints of the schema
+    /// of the datasource, that holds the variants of the enums managed by Canyon
+    async fn fetch_enums(datasource_name: &str, db_type: DatabaseType) -> EnumsMetadata {
+        let mut enums = EnumsMetadata::default();
+
+        match db_type {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => {
+                let enum_types = Self::query(
+                    constants::postgresql_queries::FETCH_ENUM_TYPES,
+                    [],
+                    datasource_name,
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    panic!("Error querying the enum types for the datasource: {datasource_name}")
+                });
+                for row in enum_types.get_postgres_rows() {
+                    let type_name = row.get::<&str, String>("type_name");
+                    let label = row.get::<&str, String>("label");
+                    match enums
+                        .enum_types
+                        .iter_mut()
+                        .find(|enum_type| enum_type.type_name == type_name)
+                    {
+                        Some(enum_type) => enum_type.labels.push(label),
+                        None => enums.enum_types.push(EnumTypeMetadata {
+                            type_name,
+                            labels: vec![label],
+                        }),
+                    }
+                }
+
+                let check_constraints = Self::query(
+                    constants::postgresql_queries::FETCH_CHECK_CONSTRAINTS,
+                    [],
+                    datasource_name,
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    panic!(
+                        "Error querying the check constraints for the datasource: {datasource_name}"
+                    )
+                });
+                for row in check_constraints.get_postgres_rows() {
+                    enums.check_constraints.push(CheckConstraintMetadata {
+                        table_name: row.get::<&str, String>("table_name"),
+                        constraint_name: row.get::<&str, String>("constraint_name"),
+                        definition: row.get::<&str, String>("definition"),
+                    });
+                }
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => {
+                let check_constraints = Self::query(
+                    constants::mssql_queries::FETCH_CHECK_CONSTRAINTS,
+                    [],
+                    datasource_name,
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    panic!(
+                        "Error querying the check constraints for the datasource: {datasource_name}"
+                    )
+                });
+                for row in check_constraints.get_tiberius_rows() {
+                    let column =
+                        |name: &str| row.get::<&str, &str>(name).unwrap_or_default().to_string();
+                    enums.check_constraints.push(CheckConstraintMetadata {
+                        table_name: column("table_name"),
+                        constraint_name: column("constraint_name"),
+                        definition: column("definition"),
+                    });
+                }
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseType::MySQL => {
+                let check_constraints = Self::query(
+                    constants::mysql_queries::FETCH_CHECK_CONSTRAINTS,
+                    [],
+                    datasource_name,
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    panic!(
+                        "Error querying the check constraints for the datasource: {datasource_name}"
+                    )
+                });
+                for row in check_constraints.get_mysql_rows() {
+                    let column = |name: &str| row.get::<String, &str>(name).unwrap_or_default();
+                    enums.check_constraints.push(CheckConstraintMetadata {
+                        table_name: column("table_name"),
+                        constraint_name: column("constraint_name"),
+                        definition: column("definition"),
+                    });
+                }
+            }
+        }
+
+        enums
+    }
+
     /// Handler for parse the result of query the information of some database schema,
     /// and extract the content of the returned rows into custom structures with
     /// the data well organized for every entity present on that schema
@@ -31,6 +31,30 @@ pub struct ColumnMetadata {
     pub identity_generation: Option<String>,
 }
 
+/// The native `ENUM` types and the `CHECK` constraints of the current schema, that
+/// holds the variants of the enums that derives `CanyonEnum`
+#[derive(Debug, Default)]
+pub struct EnumsMetadata {
+    pub enum_types: Vec<EnumTypeMetadata>,
+    pub check_constraints: Vec<CheckConstraintMetadata>,
+}
+
+/// Represents a native `ENUM` type of a `PostgreSQL` schema
+#[derive(Debug, Default, Clone)]
+pub struct EnumTypeMetadata {
+    pub type_name: String,
+    /// The labels of the type, in their sort order
+    pub labels: Vec<String>,
+}
+
+/// Represents a `CHECK` constraint of a table
+#[derive(Debug, Default, Clone)]
+pub struct CheckConstraintMetadata {
+    pub table_name: String,
+    pub constraint_name: String,
+    pub definition: String,
+}
+
 /// Represents the relation between a real value stored inside a [`ColumnMetadata`]
 /// and the datatype of that value
 #[derive(Debug)]
@@ -11,18 +11,27 @@ use crate::canyon_crud::{crud::Transaction, DatasourceConfig};
 use crate::constants::regex_patterns;
 use crate::save_migrations_query_to_execute;
 
-use super::information_schema::{ColumnMetadata, TableMetadata};
+use super::information_schema::{
+    CheckConstraintMetadata, ColumnMetadata, EnumsMetadata, TableMetadata,
+};
 use super::memory::CanyonMemory;
 #[cfg(feature = "postgres")]
-use crate::migrations::transforms::{to_postgres_alter_syntax, to_postgres_syntax};
+use crate::migrations::transforms::{
+    postgres_enum_type, to_postgres_alter_syntax, to_postgres_syntax,
+};
 #[cfg(feature = "mssql")]
 use crate::migrations::transforms::{to_sqlserver_alter_syntax, to_sqlserver_syntax};
-use canyon_entities::register_types::{CanyonRegisterEntity, CanyonRegisterEntityField};
+use canyon_entities::register_types::{
+    CanyonRegisterEntity, CanyonRegisterEntityField, CanyonRegisterEnum, EnumRepr,
+};
+#[cfg(feature = "postgres")]
+use canyon_entities::CANYON_REGISTER_ENUMS;
 
 /// Responsible of generating the queries to sync the database status with the
 /// Rust source code managed by Canyon, for successfully make the migrations
 #[derive(Debug, Default)]
 pub struct MigrationsProcessor {
+    enum_operations: Vec<Box<dyn DatabaseOperation>>,
     operations: Vec<Box<dyn DatabaseOperation>>,
     set_primary_key_operations: Vec<Box<dyn DatabaseOperation>>,
     drop_primary_key_operations: Vec<Box<dyn DatabaseOperation>>,
@@ -36,10 +45,23 @@ impl MigrationsProcessor {
         canyon_memory: CanyonMemory,
         canyon_entities: Vec<CanyonRegisterEntity<'a>>,
         database_tables: Vec<&'a TableMetadata>,
+        database_enums: &'_ EnumsMetadata,
         datasource: &'_ DatasourceConfig,
     ) {
         // The database type formally represented in Canyon
         let db_type = datasource.get_db_type();
+
+        // The native enum types must exist before the tables that uses them
+        #[cfg(feature = "postgres")]
+        {
+            if db_type == DatabaseType::PostgreSql {
+                self.sync_enum_types(&canyon_entities, &database_tables, database_enums);
+            }
+        }
+
+        // The join tables of the `#[many_to_many]` relations, that aren't entities
+        let join_tables = MigrationsHelper::get_join_tables(&canyon_entities);
+
         // For each entity (table) on the register (Rust structs)
         for canyon_register_entity in canyon_entities {
             let entity_name = canyon_register_entity.entity_db_table_name;
@@ -94,6 +116,13 @@ impl MigrationsProcessor {
                     self.add_constraints(entity_name, canyon_register_field.clone())
                 }
 
+                self.sync_enum_check_constraint(
+                    entity_name,
+                    db_type,
+                    &canyon_register_field,
+                    database_enums,
+                );
+
                 // Case when we need to compare the entity with the database contain
                 #[allow(clippy::unnecessary_unwrap)]
                 if current_table_metadata.is_some() && current_column_metadata.is_some() {
@@ -106,6 +135,13 @@ impl MigrationsProcessor {
             }
         }
 
+        for (table_name, columns) in join_tables {
+            self.create_join_table(table_name, columns, &database_tables);
+        }
+
+        for operation in &self.enum_operations {
+            operation.generate_sql(datasource).await; // This should be moved again to runtime
+        }
         for operation in &self.operations {
             operation.generate_sql(datasource).await; // This should be moved again to runtime
         }
@@ -159,6 +195,45 @@ impl MigrationsProcessor {
         )));
     }
 
+    /// Creates the join table of a `#[many_to_many]` relation if it isn't already on the
+    /// database, with a composite primary key over its columns, where every column references
+    /// the primary key of one of the related entities
+    fn create_join_table(
+        &mut self,
+        table_name: String,
+        columns: Vec<JoinTableColumn>,
+        database_tables: &[&TableMetadata],
+    ) {
+        if MigrationsHelper::entity_already_on_database(&table_name, database_tables) {
+            return;
+        }
+        println!("Processing migrations for join table: {table_name}");
+
+        self.create_table(
+            table_name.clone(),
+            columns.iter().map(|column| column.field.clone()).collect(),
+        );
+        self.set_primary_key_operations.push(Box::new(
+            TableOperation::AddTableCompositePrimaryKey(
+                table_name.clone(),
+                columns
+                    .iter()
+                    .map(|column| column.field.field_name.clone())
+                    .collect(),
+            ),
+        ));
+        for column in columns {
+            let foreign_key_name = format!("{table_name}_{}_fkey", column.field.field_name);
+            self.add_foreign_key(
+                &table_name,
+                foreign_key_name,
+                column.table_to_reference,
+                column.column_to_reference,
+                &column.field,
+            );
+        }
+    }
+
     /// Generates a database agnostic query to change the name of a table
     fn table_rename(&mut self, old_table_name: String, new_table_name: String) {
         self.operations
@@ -540,6 +615,123 @@ impl MigrationsProcessor {
         }
     }
 
+    /// Creates the native `ENUM` types of the enums that derives `CanyonEnum` with the
+    /// `pg_enum` representation, or updates its labels when the variants changed.
+    ///
+    /// `PostgreSQL` can't remove a label from an `ENUM` type, so when some variant is
+    /// removed the type is created again, and the columns that holds it are converted
+    /// to the new one. That conversion fails if any row still holds a removed label
+    #[cfg(feature = "postgres")]
+    fn sync_enum_types(
+        &mut self,
+        canyon_entities: &[CanyonRegisterEntity<'_>],
+        database_tables: &[&TableMetadata],
+        database_enums: &EnumsMetadata,
+    ) {
+        let pg_enums: Vec<CanyonRegisterEnum> = CANYON_REGISTER_ENUMS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|canyon_enum| canyon_enum.repr == EnumRepr::PgEnum)
+            .cloned()
+            .collect();
+
+        for canyon_enum in pg_enums {
+            let current_type = database_enums
+                .enum_types
+                .iter()
+                .find(|enum_type| enum_type.type_name == canyon_enum.db_type_name);
+
+            let Some(current_type) = current_type else {
+                self.enum_operations
+                    .push(Box::new(EnumOperation::CreateEnumType(canyon_enum)));
+                continue;
+            };
+
+            let has_removed_labels = current_type
+                .labels
+                .iter()
+                .any(|label| !canyon_enum.variants.contains(label));
+            if has_removed_labels {
+                let columns = MigrationsHelper::get_enum_columns(
+                    &canyon_enum,
+                    canyon_entities,
+                    database_tables,
+                );
+                self.enum_operations
+                    .push(Box::new(EnumOperation::RecreateEnumType(
+                        canyon_enum,
+                        columns,
+                    )));
+            } else {
+                for variant in canyon_enum
+                    .variants
+                    .iter()
+                    .filter(|variant| !current_type.labels.contains(variant))
+                {
+                    self.enum_operations
+                        .push(Box::new(EnumOperation::AddEnumTypeValue(
+                            canyon_enum.db_type_name.clone(),
+                            variant.clone(),
+                        )));
+                }
+            }
+        }
+    }
+
+    /// Adds, replaces or drops the `CHECK` constraint that restricts the values of the
+    /// column of a field, when it's an enum that derives `CanyonEnum` that isn't stored
+    /// as a native `ENUM` type, by comparing its variants with the ones on the database
+    fn sync_enum_check_constraint(
+        &mut self,
+        entity_name: &str,
+        db_type: DatabaseType,
+        canyon_register_field: &CanyonRegisterEntityField,
+        database_enums: &EnumsMetadata,
+    ) {
+        let constraint_name = CanyonRegisterEnum::check_constraint_name(
+            entity_name,
+            &canyon_register_field.field_name,
+        );
+        let current_constraint = database_enums.check_constraints.iter().find(|constraint| {
+            constraint.table_name == entity_name && constraint.constraint_name == constraint_name
+        });
+        let canyon_enum = canyon_register_field
+            .canyon_enum
+            .clone()
+            .filter(|canyon_enum| MigrationsHelper::is_check_constrained(canyon_enum, db_type));
+
+        let drop_constraint =
+            EnumOperation::DropCheckConstraint(entity_name.to_string(), constraint_name.clone());
+        match (canyon_enum, current_constraint) {
+            (Some(canyon_enum), current_constraint) => {
+                if let Some(current_constraint) = current_constraint {
+                    let mut variants = canyon_enum.variants.clone();
+                    variants.sort();
+                    if MigrationsHelper::get_check_constraint_values(
+                        current_constraint,
+                        &canyon_register_field.field_name,
+                        canyon_enum.repr,
+                    ) == variants
+                    {
+                        return;
+                    }
+                    self.enum_operations.push(Box::new(drop_constraint));
+                }
+
+                self.constraints_operations
+                    .push(Box::new(EnumOperation::AddCheckConstraint(
+                        entity_name.to_string(),
+                        constraint_name,
+                        canyon_register_field.field_name.clone(),
+                        canyon_enum,
+                    )));
+            }
+            (None, Some(_)) => self.enum_operations.push(Box::new(drop_constraint)),
+            (None, None) => (),
+        }
+    }
+
     fn drop_primary_key(&mut self, entity_name: &str, primary_key_name: String) {
         self.drop_primary_key_operations
             .push(Box::new(TableOperation::DeleteTablePrimaryKey(
@@ -596,7 +788,76 @@ impl MigrationsProcessor {
 /// Contains helper methods to parse and process the external and internal input data
 /// for the migrations
 struct MigrationsHelper;
+/// A column of the join table of a `#[many_to_many]` relation, that references
+/// the primary key of one of the related entities
+#[derive(Debug, Clone)]
+struct JoinTableColumn {
+    /// The column, typed as the primary key that it references
+    field: CanyonRegisterEntityField,
+    table_to_reference: String,
+    column_to_reference: String,
+}
+
 impl MigrationsHelper {
+    /// Returns the join tables of the `#[many_to_many]` relations declared on the entities,
+    /// along with their columns. The join tables declared on both sides of a relation are
+    /// only returned once, and the ones whose related entity (or the primary key of some
+    /// of the entities) isn't found are skipped
+    fn get_join_tables(
+        canyon_entities: &[CanyonRegisterEntity<'_>],
+    ) -> Vec<(String, Vec<JoinTableColumn>)> {
+        let referenced_column = |entity: &CanyonRegisterEntity<'_>, column_name: &str| {
+            let primary_key = entity.entity_fields.iter().find(|field| {
+                field
+                    .annotations
+                    .iter()
+                    .any(|anno| anno.starts_with("Annotation: PrimaryKey"))
+            })?;
+            Some(JoinTableColumn {
+                field: CanyonRegisterEntityField {
+                    field_name: column_name.to_owned(),
+                    annotations: Vec::new(),
+                    ..primary_key.clone()
+                },
+                table_to_reference: entity.entity_db_table_name.to_owned(),
+                column_to_reference: primary_key.field_name.clone(),
+            })
+        };
+
+        let mut join_tables: Vec<(String, Vec<JoinTableColumn>)> = Vec::new();
+        for canyon_register_entity in canyon_entities {
+            for join_table in &canyon_register_entity.join_tables {
+                if join_tables
+                    .iter()
+                    .any(|(table_name, _)| *table_name == join_table.table_name)
+                {
+                    continue;
+                }
+                let related_entity = canyon_entities
+                    .iter()
+                    .find(|entity| entity.entity_name == join_table.related_entity_name);
+                let columns = related_entity.and_then(|related_entity| {
+                    Some(vec![
+                        referenced_column(canyon_register_entity, &join_table.column)?,
+                        referenced_column(related_entity, &join_table.related_column)?,
+                    ])
+                });
+
+                match columns {
+                    Some(columns) => join_tables.push((join_table.table_name.clone(), columns)),
+                    None => println!(
+                        "Skipping the join table: {}, the entities `{}` and `{}` must be \
+                        registered and have a primary key",
+                        join_table.table_name,
+                        canyon_register_entity.entity_name,
+                        join_table.related_entity_name
+                    ),
+                }
+            }
+        }
+        join_tables
+    }
+
     /// Checks if a tracked Canyon entity is already present in the database
     fn entity_already_on_database<'a>(
         entity_name: &'a str,
@@ -666,6 +927,10 @@ impl MigrationsHelper {
         #[cfg(feature = "postgres")]
         {
             if db_type == DatabaseType::PostgreSql {
+                // The native enum types are reported as user defined ones
+                if postgres_enum_type(canyon_register_entity_field).is_some() {
+                    return current_column_metadata.datatype == "USER-DEFINED";
+                }
                 return to_postgres_alter_syntax(canyon_register_entity_field).to_lowercase()
                     == current_column_metadata.datatype;
             }
@@ -682,6 +947,85 @@ impl MigrationsHelper {
         false
     }
 
+    /// Returns true if the values of the enum are restricted with a `CHECK` constraint
+    /// on the given database, instead of with a native `ENUM` type
+    fn is_check_constrained(canyon_enum: &CanyonRegisterEnum, _db_type: DatabaseType) -> bool {
+        match canyon_enum.repr {
+            EnumRepr::Text | EnumRepr::Int => true,
+            #[cfg(feature = "postgres")]
+            EnumRepr::PgEnum => _db_type != DatabaseType::PostgreSql,
+            #[cfg(not(feature = "postgres"))]
+            EnumRepr::PgEnum => true,
+        }
+    }
+
+    /// Extracts the sorted values allowed by a `CHECK` constraint generated for the column
+    /// of an enum, from its definition, as it's reported by the database. For example,
+    /// `CHECK ((status = ANY (ARRAY['active'::text, 'closed'::text])))` on `PostgreSQL`,
+    /// `([status]='closed' OR [status]='active')` on `SQL Server`, or
+    /// ``(`status` in (_utf8mb4'closed',_utf8mb4'active'))`` on `MySQL`
+    fn get_check_constraint_values(
+        check_constraint: &CheckConstraintMetadata,
+        column_name: &str,
+        repr: EnumRepr,
+    ) -> Vec<String> {
+        let mut values: Vec<String> = match repr {
+            EnumRepr::Int => Regex::new(r"-?\d+")
+                .unwrap()
+                .find_iter(&check_constraint.definition.replace(column_name, ""))
+                .map(|value| value.as_str().to_string())
+                .collect(),
+            EnumRepr::Text | EnumRepr::PgEnum => Regex::new(r"'((?:[^']|'')*)'")
+                .unwrap()
+                .captures_iter(&check_constraint.definition)
+                .map(|label| label[1].replace("''", "'"))
+                .collect(),
+        };
+        values.sort();
+        values.dedup();
+        values
+    }
+
+    /// Returns the table and the column of every field that holds the given enum, and
+    /// it's already stored as a native `ENUM` type on the database
+    #[cfg(feature = "postgres")]
+    fn get_enum_columns(
+        canyon_enum: &CanyonRegisterEnum,
+        canyon_entities: &[CanyonRegisterEntity<'_>],
+        database_tables: &[&TableMetadata],
+    ) -> Vec<(String, String)> {
+        canyon_entities
+            .iter()
+            .flat_map(|entity| {
+                entity
+                    .entity_fields
+                    .iter()
+                    .filter(|field| {
+                        field
+                            .canyon_enum
+                            .as_ref()
+                            .is_some_and(|e| e.registry_key == canyon_enum.registry_key)
+                    })
+                    .filter(|field| {
+                        database_tables
+                            .iter()
+                            .filter(|table| table.table_name == entity.entity_db_table_name)
+                            .flat_map(|table| table.columns.iter())
+                            .any(|column| {
+                                column.column_name == field.field_name
+                                    && column.datatype == "USER-DEFINED"
+                            })
+                    })
+                    .map(|field| {
+                        (
+                            entity.entity_db_table_name.to_string(),
+                            field.field_name.clone(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
     fn extract_foreign_key_annotation(field_annotations: &[String]) -> (String, String) {
         let opt_fk_annotation = field_annotations
             .iter()
@@ -720,6 +1064,9 @@ impl MigrationsHelper {
 mod migrations_helper_tests {
     use super::*;
     use crate::constants;
+    use canyon_entities::register_types::CanyonRegisterJoinTable;
+    #[cfg(feature = "postgres")]
+    use canyon_entities::register_types::CanyonRegisterType;
 
     const MOCKED_ENTITY_NAME: &str = "league";
 
@@ -744,6 +1091,144 @@ mod migrations_helper_tests {
         );
         assert!(!mocked_league_entity_on_database)
     }
+
+    #[test]
+    fn test_get_join_tables() {
+        let primary_key = CanyonRegisterEntityField {
+            field_name: "id".to_owned(),
+            field_type: "i32".to_owned(),
+            annotations: vec!["Annotation: PrimaryKey, Autoincremental: true".to_owned()],
+            ..Default::default()
+        };
+        let join_table = CanyonRegisterJoinTable {
+            table_name: "league_tag".to_owned(),
+            column: "league".to_owned(),
+            related_entity_name: "Tag".to_owned(),
+            related_column: "tag".to_owned(),
+        };
+        let league = CanyonRegisterEntity {
+            entity_name: "League",
+            entity_db_table_name: MOCKED_ENTITY_NAME,
+            entity_fields: vec![primary_key.clone()],
+            join_tables: vec![join_table.clone()],
+            ..Default::default()
+        };
+        let tag = CanyonRegisterEntity {
+            entity_name: "Tag",
+            entity_db_table_name: "tag",
+            entity_fields: vec![primary_key],
+            ..Default::default()
+        };
+
+        // The join table is returned once, although both sides declares the relation
+        let tag_with_relation = CanyonRegisterEntity {
+            join_tables: vec![CanyonRegisterJoinTable {
+                column: "tag".to_owned(),
+                related_entity_name: "League".to_owned(),
+                related_column: "league".to_owned(),
+                ..join_table
+            }],
+            ..tag.clone()
+        };
+        let join_tables = MigrationsHelper::get_join_tables(&[league.clone(), tag_with_relation]);
+        assert_eq!(join_tables.len(), 1);
+
+        let (table_name, columns) = &join_tables[0];
+        assert_eq!(table_name, "league_tag");
+        let columns = columns
+            .iter()
+            .map(|column| {
+                (
+                    column.field.field_name.as_str(),
+                    column.field.field_type.as_str(),
+                    column.table_to_reference.as_str(),
+                    column.column_to_reference.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec![
+                ("league", "i32", "league", "id"),
+                ("tag", "i32", "tag", "id")
+            ]
+        );
+
+        // Without the related entity, there's nothing to reference
+        assert!(MigrationsHelper::get_join_tables(&[league]).is_empty());
+    }
+
+    #[test]
+    fn test_get_check_constraint_values() {
+        let check_constraint = |definition: &str| CheckConstraintMetadata {
+            table_name: MOCKED_ENTITY_NAME.to_owned(),
+            constraint_name: "league_status_enum_check".to_owned(),
+            definition: definition.to_owned(),
+        };
+
+        let postgres_labels = MigrationsHelper::get_check_constraint_values(
+            &check_constraint(
+                "CHECK ((status = ANY (ARRAY['closed'::text, 'in_progress'::text, 'it''s'::text])))",
+            ),
+            "status",
+            EnumRepr::Text,
+        );
+        assert_eq!(postgres_labels, vec!["closed", "in_progress", "it's"]);
+
+        let sqlserver_labels = MigrationsHelper::get_check_constraint_values(
+            &check_constraint("([status]='closed' OR [status]='active')"),
+            "status",
+            EnumRepr::PgEnum,
+        );
+        assert_eq!(sqlserver_labels, vec!["active", "closed"]);
+
+        let discriminants = MigrationsHelper::get_check_constraint_values(
+            &check_constraint("([status2]=(10) OR [status2]=(-1) OR [status2]=(2))"),
+            "status2",
+            EnumRepr::Int,
+        );
+        assert_eq!(discriminants, vec!["-1", "10", "2"]);
+
+        let mysql_labels = MigrationsHelper::get_check_constraint_values(
+            &check_constraint("(`status` in (_utf8mb4'closed',_utf8mb4'active'))"),
+            "status",
+            EnumRepr::Text,
+        );
+        assert_eq!(mysql_labels, vec!["active", "closed"]);
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_is_same_datatype_of_user_defined_types() {
+        let score = CanyonRegisterEntityField {
+            field_name: "score".to_owned(),
+            field_type: "Option < ProcessorLeagueScore >".to_owned(),
+            user_type: Some(CanyonRegisterType {
+                type_name: "ProcessorLeagueScore".to_owned(),
+                inner_type: "i64".to_owned(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let column = |datatype: &str| ColumnMetadata {
+            column_name: "score".to_owned(),
+            datatype: datatype.to_owned(),
+            is_nullable: true,
+            ..Default::default()
+        };
+
+        // The column of a newtype is only altered when the wrapped type changes
+        assert!(MigrationsHelper::is_same_datatype(
+            DatabaseType::PostgreSql,
+            &score,
+            &column("bigint")
+        ));
+        assert!(!MigrationsHelper::is_same_datatype(
+            DatabaseType::PostgreSql,
+            &score,
+            &column("integer")
+        ));
+    }
 }
 
 /// Trait that enables implementors to generate the migration queries
@@ -767,6 +1252,8 @@ enum TableOperation {
     AddTablePrimaryKey(String, CanyonRegisterEntityField),
     // table_name, constraint_name
     DeleteTablePrimaryKey(String, String),
+    // table_name, column_names
+    AddTableCompositePrimaryKey(String, Vec<String>),
 }
 
 impl<T: Debug> Transaction<T> for TableOperation {}
@@ -886,6 +1373,38 @@ impl DatabaseOperation for TableOperation {
                 }
             }
 
+            TableOperation::AddTableCompositePrimaryKey(table_name, column_names) => {
+                match db_type {
+                    #[cfg(feature = "postgres")] DatabaseType::PostgreSql =>
+                        format!(
+                            "ALTER TABLE \"{table_name}\" ADD PRIMARY KEY ({});",
+                            column_names
+                                .iter()
+                                .map(|column_name| format!("\"{column_name}\""))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                    #[cfg(feature = "mssql")] DatabaseType::SqlServer =>
+                        format!(
+                            "ALTER TABLE [{table_name}] ADD CONSTRAINT {table_name}_pkey PRIMARY KEY ({});",
+                            column_names
+                                .iter()
+                                .map(|column_name| format!("[{column_name}]"))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                    #[cfg(feature = "mysql")] DatabaseType::MySQL =>
+                        format!(
+                            "ALTER TABLE `{table_name}` ADD PRIMARY KEY ({});",
+                            column_names
+                                .iter()
+                                .map(|column_name| format!("`{column_name}`"))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                }
+            }
+
             TableOperation::DeleteTablePrimaryKey(table_name, primary_key_name) => {
                 match db_type {
                     #[cfg(feature = "postgres")] DatabaseType::PostgreSql =>
@@ -956,10 +1475,18 @@ impl DatabaseOperation for ColumnOperation {
             ColumnOperation::AlterColumnType(_table_name, _entity_field) =>
                 match db_type {
                     #[cfg(feature = "postgres")] DatabaseType::PostgreSql =>
-                        format!(
-                            "ALTER TABLE \"{_table_name}\" ALTER COLUMN \"{}\" TYPE {};",
-                            _entity_field.field_name, to_postgres_alter_syntax(_entity_field)
-                        ),
+                        match postgres_enum_type(_entity_field) {
+                            // There's no implicit cast from the other types into an enum type
+                            Some(enum_type) => format!(
+                                "ALTER TABLE \"{_table_name}\" ALTER COLUMN \"{0}\" TYPE {enum_type} \
+                                USING \"{0}\"::text::{enum_type};",
+                                _entity_field.field_name
+                            ),
+                            None => format!(
+                                "ALTER TABLE \"{_table_name}\" ALTER COLUMN \"{}\" TYPE {};",
+                                _entity_field.field_name, to_postgres_alter_syntax(_entity_field)
+                            ),
+                        },
                     #[cfg(feature = "mssql")] DatabaseType::SqlServer =>
                         todo!("[MS-SQL -> Operation still won't supported by Canyon for Sql Server]"),
                     #[cfg(feature = "mysql")] DatabaseType::MySQL => todo!()
@@ -1045,3 +1572,105 @@ impl DatabaseOperation for SequenceOperation {
         save_migrations_query_to_execute(stmt, &datasource.name);
     }
 }
+
+/// Helper for operations involving the enums that derives `CanyonEnum`
+#[derive(Debug)]
+enum EnumOperation {
+    #[cfg(feature = "postgres")]
+    CreateEnumType(CanyonRegisterEnum),
+    // type_name, label
+    #[cfg(feature = "postgres")]
+    AddEnumTypeValue(String, String),
+    // the enum, and the table and column of the fields that holds it
+    #[cfg(feature = "postgres")]
+    RecreateEnumType(CanyonRegisterEnum, Vec<(String, String)>),
+    // table_name, constraint_name, column_name, the enum
+    AddCheckConstraint(String, String, String, CanyonRegisterEnum),
+    // table_name, constraint_name
+    DropCheckConstraint(String, String),
+}
+impl Transaction<Self> for EnumOperation {}
+
+#[async_trait]
+impl DatabaseOperation for EnumOperation {
+    async fn generate_sql(&self, datasource: &DatasourceConfig) {
+        let db_type = datasource.get_db_type();
+
+        let stmts: Vec<String> = match self {
+            #[cfg(feature = "postgres")]
+            EnumOperation::CreateEnumType(canyon_enum) => vec![format!(
+                "CREATE TYPE {} AS ENUM ({});",
+                canyon_enum.db_type_name,
+                canyon_enum.sql_values()
+            )],
+            #[cfg(feature = "postgres")]
+            EnumOperation::AddEnumTypeValue(type_name, label) => vec![format!(
+                "ALTER TYPE {type_name} ADD VALUE IF NOT EXISTS '{}';",
+                label.replace('\'', "''")
+            )],
+            #[cfg(feature = "postgres")]
+            EnumOperation::RecreateEnumType(canyon_enum, columns) => {
+                let type_name = &canyon_enum.db_type_name;
+                let mut stmts = vec![
+                    format!("ALTER TYPE {type_name} RENAME TO {type_name}_old;"),
+                    format!(
+                        "CREATE TYPE {type_name} AS ENUM ({});",
+                        canyon_enum.sql_values()
+                    ),
+                ];
+                stmts.extend(columns.iter().map(|(table_name, column_name)| {
+                    format!(
+                        "ALTER TABLE \"{table_name}\" ALTER COLUMN \"{column_name}\" TYPE {type_name} \
+                        USING \"{column_name}\"::text::{type_name};"
+                    )
+                }));
+                stmts.push(format!("DROP TYPE {type_name}_old;"));
+                stmts
+            }
+            EnumOperation::AddCheckConstraint(
+                table_name,
+                constraint_name,
+                column_name,
+                canyon_enum,
+            ) => match db_type {
+                #[cfg(feature = "postgres")]
+                DatabaseType::PostgreSql => vec![format!(
+                    "ALTER TABLE \"{table_name}\" ADD CONSTRAINT \"{constraint_name}\" \
+                        CHECK (\"{column_name}\" IN ({}));",
+                    canyon_enum.sql_values()
+                )],
+                #[cfg(feature = "mssql")]
+                DatabaseType::SqlServer => vec![format!(
+                    "ALTER TABLE {table_name} ADD CONSTRAINT {constraint_name} \
+                        CHECK ({column_name} IN ({}));",
+                    canyon_enum.sql_values()
+                )],
+                #[cfg(feature = "mysql")]
+                DatabaseType::MySQL => vec![format!(
+                    "ALTER TABLE `{table_name}` ADD CONSTRAINT `{constraint_name}` \
+                        CHECK (`{column_name}` IN ({}));",
+                    canyon_enum.sql_values()
+                )],
+            },
+            EnumOperation::DropCheckConstraint(table_name, constraint_name) => match db_type {
+                #[cfg(feature = "postgres")]
+                DatabaseType::PostgreSql => vec![format!(
+                    "ALTER TABLE \"{table_name}\" DROP CONSTRAINT \"{constraint_name}\";"
+                )],
+                #[cfg(feature = "mssql")]
+                DatabaseType::SqlServer => vec![format!(
+                    "ALTER TABLE {table_name} DROP CONSTRAINT {constraint_name};"
+                )],
+                #[cfg(feature = "mysql")]
+                DatabaseType::MySQL => vec![format!(
+                    "ALTER TABLE `{table_name}` DROP CHECK `{constraint_name}`;"
+                )],
+            },
+        };
+
+        // Every statement is executed on its own
+        for stmt in stmts {
+            save_migrations_query_to_execute(stmt, &datasource.name);
+        }
+    }
+}
@@ -5,14 +5,61 @@ use crate::constants::sqlserver_type;
 use crate::constants::{regex_patterns, rust_type};
 
 use canyon_entities::register_types::CanyonRegisterEntityField;
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+use canyon_entities::register_types::ColumnDefault;
+#[cfg(feature = "postgres")]
+use canyon_entities::register_types::EnumRepr;
 use regex::Regex;
 
+/// Returns the Rust type of the field without whitespaces, and without the path of
+/// the external crates whose types are supported, so `uuid::Uuid` is matched as `Uuid`.
+/// The JSON values must be declared with their full path, `serde_json::Value`.
+///
+/// The user defined types that derives `CanyonType` are replaced by the type that
+/// they wrap, unless they declare their own SQL type, that's returned along with it
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+fn clean_rust_type(field: &CanyonRegisterEntityField) -> (String, Option<String>) {
+    let (rust_type, sql_type) = field.resolved_type();
+    let rust_type = rust_type::EXTERNAL_CRATE_PATHS
+        .iter()
+        .fold(rust_type, |rust_type, path| rust_type.replace(path, ""));
+
+    (rust_type, sql_type)
+}
+
+/// Declares the column with the SQL type declared by the user defined type of the field
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+fn declared_sql_type_syntax(field: &CanyonRegisterEntityField, sql_type: String) -> String {
+    if field.is_nullable() {
+        sql_type
+    } else {
+        format!("{sql_type} NOT NULL")
+    }
+}
+
+/// Returns the name of the native `ENUM` type of the column, when the field is
+/// an enum that derives `CanyonEnum` with the `pg_enum` representation
+#[cfg(feature = "postgres")]
+pub fn postgres_enum_type(field: &CanyonRegisterEntityField) -> Option<String> {
+    field
+        .canyon_enum
+        .as_ref()
+        .filter(|canyon_enum| canyon_enum.repr == EnumRepr::PgEnum)
+        .map(|canyon_enum| canyon_enum.db_type_name.clone())
+}
+
 /// Return the postgres datatype and parameters to create a column for a given rust type
 #[cfg(feature = "postgres")]
 pub fn to_postgres_syntax(field: &CanyonRegisterEntityField) -> String {
-    let rust_type_clean = field.field_type.replace(' ', "");
+    if let Some(enum_type) = postgres_enum_type(field) {
+        return declared_sql_type_syntax(field, enum_type);
+    }
+    let (rust_type_clean, declared_sql_type) = clean_rust_type(field);
+    if let Some(sql_type) = declared_sql_type {
+        return declared_sql_type_syntax(field, sql_type);
+    }
 
-    match rust_type_clean.as_str() {
+    let syntax = match rust_type_clean.as_str() {
         rust_type::I8 | rust_type::U8 => {
             String::from(&format!("{} NOT NULL", postgresql_type::INTEGER))
         }
@@ -49,17 +96,42 @@ pub fn to_postgres_syntax(field: &CanyonRegisterEntityField) -> String {
             String::from(&format!("{} NOT NULL", postgresql_type::DATETIME))
         }
         rust_type::OPT_NAIVE_DATE_TIME => String::from(postgresql_type::DATETIME),
+
+        rust_type::DATE_TIME_UTC => {
+            String::from(&format!("{} NOT NULL", postgresql_type::DATETIME_TZ))
+        }
+        rust_type::OPT_DATE_TIME_UTC => String::from(postgresql_type::DATETIME_TZ),
+
+        rust_type::BYTES => String::from(&format!("{} NOT NULL", postgresql_type::BYTEA)),
+        rust_type::OPT_BYTES => String::from(postgresql_type::BYTEA),
+
+        rust_type::UUID => String::from(&format!("{} NOT NULL", postgresql_type::UUID)),
+        rust_type::OPT_UUID => String::from(postgresql_type::UUID),
+
+        rust_type::DECIMAL => String::from(&format!("{} NOT NULL", postgresql_type::NUMERIC)),
+        rust_type::OPT_DECIMAL => String::from(postgresql_type::NUMERIC),
+
+        rust_type::JSON => String::from(&format!("{} NOT NULL", postgresql_type::JSONB)),
+        rust_type::OPT_JSON => String::from(postgresql_type::JSONB),
         &_ => todo!("Not supported datatype for this migrations version"),
-    }
+    };
+
+    with_column_default(field, syntax, |rust_type| match rust_type {
+        rust_type::DATE_TIME_UTC => "CURRENT_TIMESTAMP",
+        _ => "(CURRENT_TIMESTAMP AT TIME ZONE 'UTC')",
+    })
 }
 
 /// Return the postgres datatype and parameters to create a column for a given rust type
 /// for Microsoft SQL Server
 #[cfg(feature = "mssql")]
 pub fn to_sqlserver_syntax(field: &CanyonRegisterEntityField) -> String {
-    let rust_type_clean = field.field_type.replace(' ', "");
+    let (rust_type_clean, declared_sql_type) = clean_rust_type(field);
+    if let Some(sql_type) = declared_sql_type {
+        return declared_sql_type_syntax(field, sql_type);
+    }
 
-    match rust_type_clean.as_str() {
+    let syntax = match rust_type_clean.as_str() {
         rust_type::I8 | rust_type::U8 => String::from(&format!("{} NOT NULL", sqlserver_type::INT)),
         rust_type::OPT_I8 | rust_type::OPT_U8 => String::from(sqlserver_type::INT),
 
@@ -96,13 +168,61 @@ pub fn to_sqlserver_syntax(field: &CanyonRegisterEntityField) -> String {
             String::from(&format!("{} NOT NULL", sqlserver_type::DATETIME))
         }
         rust_type::OPT_NAIVE_DATE_TIME => String::from(sqlserver_type::DATETIME),
+
+        rust_type::DATE_TIME_UTC => {
+            String::from(&format!("{} NOT NULL", sqlserver_type::DATETIME_TZ))
+        }
+        rust_type::OPT_DATE_TIME_UTC => String::from(sqlserver_type::DATETIME_TZ),
+
+        rust_type::BYTES => String::from(&format!("{} NOT NULL", sqlserver_type::VARBINARY)),
+        rust_type::OPT_BYTES => String::from(sqlserver_type::VARBINARY),
+
+        rust_type::UUID => String::from(&format!("{} NOT NULL", sqlserver_type::UNIQUEIDENTIFIER)),
+        rust_type::OPT_UUID => String::from(sqlserver_type::UNIQUEIDENTIFIER),
+
+        rust_type::DECIMAL => String::from(&format!("{} NOT NULL", sqlserver_type::DECIMAL)),
+        rust_type::OPT_DECIMAL => String::from(sqlserver_type::DECIMAL),
+
+        rust_type::JSON => String::from(&format!("{} NOT NULL", sqlserver_type::JSON)),
+        rust_type::OPT_JSON => String::from(sqlserver_type::JSON),
         &_ => todo!("Not supported datatype for this migrations version"),
+    };
+
+    with_column_default(field, syntax, |rust_type| match rust_type {
+        rust_type::DATE_TIME_UTC => "SYSDATETIMEOFFSET()",
+        _ => "SYSUTCDATETIME()",
+    })
+}
+
+/// Appends the `DEFAULT` clause to the column definition, if the field requires one.
+///
+/// * `current_timestamp` - Returns the expression that evaluates to the current `UTC`
+///   time in the syntax of the database, for the Rust type of the field
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+fn with_column_default(
+    field: &CanyonRegisterEntityField,
+    syntax: String,
+    current_timestamp: impl Fn(&str) -> &'static str,
+) -> String {
+    match field.column_default() {
+        Some(ColumnDefault::Value(default)) => format!("{syntax} DEFAULT {default}"),
+        Some(ColumnDefault::CurrentTimestamp) => format!(
+            "{syntax} DEFAULT {}",
+            current_timestamp(&field.field_type.replace(' ', ""))
+        ),
+        None => syntax,
     }
 }
 
 #[cfg(feature = "postgres")]
 pub fn to_postgres_alter_syntax(field: &CanyonRegisterEntityField) -> String {
-    let mut rust_type_clean = field.field_type.replace(' ', "");
+    if let Some(enum_type) = postgres_enum_type(field) {
+        return enum_type;
+    }
+    let (mut rust_type_clean, declared_sql_type) = clean_rust_type(field);
+    if let Some(sql_type) = declared_sql_type {
+        return sql_type;
+    }
     let rs_type_is_optional = field.field_type.to_uppercase().starts_with("OPTION");
 
     if rs_type_is_optional {
@@ -135,13 +255,23 @@ pub fn to_postgres_alter_syntax(field: &CanyonRegisterEntityField) -> String {
         rust_type::NAIVE_DATE_TIME | rust_type::OPT_NAIVE_DATE_TIME => {
             String::from(postgresql_type::DATETIME)
         }
+        rust_type::DATE_TIME_UTC | rust_type::OPT_DATE_TIME_UTC => {
+            String::from(postgresql_type::DATETIME_TZ)
+        }
+        rust_type::BYTES | rust_type::OPT_BYTES => String::from(postgresql_type::BYTEA),
+        rust_type::UUID | rust_type::OPT_UUID => String::from(postgresql_type::UUID),
+        rust_type::DECIMAL | rust_type::OPT_DECIMAL => String::from(postgresql_type::NUMERIC),
+        rust_type::JSON | rust_type::OPT_JSON => String::from(postgresql_type::JSONB),
         &_ => todo!("Not supported datatype for this migrations version"),
     }
 }
 
 #[cfg(feature = "mssql")]
 pub fn to_sqlserver_alter_syntax(field: &CanyonRegisterEntityField) -> String {
-    let mut rust_type_clean = field.field_type.replace(' ', "");
+    let (mut rust_type_clean, declared_sql_type) = clean_rust_type(field);
+    if let Some(sql_type) = declared_sql_type {
+        return sql_type;
+    }
     let rs_type_is_optional = field.field_type.to_uppercase().starts_with("OPTION");
 
     if rs_type_is_optional {
@@ -174,6 +304,131 @@ pub fn to_sqlserver_alter_syntax(field: &CanyonRegisterEntityField) -> String {
         rust_type::NAIVE_DATE_TIME | rust_type::OPT_NAIVE_DATE_TIME => {
             String::from(sqlserver_type::DATETIME)
         }
+        rust_type::DATE_TIME_UTC | rust_type::OPT_DATE_TIME_UTC => {
+            String::from(sqlserver_type::DATETIME_TZ)
+        }
+        rust_type::BYTES | rust_type::OPT_BYTES => String::from(sqlserver_type::VARBINARY),
+        rust_type::UUID | rust_type::OPT_UUID => String::from(sqlserver_type::UNIQUEIDENTIFIER),
+        rust_type::DECIMAL | rust_type::OPT_DECIMAL => String::from(sqlserver_type::DECIMAL),
+        rust_type::JSON | rust_type::OPT_JSON => String::from(sqlserver_type::JSON),
         &_ => todo!("Not supported datatype for this migrations version"),
     }
 }
+
+#[cfg(test)]
+mod transforms_tests {
+    use super::*;
+    #[cfg(feature = "postgres")]
+    use canyon_entities::register_types::CanyonRegisterEnum;
+    use canyon_entities::register_types::CanyonRegisterType;
+
+    fn field(field_type: &str) -> CanyonRegisterEntityField {
+        CanyonRegisterEntityField {
+            field_name: "some_field".to_owned(),
+            field_type: field_type.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// A field whose type was found on the register as the given user defined type
+    fn user_type_field(
+        field_type: &str,
+        inner_type: &str,
+        sql_type: Option<&str>,
+    ) -> CanyonRegisterEntityField {
+        CanyonRegisterEntityField {
+            user_type: Some(CanyonRegisterType {
+                type_name: field_type.to_owned(),
+                inner_type: inner_type.to_owned(),
+                sql_type: sql_type.map(str::to_owned),
+                ..Default::default()
+            }),
+            ..field(field_type)
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_external_types_to_postgres_syntax() {
+        assert_eq!(to_postgres_syntax(&field("uuid :: Uuid")), "uuid NOT NULL");
+        assert_eq!(to_postgres_syntax(&field("Option < Decimal >")), "numeric");
+        assert_eq!(
+            to_postgres_syntax(&field("serde_json :: Value")),
+            "jsonb NOT NULL"
+        );
+        assert_eq!(to_postgres_syntax(&field("Option < Vec < u8 > >")), "bytea");
+        assert_eq!(
+            to_postgres_alter_syntax(&field("Option < serde_json :: Value >")),
+            "jsonb"
+        );
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn test_external_types_to_sqlserver_syntax() {
+        assert_eq!(
+            to_sqlserver_syntax(&field("Uuid")),
+            "UNIQUEIDENTIFIER NOT NULL"
+        );
+        assert_eq!(
+            to_sqlserver_syntax(&field("rust_decimal :: Decimal")),
+            "DECIMAL(28, 10) NOT NULL"
+        );
+        assert_eq!(
+            to_sqlserver_syntax(&field("Option < serde_json :: Value >")),
+            "nvarchar(max)"
+        );
+        assert_eq!(
+            to_sqlserver_alter_syntax(&field("Option < Vec < u8 > >")),
+            "VARBINARY(MAX)"
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_user_defined_types_to_postgres_syntax() {
+        let user_id = |field_type: &str| user_type_field(field_type, "i64", None);
+        let email = |field_type: &str| user_type_field(field_type, "String", Some("citext"));
+
+        assert_eq!(to_postgres_syntax(&user_id("UserId")), "bigint NOT NULL");
+        assert_eq!(
+            to_postgres_syntax(&user_id("Option < crate :: UserId >")),
+            "bigint"
+        );
+        assert_eq!(to_postgres_syntax(&email("Email")), "citext NOT NULL");
+        assert_eq!(
+            to_postgres_alter_syntax(&email("Option < Email >")),
+            "citext"
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_enums_to_postgres_syntax() {
+        let status = |field_type: &str| CanyonRegisterEntityField {
+            canyon_enum: Some(CanyonRegisterEnum {
+                enum_name: "Status".to_owned(),
+                db_type_name: "status".to_owned(),
+                repr: EnumRepr::PgEnum,
+                variants: vec!["active".to_owned(), "closed".to_owned()],
+                ..Default::default()
+            }),
+            ..user_type_field(field_type, "String", None)
+        };
+
+        assert_eq!(to_postgres_syntax(&status("Status")), "status NOT NULL");
+        assert_eq!(
+            to_postgres_alter_syntax(&status("Option < Status >")),
+            "status"
+        );
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn test_enums_to_sqlserver_syntax() {
+        assert_eq!(
+            to_sqlserver_syntax(&user_type_field("Priority", "i32", None)),
+            "INT NOT NULL"
+        );
+    }
+}
@@ -38,6 +38,87 @@ CREATE TABLE public.team (
 	home_league			INTEGER REFERENCES league(id)
 );
 
+CREATE TABLE public.versioned_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	version				INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE public.audited_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	created_at			TIMESTAMP NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'UTC'),
+	updated_at			TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE public.soft_deleted_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	deleted_at			TIMESTAMP WITH TIME ZONE
+);
+
+CREATE TABLE public.soft_deleted_tournament (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	slug				TEXT NOT NULL,
+	league				INTEGER NOT NULL REFERENCES soft_deleted_league(id),
+	deleted_at			TIMESTAMP
+);
+
+CREATE TABLE public.hooked_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	slug				TEXT NOT NULL,
+	name				TEXT NOT NULL
+);
+
+CREATE TABLE public.defaulted_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	region				TEXT NOT NULL DEFAULT 'EUROPE',
+	score				INTEGER NOT NULL DEFAULT 100
+);
+
+CREATE TABLE public.sponsor (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	billing_street		TEXT NOT NULL,
+	billing_city		TEXT NOT NULL
+);
+
+CREATE TABLE public.tag (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL
+);
+
+CREATE TABLE public.league_asset (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	code				UUID NOT NULL,
+	price				NUMERIC NOT NULL,
+	discount			NUMERIC,
+	metadata			JSONB NOT NULL,
+	logo				BYTEA NOT NULL
+);
+
+CREATE TABLE public.typed_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	slug				TEXT NOT NULL,
+	score				BIGINT
+);
+
+CREATE TYPE public.league_status AS ENUM ('scheduled', 'in_progress', 'closed');
+
+CREATE TABLE public.ranked_league (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	status				league_status NOT NULL,
+	tier				INTEGER CHECK (tier IN (1, 2, 10))
+);
+
+CREATE TABLE public.league_tag (
+	league				INTEGER NOT NULL REFERENCES league(id),
+	tag					INTEGER NOT NULL REFERENCES tag(id),
+	PRIMARY KEY (league, tag)
+);
+
 -- For now, we use for out CI process the default data for postgres instances
 
 -- ALTER TABLE public.league OWNER TO triforce;
@@ -19,6 +19,9 @@ pub mod migrations {
 /// things in `Canyon-SQL`, like the `main` macro, the IT macro.
 pub use canyon_macros::main;
 
+/// Runs ad-hoc SQL, returning its results as [`crud::DynamicRow`], tuples or scalar values
+pub use canyon_crud::crud::{query_as, query_raw, query_scalar};
+
 /// Public API for the `Canyon-SQL` proc-macros, and for the external ones
 pub mod macros {
     pub use canyon_crud::async_trait::*;
@@ -41,15 +44,27 @@ pub mod connection {
 /// Crud module serves to reexport the public elements of the `canyon_crud` crate,
 /// exposing them through the public API
 pub mod crud {
+    pub use canyon_crud::batch::{self, FoundByPks};
     pub use canyon_crud::bounds;
     pub use canyon_crud::crud::*;
+    pub use canyon_crud::deserializer::RowDeserializer;
+    pub use canyon_crud::dynamic_row::{ColumnIndex, DynamicRow};
+    pub use canyon_crud::embeddable::CanyonEmbeddable;
+    pub use canyon_crud::errors;
+    pub use canyon_crud::hooks::{self, CanyonHooks};
     pub use canyon_crud::mapper::*;
+    pub use canyon_crud::relations::{HasMany, Loaded, ManyToMany, Relation};
     pub use canyon_crud::rows::CanyonRows;
+    pub use canyon_crud::timestamps;
+    pub use canyon_crud::tracked::{Trackable, Tracked};
+    pub use canyon_crud::types::{self, CanyonType};
+    pub use canyon_crud::validation;
     pub use canyon_crud::DatabaseType;
 }
 
 /// Re-exports the query elements from the `crud`crate
 pub mod query {
+    pub use canyon_crud::query_elements::aggregates;
     pub use canyon_crud::query_elements::operators;
     pub use canyon_crud::query_elements::{query::*, query_builder::*};
 }
@@ -1,6 +1,8 @@
 //! Integration tests for the CRUD operations available in `Canyon` that
 //! generates and executes *INSERT* statements
 use canyon_sql::crud::CrudOperations;
+#[cfg(feature = "postgres")]
+use canyon_sql::query::{operators::Comp, ops::QueryBuilder};
 
 #[cfg(feature = "mysql")]
 use crate::constants::MYSQL_DS;
@@ -9,7 +11,12 @@ use crate::constants::PSQL_DS;
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
 
+use crate::tests_models::hooked_league::*;
 use crate::tests_models::league::*;
+#[cfg(feature = "postgres")]
+use crate::tests_models::soft_deleted_league::*;
+#[cfg(feature = "postgres")]
+use crate::tests_models::soft_deleted_tournament::*;
 
 /// Deletes a row from the database that is mapped into some instance of a `T` entity.
 ///
@@ -48,10 +55,11 @@ fn test_crud_delete_method_operation() {
 
     // Now that we have an instance mapped to some entity by a primary key, we can now
     // remove that entry from the database with the delete operation
-    new_league
+    let affected_rows = new_league
         .delete()
         .await
         .expect("Failed to delete the operation");
+    assert_eq!(affected_rows, 1);
 
     // To check the success, we can query by the primary key value and check if, after unwrap()
     // the result of the operation, the find by primary key contains Some(v) or None
@@ -157,3 +165,344 @@ fn test_crud_delete_datasource_mysql_method_operation() {
         None
     );
 }
+
+/// An error returned by the `before_delete` hook of the entity aborts the operation,
+/// so the row is kept on the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_delete_aborted_by_hook_operation() {
+    let mut protected_league = HookedLeague {
+        id: Default::default(),
+        slug: "protected".to_string(),
+        name: "Protected League".to_string(),
+    };
+    protected_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    assert!(protected_league.delete().await.is_err());
+    assert!(HookedLeague::find_by_pk(&protected_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .is_some());
+}
+
+/// An error returned by the `after_delete` hook of the entity rolls back the delete,
+/// so the row is kept on the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_delete_rolled_back_by_hook_operation() {
+    let mut rolled_back_league = HookedLeague {
+        id: Default::default(),
+        slug: "deleted-league".to_string(),
+        name: "Rolled Back League".to_string(),
+    };
+    rolled_back_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+    // The query builder doesn't call the hooks
+    HookedLeague::update_query()
+        .set(&[(HookedLeagueField::slug, "rolled-back")])
+        .r#where(HookedLeagueFieldValue::id(&rolled_back_league.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed to update the records with the querybuilder");
+    rolled_back_league.slug = "rolled-back".to_string();
+
+    assert!(rolled_back_league.delete().await.is_err());
+    assert!(HookedLeague::find_by_pk(&rolled_back_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .is_some());
+}
+
+/// Deletes several rows at once with the `delete_by_pks` operation, generated
+/// for the entities with a primary key
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_delete_by_pks_operation() {
+    let mut pks = Vec::new();
+    for slug in ["first-deleted-league", "second-deleted-league"] {
+        let mut new_league: League = League {
+            id: Default::default(),
+            ext_id: 7892635306594_i64,
+            slug: slug.to_string(),
+            name: "Some Deleted League".to_string(),
+            region: "Bahía de cochinos".to_string(),
+            image_url: "https://nobodyspectsandimage.io".to_string(),
+        };
+        new_league.insert().await.expect("Failed insert operation");
+        pks.push(new_league.id);
+    }
+
+    let affected_rows = League::delete_by_pks_datasource(&pks, PSQL_DS)
+        .await
+        .expect("Failed to delete the leagues");
+    assert_eq!(affected_rows, 2);
+
+    let leagues = League::find_by_pks(&pks)
+        .await
+        .expect("Failed the query to the database");
+    assert!(leagues.found.is_empty());
+    assert_eq!(leagues.missing, pks);
+}
+
+/// The `delete` operation of an entity with a `#[soft_delete]` field only marks
+/// the row as deleted, so it's excluded from the finders until it's restored,
+/// while the `hard_delete` one removes it from the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_delete_operations() {
+    let mut new_league = SoftDeletedLeague {
+        id: Default::default(),
+        name: "Soft deleted league".to_string(),
+        deleted_at: None,
+    };
+    new_league.insert().await.expect("Failed insert operation");
+
+    assert_eq!(
+        new_league
+            .delete()
+            .await
+            .expect("Failed the soft delete operation"),
+        1
+    );
+    // The row is already marked as deleted, so there's nothing to delete again
+    assert_eq!(
+        new_league
+            .delete()
+            .await
+            .expect("Failed the soft delete operation"),
+        0
+    );
+
+    assert_eq!(
+        SoftDeletedLeague::find_by_pk(&new_league.id)
+            .await
+            .expect("Failed the query to the database"),
+        None
+    );
+    assert!(!SoftDeletedLeague::find_all()
+        .await
+        .expect("Failed the query to the database")
+        .iter()
+        .any(|league| league.id == new_league.id));
+    assert!(SoftDeletedLeague::find_by_name("Soft deleted league")
+        .await
+        .expect("Failed the query to the database")
+        .is_empty());
+
+    let deleted_leagues = SoftDeletedLeague::select_query()
+        .only_deleted()
+        .r#where(SoftDeletedLeagueFieldValue::id(&new_league.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(deleted_leagues.len(), 1);
+    assert!(deleted_leagues[0].deleted_at.is_some());
+
+    assert_eq!(
+        new_league
+            .restore()
+            .await
+            .expect("Failed the restore operation"),
+        1
+    );
+    let restored_league = SoftDeletedLeague::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The restored row must be found again");
+    assert_eq!(restored_league, new_league);
+
+    assert_eq!(
+        new_league
+            .hard_delete()
+            .await
+            .expect("Failed the hard delete operation"),
+        1
+    );
+    assert!(SoftDeletedLeague::select_query()
+        .with_deleted()
+        .r#where(SoftDeletedLeagueFieldValue::id(&new_league.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed the query to the database")
+        .is_empty());
+}
+
+/// The `delete_by_pks` operation of an entity with a `#[soft_delete]` field only
+/// marks the rows as deleted
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_delete_by_pks_operation() {
+    let mut pks = Vec::new();
+    for name in ["First soft deleted league", "Second soft deleted league"] {
+        let mut new_league = SoftDeletedLeague {
+            id: Default::default(),
+            name: name.to_string(),
+            deleted_at: None,
+        };
+        new_league.insert().await.expect("Failed insert operation");
+        pks.push(new_league.id);
+    }
+
+    let affected_rows = SoftDeletedLeague::delete_by_pks(&pks)
+        .await
+        .expect("Failed to delete the leagues");
+    assert_eq!(affected_rows, 2);
+
+    let leagues = SoftDeletedLeague::find_by_pks(&pks)
+        .await
+        .expect("Failed the query to the database");
+    assert!(leagues.found.is_empty());
+    assert_eq!(leagues.missing, pks);
+
+    let deleted_leagues = SoftDeletedLeague::select_query()
+        .only_deleted()
+        .and_values_in(SoftDeletedLeagueField::id, &pks)
+        .query()
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(deleted_leagues.len(), 2);
+}
+
+/// The searches through a foreign key exclude the soft deleted parents and childrens,
+/// whose `#[soft_delete]` column holds the `UTC` time of the database when they're deleted
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_delete_foreign_key_searches() {
+    use canyon_sql::date_time::Utc;
+
+    let mut new_league = SoftDeletedLeague {
+        id: Default::default(),
+        name: "Soft deleted parent league".to_string(),
+        deleted_at: None,
+    };
+    new_league.insert().await.expect("Failed insert operation");
+
+    let mut tournaments = Vec::new();
+    for slug in ["kept-tournament", "soft-deleted-tournament"] {
+        let mut new_tournament = SoftDeletedTournament {
+            id: Default::default(),
+            slug: slug.to_string(),
+            league: new_league.id,
+            deleted_at: None,
+        };
+        new_tournament
+            .insert()
+            .await
+            .expect("Failed insert operation");
+        tournaments.push(new_tournament);
+    }
+
+    tournaments[1]
+        .delete()
+        .await
+        .expect("Failed the soft delete operation");
+    let childrens = SoftDeletedTournament::search_soft_deleted_league_childrens(&new_league)
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(childrens, vec![tournaments[0].clone()]);
+
+    let deleted_tournament = SoftDeletedTournament::select_query()
+        .only_deleted()
+        .r#where(
+            SoftDeletedTournamentFieldValue::id(&tournaments[1].id),
+            Comp::Eq,
+        )
+        .query()
+        .await
+        .expect("Failed the query to the database")
+        .remove(0);
+    let deleted_at = deleted_tournament
+        .deleted_at
+        .expect("The soft deleted row must be marked");
+    assert!((Utc::now().naive_utc() - deleted_at).num_minutes().abs() < 1);
+
+    assert_eq!(
+        tournaments[0]
+            .search_soft_deleted_league()
+            .await
+            .expect("Failed the query to the database"),
+        Some(new_league.clone())
+    );
+    new_league
+        .delete()
+        .await
+        .expect("Failed the soft delete operation");
+    assert_eq!(
+        tournaments[0]
+            .search_soft_deleted_league()
+            .await
+            .expect("Failed the query to the database"),
+        None
+    );
+}
+
+/// The statements sent with `execute_all` run in the same database transaction, so
+/// the already executed ones are rolled back when any of them fails
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_execute_all_rollback_operation() {
+    use canyon_sql::crud::{bounds::QueryParameter, Transaction};
+
+    let mut new_league: League = League {
+        id: Default::default(),
+        ext_id: 7892635306594_i64,
+        slug: "rolled-back-deletion-league".to_string(),
+        name: "Some Rolled Back League".to_string(),
+        region: "Bahía de cochinos".to_string(),
+        image_url: "https://nobodyspectsandimage.io".to_string(),
+    };
+    new_league.insert().await.expect("Failed insert operation");
+
+    let result = League::execute_all(
+        vec![
+            (
+                "DELETE FROM league WHERE id = $1".to_string(),
+                vec![&new_league.id as &dyn QueryParameter<'_>],
+            ),
+            ("DELETE FROM non_existent_table".to_string(), vec![]),
+        ],
+        PSQL_DS,
+    )
+    .await;
+    assert!(result.is_err());
+
+    assert!(League::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .is_some());
+    new_league
+        .delete()
+        .await
+        .expect("Failed to delete the league");
+}
+
+/// A transaction whose future is dropped between its statements is rolled back,
+/// so the shared connection isn't left inside of it for the following operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_execute_all_cancelled_operation() {
+    use canyon_sql::crud::Transaction;
+    use canyon_sql::runtime::tokio::time::{timeout, Duration};
+
+    let cancelled = timeout(
+        Duration::from_millis(100),
+        League::execute_all(
+            vec![
+                ("SELECT pg_sleep(0.5)".to_string(), vec![]),
+                ("SELECT pg_sleep(0.5)".to_string(), vec![]),
+            ],
+            PSQL_DS,
+        ),
+    )
+    .await;
+    assert!(cancelled.is_err());
+
+    // The savepoints can only be created inside of a transaction
+    let savepoint = League::execute("SAVEPOINT outside_of_transaction", [], PSQL_DS).await;
+    assert!(savepoint.is_err());
+}
@@ -8,7 +8,10 @@
 // Names of the foreign key methods are autogenerated for the direct and
 // reverse side of the implementations.
 // For more info: TODO -> Link to the docs of the foreign key chapter
-use canyon_sql::crud::CrudOperations;
+use canyon_sql::{
+    crud::{CrudOperations, Loaded},
+    query::{operators::Comp, ops::QueryBuilder},
+};
 
 #[cfg(feature = "mssql")]
 use crate::constants::MYSQL_DS;
@@ -16,6 +19,8 @@ use crate::constants::MYSQL_DS;
 use crate::constants::SQL_SERVER_DS;
 
 use crate::tests_models::league::*;
+use crate::tests_models::related_league::*;
+use crate::tests_models::tag::*;
 use crate::tests_models::tournament::*;
 
 /// Given an entity `T` which has some field declaring a foreign key relation
@@ -161,3 +166,198 @@ fn test_crud_search_reverse_side_foreign_key_datasource_mysql() {
         .iter()
         .for_each(|t| assert_eq!(t.league, some_league.id));
 }
+
+/// Loads the parent entities of the retrieved ones along with them, with a
+/// single query for all of them instead of one query per retrieved entity
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_select_query_with_foreign_key_relation() {
+    // Only the tournaments that aren't inserted or deleted by any other test
+    let mut select = Tournament::select_query();
+    select
+        .r#where(TournamentFieldValue::id(&10), Comp::LtEq)
+        .order_by(TournamentField::id, false);
+    let tournaments: Vec<Loaded<Tournament, League>> = select
+        .with(TournamentRelation::league)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+
+    let expected = Tournament::select_query()
+        .r#where(TournamentFieldValue::id(&10), Comp::LtEq)
+        .order_by(TournamentField::id, false)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(
+        tournaments.iter().map(|t| t.id).collect::<Vec<_>>(),
+        expected.iter().map(|t| t.id).collect::<Vec<_>>()
+    );
+    assert!(!tournaments.is_empty());
+
+    for tournament in &tournaments {
+        let league = tournament
+            .related
+            .as_ref()
+            .expect("Every tournament belongs to some league");
+        assert_eq!(tournament.league, league.id);
+    }
+
+    let first_league = tournaments[0]
+        .search_league()
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(tournaments[0].related.as_deref(), first_league.as_ref());
+
+    // The relations can be chained, being the loaded entities nested in the order
+    // of the relations, and the fields of the entity reachable through all of them
+    let mut select = Tournament::select_query();
+    select
+        .r#where(TournamentFieldValue::id(&10), Comp::LtEq)
+        .order_by(TournamentField::id, false);
+    let chained: Vec<Loaded<Loaded<Tournament, League>, League>> = select
+        .with(TournamentRelation::league)
+        .with(TournamentRelation::league)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(
+        chained.iter().map(|t| t.id).collect::<Vec<_>>(),
+        expected.iter().map(|t| t.id).collect::<Vec<_>>()
+    );
+    for (chained, tournament) in chained.iter().zip(&tournaments) {
+        assert_eq!(chained.entity, *tournament);
+        assert_eq!(chained.related, tournament.related);
+    }
+}
+
+/// Loads the tournaments of a league declared as a `#[has_many]` relation, and
+/// moves a tournament to another league by adding it to its tournaments
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_has_many_relation_operations() {
+    let mut league = RelatedLeague::find_by_pk(&1)
+        .await
+        .expect("Result variant of the query is err")
+        .expect("No result found for the given parameter");
+    assert!(league.tournaments.is_empty());
+
+    let tournaments = league
+        .load_tournaments()
+        .await
+        .expect("Result variant of the query is err");
+    assert!(!tournaments.is_empty());
+    assert!(tournaments.iter().all(|t| t.league == 1));
+
+    let mut new_tournament = Tournament {
+        id: Default::default(),
+        ext_id: 9987135230_i64,
+        slug: "relations-cup".to_string(),
+        start_date: canyon_sql::date_time::NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+        end_date: canyon_sql::date_time::NaiveDate::from_ymd_opt(2023, 2, 1).unwrap(),
+        league: 1,
+    };
+    new_tournament
+        .insert()
+        .await
+        .expect("Failed insert operation");
+
+    let mut other_league = RelatedLeague::find_by_pk(&8)
+        .await
+        .expect("Result variant of the query is err")
+        .expect("No result found for the given parameter");
+    let affected_rows = other_league
+        .add_tournaments(std::slice::from_ref(&new_tournament))
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(affected_rows, 1);
+    assert!(other_league
+        .tournaments
+        .iter()
+        .any(|t| t.id == new_tournament.id && t.league == 8));
+
+    let moved_tournament = Tournament::find_by_pk(&new_tournament.id)
+        .await
+        .expect("Result variant of the query is err")
+        .expect("No result found for the given parameter");
+    assert_eq!(moved_tournament.league, 8);
+
+    moved_tournament
+        .delete()
+        .await
+        .expect("Failed delete operation");
+}
+
+/// Relates some tags with a league through the join table of a `#[many_to_many]`
+/// relation, removing and replacing them afterwards
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_many_to_many_relation_operations() {
+    let mut tags = Vec::new();
+    for name in ["relations-classic", "relations-rookie"] {
+        let mut tag = Tag {
+            id: Default::default(),
+            name: name.to_string(),
+        };
+        tag.insert().await.expect("Failed insert operation");
+        tags.push(tag);
+    }
+    let tag_ids = |league: &RelatedLeague| {
+        let mut ids = league.tags.iter().map(|tag| tag.id).collect::<Vec<_>>();
+        ids.sort();
+        ids
+    };
+
+    let mut league = RelatedLeague::find_by_pk(&8)
+        .await
+        .expect("Result variant of the query is err")
+        .expect("No result found for the given parameter");
+
+    let added = league
+        .add_tags(&tags)
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(added, 2);
+    assert_eq!(tag_ids(&league), vec![tags[0].id, tags[1].id]);
+
+    let removed = league
+        .remove_tags(&tags[..1])
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(removed, 1);
+    assert_eq!(tag_ids(&league), vec![tags[1].id]);
+
+    // The second tag is already related, so neither of them is added
+    assert!(league.add_tags(&tags).await.is_err());
+    league
+        .load_tags()
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(tag_ids(&league), vec![tags[1].id]);
+
+    let set = league
+        .set_tags(&tags[..1])
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(set, 1);
+    assert_eq!(league.tags, tags[..1]);
+
+    let mut reloaded_league = RelatedLeague::find_by_pk(&8)
+        .await
+        .expect("Result variant of the query is err")
+        .expect("No result found for the given parameter");
+    let reloaded_tags = reloaded_league
+        .load_tags()
+        .await
+        .expect("Result variant of the query is err");
+    assert_eq!(reloaded_tags, &tags[..1]);
+
+    league
+        .set_tags(&[])
+        .await
+        .expect("Result variant of the query is err");
+    assert!(league.tags.is_empty());
+    for tag in tags {
+        tag.delete().await.expect("Failed delete operation");
+    }
+}
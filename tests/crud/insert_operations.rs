@@ -1,13 +1,24 @@
 //! Integration tests for the CRUD operations available in `Canyon` that
 //! generates and executes *INSERT* statements
 use canyon_sql::crud::CrudOperations;
+#[cfg(feature = "postgres")]
+use canyon_sql::{
+    crud::errors::ValidationErrors,
+    query::{operators::Comp, ops::QueryBuilder},
+};
 
 #[cfg(feature = "mysql")]
 use crate::constants::MYSQL_DS;
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
 
+use crate::tests_models::defaulted_league::*;
+use crate::tests_models::hooked_league::*;
 use crate::tests_models::league::*;
+#[cfg(all(feature = "uuid", feature = "decimal", feature = "json"))]
+use crate::tests_models::league_asset::*;
+use crate::tests_models::typed_league::*;
+use crate::tests_models::validated_league::*;
 
 /// Inserts a new record on the database, given an entity that is
 /// annotated with `#[canyon_entity]` macro over a *T* type.
@@ -315,3 +326,271 @@ fn test_crud_multi_insert_datasource_mysql_operation() {
     assert_eq!(new_league_mi_2.id, inserted_league_2.id);
     assert_eq!(new_league_mi_3.id, inserted_league_3.id);
 }
+
+/// The lifecycle hooks of the entity are called around the insert, letting it
+/// to normalize its data before it's written, or to abort the operation.
+///
+/// The `after_load` hook is called over the instances retrieved from the database,
+/// by the `find_*` operations, by the queries of the `SelectQueryBuilder` and by
+/// the `returning` operation of the `UpdateQueryBuilder`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_with_hooks_operation() {
+    let mut new_league = HookedLeague {
+        id: Default::default(),
+        slug: "  Hooked-League ".to_string(),
+        name: "Hooked League".to_string(),
+    };
+    new_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+    assert_eq!(new_league.slug, "hooked-league");
+
+    let loaded: HookedLeague = HookedLeague::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(loaded.slug, "HOOKED-LEAGUE");
+
+    let queried: Vec<HookedLeague> = HookedLeague::select_query()
+        .r#where(HookedLeagueFieldValue::id(&new_league.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(queried, vec![loaded]);
+
+    let updated: Vec<HookedLeague> = HookedLeague::update_query()
+        .set(&[(HookedLeagueField::name, "Hooked League Updated")])
+        .r#where(HookedLeagueFieldValue::id(&new_league.id), Comp::Eq)
+        .returning()
+        .await
+        .expect("Failed to update the records with the querybuilder");
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].slug, "HOOKED-LEAGUE");
+
+    // The `before_insert` hook rejects the leagues without name
+    let mut unnamed_league = HookedLeague {
+        id: Default::default(),
+        slug: "unnamed".to_string(),
+        name: " ".to_string(),
+    };
+    assert!(unnamed_league.insert().await.is_err());
+    assert_eq!(unnamed_league.id, i32::default());
+}
+
+/// An error returned by the `after_insert` hook of the entity rolls back the insert,
+/// so the row isn't kept on the database, even if its primary key was assigned
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_rolled_back_by_hook_operation() {
+    let mut rolled_back_league = HookedLeague {
+        id: Default::default(),
+        slug: "rolled-back".to_string(),
+        name: "Rolled Back League".to_string(),
+    };
+    assert!(rolled_back_league.insert().await.is_err());
+    assert_ne!(rolled_back_league.id, i32::default());
+    assert_eq!(
+        HookedLeague::find_by_pk(&rolled_back_league.id)
+            .await
+            .expect("Failed the query to the database"),
+        None
+    );
+
+    let mut rolled_back_leagues = [rolled_back_league.clone(), rolled_back_league];
+    let [first_league, second_league] = &mut rolled_back_leagues;
+    assert!(
+        HookedLeague::multi_insert(&mut [first_league, second_league])
+            .await
+            .is_err()
+    );
+    for league in &rolled_back_leagues {
+        assert_eq!(
+            HookedLeague::find_by_pk(&league.id)
+                .await
+                .expect("Failed the query to the database"),
+            None
+        );
+    }
+}
+
+/// The rules declared in the `#[validate(...)]` annotations are checked before the
+/// insert, so nothing is written to the database if some of them fails, returning
+/// a [`ValidationErrors`] that lists every failing field
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_with_validation_errors_operation() {
+    let mut invalid_league = ValidatedLeague {
+        id: Default::default(),
+        ext_id: -1,
+        slug: "Not A Slug".to_string(),
+        name: "Validated League".to_string(),
+        region: "Atlantis".to_string(),
+        image_url: "https://validated.league".to_string(),
+    };
+
+    let insert_result = invalid_league.insert().await;
+    let validation_errors = insert_result
+        .expect_err("The insert of an invalid instance must fail")
+        .downcast::<ValidationErrors>()
+        .expect("The insert must fail with a ValidationErrors");
+    let failing_rules = validation_errors
+        .errors
+        .iter()
+        .map(|error| (error.field, error.rule))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        failing_rules,
+        vec![("ext_id", "range"), ("slug", "regex"), ("region", "custom")]
+    );
+    assert_eq!(invalid_league.id, i32::default());
+
+    // Once fixed, the instance is written
+    invalid_league.ext_id = 1;
+    invalid_league.slug = "validated-league".to_string();
+    invalid_league.region = "EU".to_string();
+    invalid_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+    assert_ne!(invalid_league.id, i32::default());
+
+    // Every instance is validated before the multi insert
+    let mut valid_league = invalid_league.clone();
+    let mut unnamed_league = invalid_league.clone();
+    unnamed_league.name = String::new();
+    let validation_errors =
+        ValidatedLeague::multi_insert(&mut [&mut valid_league, &mut unnamed_league])
+            .await
+            .expect_err("The multi insert with an invalid instance must fail")
+            .downcast::<ValidationErrors>()
+            .expect("The multi insert must fail with a ValidationErrors");
+    assert_eq!(validation_errors.instance, Some(1));
+    assert_eq!(validation_errors.field_errors("name").count(), 1);
+}
+
+/// The `insert_returning` operation refreshes every field of the instance with
+/// the inserted row, so the values of the `#[db_default]` fields, that aren't
+/// written by the insert, are the ones generated by the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_returning_operation() {
+    let mut new_league = DefaultedLeague {
+        id: Default::default(),
+        name: "Defaulted League".to_string(),
+        region: "Ignored region".to_string(),
+        score: Default::default(),
+    };
+
+    new_league
+        .insert_returning()
+        .await
+        .expect("Failed the insert operation");
+
+    assert!(new_league.id > 0);
+    assert_eq!(new_league.name, "Defaulted League");
+    assert_eq!(new_league.region, "EUROPE");
+    assert_eq!(new_league.score, 100);
+
+    assert_eq!(
+        DefaultedLeague::find_by_pk(&new_league.id)
+            .await
+            .expect("Failed the query to the database"),
+        Some(new_league)
+    );
+}
+
+/// The fields of the user defined types that derives `CanyonType` are written
+/// and retrieved as the type that they wrap, and they can be used on the finders
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_and_find_user_defined_types_operation() {
+    let mut new_league = TypedLeague {
+        id: Default::default(),
+        slug: LeagueSlug("typed-league".to_string()),
+        score: Some(LeagueScore {
+            value: 9_000_000_000,
+        }),
+    };
+    new_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    let mut unscored_league = TypedLeague {
+        id: Default::default(),
+        slug: LeagueSlug("unscored-typed-league".to_string()),
+        score: None,
+    };
+    unscored_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    assert_eq!(
+        TypedLeague::find_by_pk(&new_league.id)
+            .await
+            .expect("Failed the query to the database"),
+        Some(new_league.clone())
+    );
+    assert_eq!(
+        TypedLeague::find_by_slug(&LeagueSlug("unscored-typed-league".to_string()))
+            .await
+            .expect("Failed the query to the database"),
+        vec![unscored_league]
+    );
+    assert_eq!(
+        TypedLeague::find_by_score(&LeagueScore {
+            value: 9_000_000_000
+        })
+        .await
+        .expect("Failed the query to the database"),
+        vec![new_league]
+    );
+}
+
+/// The values of the `Uuid`, `Decimal`, JSON and binary fields are written
+/// and retrieved back without changes
+#[cfg(all(
+    feature = "postgres",
+    feature = "uuid",
+    feature = "decimal",
+    feature = "json"
+))]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_and_find_external_types_operation() {
+    let mut new_asset = LeagueAsset {
+        id: Default::default(),
+        code: uuid::Uuid::from_u128(0x936d_a01f_9abd_4d9d_80c7_02af_85c8_22a8),
+        price: rust_decimal::Decimal::new(1999, 2),
+        discount: None,
+        metadata: serde_json::json!({ "sponsor": "Canyon", "tiers": [1, 2, 3] }),
+        logo: vec![0x89, 0x50, 0x4e, 0x47, 0x00, 0xff],
+    };
+
+    new_asset
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    assert_eq!(
+        LeagueAsset::find_by_pk(&new_asset.id)
+            .await
+            .expect("Failed the query to the database"),
+        Some(new_asset.clone())
+    );
+
+    new_asset.discount = Some(rust_decimal::Decimal::new(-250, 3));
+    new_asset
+        .update()
+        .await
+        .expect("Failed the update operation");
+
+    assert_eq!(
+        LeagueAsset::find_by_pk(&new_asset.id)
+            .await
+            .expect("Failed the query to the database"),
+        Some(new_asset)
+    );
+}
@@ -1,5 +1,7 @@
 #[cfg(feature = "mysql")]
 use crate::constants::MYSQL_DS;
+#[cfg(feature = "postgres")]
+use crate::constants::PSQL_DS;
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
 
@@ -10,12 +12,19 @@ use crate::constants::SQL_SERVER_DS;
 // with the SQL filters
 ///
 use canyon_sql::{
-    crud::CrudOperations,
-    query::{operators::Comp, operators::Like, ops::QueryBuilder},
+    crud::{errors::RowMapperError, CrudOperations},
+    macros::{CanyonEnum, CanyonMapper, CanyonType},
+    query::{
+        aggregates::Aggregate, operators::Comp, operators::Like, ops::QueryBuilder,
+        SelectQueryBuilder,
+    },
 };
 
+use crate::tests_models::audited_league::*;
 use crate::tests_models::league::*;
 use crate::tests_models::player::*;
+use crate::tests_models::ranked_league::*;
+use crate::tests_models::sponsor::*;
 use crate::tests_models::tournament::*;
 
 /// Builds a new SQL statement for retrieves entities of the `T` type, filtered
@@ -36,7 +45,7 @@ fn test_generated_sql_by_the_select_querybuilder() {
     // generated SQL by the SelectQueryBuilder<T> is the spected
     assert_eq!(
         select_with_joins.read_sql(),
-        "SELECT * FROM league INNER JOIN tournament ON league.id = tournament.league_id LEFT JOIN team ON tournament.id = player.tournament_id WHERE id > $1 AND name = $2 AND name IN ($2, $3)"
+        "SELECT * FROM league INNER JOIN tournament ON league.id = tournament.league_id LEFT JOIN team ON tournament.id = player.tournament_id WHERE id > $1 AND name = $2 AND name IN ($3, $4)"
     )
 }
 
@@ -338,6 +347,46 @@ fn test_crud_delete_with_querybuilder() {
     assert_eq!(Tournament::find_by_pk(&15).await.unwrap(), None);
 }
 
+/// Updates and then deletes some rows with the querybuilders, retrieving
+/// the modified rows with the `returning` operation
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_and_delete_returning_with_querybuilder() {
+    for _ in 0..2 {
+        let mut new_league: League = League {
+            id: Default::default(),
+            ext_id: 7892635306594_i64,
+            slug: "returning-league".to_string(),
+            name: "Returning League".to_string(),
+            region: "EUROPE".to_string(),
+            image_url: "https://nobodyspectsandimage.io".to_string(),
+        };
+        new_league.insert().await.expect("Failed insert operation");
+    }
+
+    let updated = League::update_query()
+        .set(&[(LeagueField::name, "Updated Returning League")])
+        .r#where(LeagueFieldValue::slug(&"returning-league"), Comp::Eq)
+        .returning()
+        .await
+        .expect("Failed to update records with the querybuilder");
+    assert_eq!(updated.len(), 2);
+    assert!(updated
+        .iter()
+        .all(|league| league.name == "Updated Returning League"));
+
+    let deleted = League::delete_query()
+        .r#where(LeagueFieldValue::slug(&"returning-league"), Comp::Eq)
+        .returning()
+        .await
+        .expect("Failed to delete records with the querybuilder");
+    let mut deleted_ids = deleted.iter().map(|league| league.id).collect::<Vec<_>>();
+    let mut updated_ids = updated.iter().map(|league| league.id).collect::<Vec<_>>();
+    deleted_ids.sort();
+    updated_ids.sort();
+    assert_eq!(deleted_ids, updated_ids);
+}
+
 /// Same as the above delete, but with the specified datasource
 #[cfg(feature = "mssql")]
 #[canyon_sql::macros::canyon_tokio_test]
@@ -410,7 +459,20 @@ fn test_and_clause_with_in_constraint() {
 
     assert_eq!(
         l.read_sql().trim(),
-        "SELECT * FROM league WHERE name = $1 AND id IN ($1, $2, $3)"
+        "SELECT * FROM league WHERE name = $1 AND id IN ($2, $3, $4)"
+    )
+}
+
+/// Tests for the generated SQL query when the IN constraint
+/// is the first condition of the query
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_in_constraint_without_previous_conditions() {
+    let mut l = League::delete_query();
+    l.and_values_in(LeagueField::id, &[1, 7, 10]);
+
+    assert_eq!(
+        l.read_sql().trim(),
+        "DELETE FROM league WHERE id IN ($1, $2, $3)"
     )
 }
 
@@ -438,7 +500,7 @@ fn test_or_clause_with_in_constraint() {
 
     assert_eq!(
         l.read_sql(),
-        "SELECT * FROM league WHERE name = $1 OR id IN ($1, $2, $3)"
+        "SELECT * FROM league WHERE name = $1 OR id IN ($2, $3, $4)"
     )
 }
 
@@ -455,3 +517,646 @@ fn test_order_by_clause() {
         "SELECT * FROM league WHERE name = $1 ORDER BY id"
     )
 }
+
+/// Tests for the generated SQL query after use the
+/// pagination clauses
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_limit_offset_clauses() {
+    let mut l = League::select_query();
+    l.r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .order_by(LeagueField::id, false)
+        .limit(10)
+        .offset(20);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT * FROM league WHERE region = $1 ORDER BY id LIMIT 10 OFFSET 20"
+    )
+}
+
+/// Same as the above, but with the pagination syntax of the SqlServer
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_limit_offset_clauses_datasource_mssql() {
+    let mut l = League::select_query_datasource(SQL_SERVER_DS);
+    l.r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .page(2, 10);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT * FROM league WHERE region = $1 ORDER BY (SELECT NULL) OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+    )
+}
+
+/// Same as the above, but with the pagination syntax of MySQL
+#[cfg(feature = "mysql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_limit_offset_clauses_datasource_mysql() {
+    let mut l = League::select_query_datasource(MYSQL_DS);
+    l.r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .offset(5)
+        .limit(10);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT * FROM league WHERE region = $1 LIMIT 10 OFFSET 5"
+    )
+}
+
+/// Tests for the generated SQL query after use the
+/// keyset pagination
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_keyset_pagination_clause() {
+    let mut l = League::select_query();
+    l.r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .after(LeagueField::id, &10)
+        .limit(5);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT * FROM league WHERE region = $1 AND id > $2 ORDER BY id LIMIT 5"
+    )
+}
+
+/// Tests for the generated SQL query after use the keyset pagination
+/// in descending order, followed by more conditions
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_keyset_pagination_desc_clause() {
+    let mut l = League::select_query();
+    l.after_desc(LeagueField::id, &10)
+        .and(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .limit(5);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT * FROM league WHERE id < $1 AND region = $2 ORDER BY id DESC LIMIT 5"
+    )
+}
+
+/// Retrieves a page of results, along with the total of rows that
+/// matches the same filters
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_paginate_with_querybuilder() {
+    let page = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .paginate(1, 10)
+        .await
+        .expect("Failed to retrieve a page of leagues");
+
+    let filtered_leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .query()
+        .await
+        .expect("Failed to retrieve the leagues with the querybuilder");
+
+    assert_eq!(page.total, filtered_leagues.len() as i64);
+    assert_eq!(page.items, filtered_leagues[10..20]);
+    assert!(page.has_next());
+}
+
+/// The pages retrieved with the keyset pagination are ordered by the key column,
+/// and their total counts every row that matches the filters, and not only the
+/// ones placed after the key
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_paginate_with_keyset_querybuilder() {
+    let filtered_leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .query()
+        .await
+        .expect("Failed to retrieve the leagues with the querybuilder");
+
+    let first_page = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .after(LeagueField::id, &0)
+        .paginate(0, 10)
+        .await
+        .expect("Failed to retrieve a page of leagues");
+    assert_eq!(first_page.items, filtered_leagues[..10]);
+
+    let last_id = first_page.items.last().map(|league| league.id).unwrap();
+    let second_page = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .after(LeagueField::id, &last_id)
+        .paginate(0, 10)
+        .await
+        .expect("Failed to retrieve a page of leagues");
+    assert_eq!(second_page.items, filtered_leagues[10..20]);
+    assert_eq!(second_page.total, filtered_leagues.len() as i64);
+    assert_eq!(second_page.total, first_page.total);
+}
+
+/// The keyset pagination orders the rows by its key column, so
+/// it can't be combined with another ordering
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+#[should_panic(expected = "can't be combined with an `order_by`")]
+fn test_keyset_pagination_with_order_by_clause() {
+    League::select_query()
+        .order_by(LeagueField::name, false)
+        .after(LeagueField::id, &10);
+}
+
+/// Tests for the generated SQL query after use the
+/// aggregate functions along with the GROUP BY and HAVING clauses
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_aggregates_with_group_by_and_having_clauses() {
+    let mut l = League::select_query();
+    l.r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .group_by(LeagueField::region)
+        .count()
+        .max(LeagueField::id)
+        .having(Aggregate::count(), Comp::Gt, &1)
+        .order_by(LeagueField::region, false);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT region, COUNT(*) AS count, MAX(id) AS max_id FROM league WHERE id <= $1 GROUP BY region HAVING COUNT(*) > $2 ORDER BY region"
+    )
+}
+
+/// Same as the above, but with the aggregates syntax of the SqlServer
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_aggregates_with_group_by_and_having_clauses_datasource_mssql() {
+    let mut l = League::select_query_datasource(SQL_SERVER_DS);
+    l.group_by(LeagueField::region)
+        .count()
+        .avg(LeagueField::ext_id)
+        .having(Aggregate::count(), Comp::GtEq, &2);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT region, COUNT_BIG(*) AS count, CAST(AVG(ext_id) AS FLOAT) AS avg_ext_id FROM league GROUP BY region HAVING COUNT_BIG(*) >= $1"
+    )
+}
+
+/// Type for retrieve the results of the aggregate queries over the `League` entity
+#[derive(Debug, CanyonMapper)]
+pub struct LeaguesPerRegion {
+    region: String,
+    count: i64,
+}
+
+/// Retrieves the results of the aggregate functions mapped into tuples and
+/// into custom types deriving `CanyonMapper`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_aggregates_with_querybuilder() {
+    let leagues_per_region: Vec<(String, i64)> = League::select_query()
+        .group_by(LeagueField::region)
+        .count()
+        .order_by(LeagueField::region, false)
+        .query_as()
+        .await
+        .expect("Failed to retrieve the aggregated leagues as tuples");
+    assert!(!leagues_per_region.is_empty());
+
+    let mapped_leagues_per_region: Vec<LeaguesPerRegion> = League::select_query()
+        .group_by(LeagueField::region)
+        .count()
+        .order_by(LeagueField::region, false)
+        .query_as()
+        .await
+        .expect("Failed to retrieve the aggregated leagues as LeaguesPerRegion");
+
+    assert_eq!(leagues_per_region.len(), mapped_leagues_per_region.len());
+    leagues_per_region
+        .iter()
+        .zip(mapped_leagues_per_region.iter())
+        .for_each(|((region, count), mapped)| {
+            assert_eq!(region, &mapped.region);
+            assert_eq!(count, &mapped.count);
+        });
+
+    let total: i64 = leagues_per_region.iter().map(|(_, count)| count).sum();
+    assert_eq!(total, League::count().await.unwrap());
+}
+
+/// Lighter type for retrieve only some of the columns of the `League` entity
+#[derive(Debug, CanyonMapper)]
+pub struct LeagueSummary {
+    id: i32,
+    name: String,
+}
+
+/// Tests for the generated SQL query after restrict the
+/// projection to some of the columns of the entity
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_projection_clause() {
+    let mut l =
+        League::select_query().project::<LeagueSummary>(&[LeagueField::id, LeagueField::name]);
+    l.r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq);
+
+    assert_eq!(
+        l.read_sql(),
+        "SELECT id, name FROM league WHERE region = $1"
+    )
+}
+
+/// The projected type can't be built from a projection that
+/// doesn't contains every column that it expects
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+#[should_panic]
+fn test_projection_with_missing_columns() {
+    let _ = League::select_query().project::<LeagueSummary>(&[LeagueField::id]);
+}
+
+/// Retrieves only the projected columns of the entity, mapped into
+/// a type that only derives `CanyonMapper`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_querybuilder_and_projection() {
+    let summaries: Vec<LeagueSummary> = League::select_query()
+        .project::<LeagueSummary>(&[LeagueField::id, LeagueField::name])
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .query()
+        .await
+        .expect("Failed to retrieve the projected leagues");
+
+    let leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .query()
+        .await
+        .expect("Failed to retrieve the leagues");
+
+    assert_eq!(summaries.len(), leagues.len());
+    summaries
+        .iter()
+        .zip(leagues.iter())
+        .for_each(|(summary, league)| {
+            assert_eq!(summary.id, league.id);
+            assert_eq!(summary.name, league.name);
+        });
+}
+
+/// A type whose `id` doesn't match the type of the column on the database
+#[allow(dead_code)]
+#[derive(Debug, CanyonMapper)]
+pub struct DriftedLeagueSummary {
+    id: String,
+    name: String,
+}
+
+/// The rows that can't be mapped into the requested type are reported as
+/// a `RowMapperError`, instead of panicking
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_querybuilder_and_mismatched_types() {
+    let drifted = League::select_query()
+        .query_as::<DriftedLeagueSummary>()
+        .await
+        .expect_err("An integer column can't be mapped into a String");
+    let drifted = drifted
+        .downcast_ref::<RowMapperError>()
+        .expect("The error must be a RowMapperError");
+    assert_eq!(drifted.entity, "DriftedLeagueSummary");
+    assert_eq!(drifted.column, "id");
+    assert_eq!(drifted.expected, "String");
+    assert_eq!(drifted.actual.as_deref(), Some("int4"));
+
+    let missing = League::select_query()
+        .project::<LeagueSummary>(&[LeagueField::id, LeagueField::name])
+        .query_as::<(i32, String, String)>()
+        .await
+        .expect_err("There's no third column on the projection");
+    let missing = missing
+        .downcast_ref::<RowMapperError>()
+        .expect("The error must be a RowMapperError");
+    assert_eq!(missing.column, "2");
+    assert!(missing.actual.is_none());
+}
+
+/// A read model that takes its key from the first column, its name from any of
+/// two differently named columns, and that tolerates the absence of the
+/// columns of its optional fields
+#[derive(Debug, CanyonMapper)]
+#[canyon_mapper(lenient)]
+pub struct LeagueView {
+    #[column_index(0)]
+    key: i32,
+    #[column(alias = "title")]
+    name: String,
+    region: Option<String>,
+    image_url: Option<String>,
+}
+
+/// The lenient mappers are built from projections that lacks the columns of their
+/// optional fields, and their fields can be read by position or through aliases
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_querybuilder_and_lenient_mapper() {
+    let views: Vec<LeagueView> = League::select_query()
+        .project::<LeagueView>(&[LeagueField::id, LeagueField::name])
+        .r#where(LeagueFieldValue::id(&1), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed to retrieve the projected leagues");
+    assert_eq!(views.len(), 1);
+    assert_eq!(views[0].key, 1);
+    assert_eq!(views[0].name, "European Masters");
+    assert!(views[0].region.is_none());
+    assert!(views[0].image_url.is_none());
+
+    let views: Vec<LeagueView> = canyon_sql::query_as(
+        "SELECT id, name AS title, region FROM league WHERE id IN (1, 8) ORDER BY id",
+        [],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(views[1].key, 8);
+    assert_eq!(views[1].name, "PG Nationals");
+    assert_eq!(views[1].region.as_deref(), Some("EUROPE"));
+    assert!(views[1].image_url.is_none());
+
+    let missing = canyon_sql::query_as::<LeagueView, _, _>(
+        "SELECT id, region FROM league WHERE id = 1",
+        [],
+        PSQL_DS,
+    )
+    .await
+    .expect_err("The non optional fields can't be defaulted");
+    let missing = missing
+        .downcast_ref::<RowMapperError>()
+        .expect("The error must be a RowMapperError");
+    assert_eq!(missing.column, "name | title");
+    assert!(missing.actual.is_none());
+}
+
+/// Newtype over the primary key of the `League` entity
+#[derive(Debug, Clone, Copy, PartialEq, CanyonType)]
+pub struct LeagueId(i32);
+
+/// Newtype over the name of a `League`
+#[derive(Debug, Clone, PartialEq, CanyonType)]
+pub struct LeagueName {
+    value: String,
+}
+
+/// Lighter type for retrieve some of the columns of the `League` entity
+/// as user defined types
+#[derive(Debug, CanyonMapper)]
+pub struct TypedLeagueSummary {
+    id: LeagueId,
+    name: LeagueName,
+}
+
+/// The user defined types that derives `CanyonType` can be used as the
+/// values of the filters and as the fields of the mapped types
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_querybuilder_and_user_defined_types() {
+    let summaries: Vec<TypedLeagueSummary> = League::select_query()
+        .project::<TypedLeagueSummary>(&[LeagueField::id, LeagueField::name])
+        .r#where(LeagueFieldValue::id(&LeagueId(50)), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .query_as()
+        .await
+        .expect("Failed to retrieve the leagues as user defined types");
+
+    let leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&50), Comp::LtEq)
+        .order_by(LeagueField::id, false)
+        .query()
+        .await
+        .expect("Failed to retrieve the leagues");
+
+    assert_eq!(summaries.len(), leagues.len());
+    summaries
+        .iter()
+        .zip(leagues.iter())
+        .for_each(|(summary, league)| {
+            assert_eq!(summary.id, LeagueId(league.id));
+            assert_eq!(summary.name.value, league.name);
+        });
+}
+
+/// Some of the regions of the leagues, stored as text on the database
+#[derive(Debug, Clone, Copy, PartialEq, CanyonEnum)]
+pub enum Region {
+    #[canyon_enum(rename = "KOREA")]
+    Korea,
+    #[canyon_enum(rename = "EUROPE")]
+    Europe,
+}
+
+/// Type for retrieve the region of the leagues as an enum
+#[derive(Debug, CanyonMapper)]
+pub struct LeagueRegion {
+    region: Region,
+}
+
+/// The enums that derives `CanyonEnum` can be used as the values of the
+/// filters and as the fields of the mapped types
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_querybuilder_and_enums() {
+    let korean_leagues: Vec<LeagueRegion> = League::select_query()
+        .project::<LeagueRegion>(&[LeagueField::region])
+        .r#where(LeagueFieldValue::region(&Region::Korea), Comp::Eq)
+        .query_as()
+        .await
+        .expect("Failed to retrieve the regions of the leagues as enums");
+
+    assert!(!korean_leagues.is_empty());
+    assert!(korean_leagues
+        .iter()
+        .all(|league| league.region == Region::Korea));
+}
+
+/// The enums with the `pg_enum` representation are written to and read from the
+/// columns of a native `ENUM` type, while the ones with the `int` representation are
+/// stored as the discriminants of their variants
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_with_querybuilder_and_enum_representations() {
+    let mut gold_league = RankedLeague {
+        id: Default::default(),
+        name: "Gold League".to_string(),
+        status: LeagueStatus::InProgress,
+        tier: Some(LeagueTier::Gold),
+    };
+    gold_league.insert().await.expect("Failed insert operation");
+    let mut unranked_league = RankedLeague {
+        id: Default::default(),
+        name: "Unranked League".to_string(),
+        status: LeagueStatus::Scheduled,
+        tier: None,
+    };
+    unranked_league
+        .insert()
+        .await
+        .expect("Failed insert operation");
+
+    let in_progress: Vec<RankedLeague> = RankedLeague::select_query()
+        .r#where(
+            RankedLeagueFieldValue::status(&LeagueStatus::InProgress),
+            Comp::Eq,
+        )
+        .and(RankedLeagueFieldValue::tier(&LeagueTier::Gold), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed to retrieve the leagues filtered by enums");
+    assert_eq!(in_progress, vec![gold_league.clone()]);
+
+    let status: String = canyon_sql::query_scalar(
+        "SELECT CAST(status AS TEXT) FROM ranked_league WHERE id = $1",
+        [&gold_league.id as &dyn canyon_sql::crud::bounds::QueryParameter<'_>],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed to retrieve the stored status");
+    assert_eq!(status, "in_progress");
+    let tier: i32 = canyon_sql::query_scalar(
+        "SELECT tier FROM ranked_league WHERE id = $1",
+        [&gold_league.id as &dyn canyon_sql::crud::bounds::QueryParameter<'_>],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed to retrieve the stored tier");
+    assert_eq!(tier, 10);
+
+    assert_eq!(
+        RankedLeague::find_by_pk(&unranked_league.id)
+            .await
+            .expect("Failed the query to the database"),
+        Some(unranked_league)
+    );
+}
+
+/// Checks that the soft deleted rows are excluded by default, and that
+/// they can be included again, or retrieved alone, when requested
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_soft_delete_clauses() {
+    let mut l = SelectQueryBuilder::<League>::new_with_soft_delete("league", "deleted_at", "");
+    l.r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .after(LeagueField::id, &10)
+        .limit(5);
+    assert_eq!(
+        l.read_sql(),
+        "SELECT * FROM (SELECT * FROM league WHERE deleted_at IS NULL) AS league WHERE region = $1 AND id > $2 ORDER BY id LIMIT 5"
+    );
+
+    let mut only_deleted =
+        SelectQueryBuilder::<League>::new_with_soft_delete("league", "deleted_at", "");
+    only_deleted
+        .limit(5)
+        .only_deleted()
+        .r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq);
+    assert_eq!(
+        only_deleted.read_sql(),
+        "SELECT * FROM (SELECT * FROM league WHERE deleted_at IS NOT NULL) AS league WHERE region = $1 LIMIT 5"
+    );
+
+    let mut with_deleted =
+        SelectQueryBuilder::<League>::new_with_soft_delete("league", "deleted_at", "");
+    with_deleted
+        .r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .with_deleted();
+    assert_eq!(
+        with_deleted.read_sql(),
+        "SELECT * FROM league WHERE region = $1"
+    );
+}
+
+/// The column of the `#[updated_at]` field is refreshed by the `SET` clause
+/// of the update builder, even when it isn't set explicitly
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_update_querybuilder_refreshes_updated_at_column() {
+    let mut q = AuditedLeague::update_query();
+    q.set(&[(AuditedLeagueField::name, "Renamed")])
+        .r#where(AuditedLeagueFieldValue::id(&1), Comp::Eq);
+
+    assert_eq!(
+        q.read_sql(),
+        "UPDATE audited_league SET name = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2"
+    );
+}
+
+/// The fields of an `#[embedded]` value object are available on the query builders
+/// as the prefixed columns where they're stored
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_querybuilder_over_embedded_fields() {
+    let mut q = Sponsor::select_query();
+    q.r#where(SponsorFieldValue::billing_city(&"Madrid"), Comp::Eq)
+        .and_values_in(SponsorField::billing_street, &["Gran Via, 1"]);
+
+    assert_eq!(
+        q.read_sql(),
+        "SELECT * FROM sponsor WHERE billing_city = $1 AND billing_street IN ($2)"
+    );
+}
+
+/// Tests the deserialization of the rows with `serde`, into read models that doesn't
+/// derive `CanyonMapper`, honoring the renamed and the flattened fields, and mapping
+/// the `NULL` values into `None`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_querybuilder_deserialize_with_serde() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Location {
+        region: String,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct LeagueView {
+        id: i32,
+        #[serde(rename = "slug")]
+        code: String,
+        #[serde(flatten)]
+        location: Location,
+        logo: Option<String>,
+    }
+
+    let expected = LeagueView {
+        id: 8,
+        code: "pg_nationals".to_string(),
+        location: Location {
+            region: "EUROPE".to_string(),
+        },
+        logo: None,
+    };
+
+    let mut q = League::select_query();
+    q.r#where(LeagueFieldValue::id(&8), Comp::Eq);
+    let leagues = q
+        .deserialize::<LeagueView>()
+        .await
+        .expect("Failed to deserialize the leagues");
+    assert_eq!(leagues, vec![expected]);
+
+    let rows = canyon_sql::query_raw(
+        "SELECT id, slug, region, NULL::TEXT AS logo FROM league WHERE id = 8",
+        [],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(
+        rows[0]
+            .deserialize::<(i32, String, String, Option<String>)>()
+            .unwrap(),
+        (8, "pg_nationals".to_string(), "EUROPE".to_string(), None)
+    );
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct WrongView {
+        id: String,
+    }
+    assert!(rows[0].deserialize::<WrongView>().is_err());
+}
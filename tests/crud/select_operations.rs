@@ -2,6 +2,8 @@
 
 #[cfg(feature = "mysql")]
 use crate::constants::MYSQL_DS;
+#[cfg(feature = "postgres")]
+use crate::constants::PSQL_DS;
 
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
@@ -175,3 +177,196 @@ fn test_crud_count_datasource_operation_mysql() {
         League::count_datasource(MYSQL_DS).await.unwrap()
     );
 }
+
+/// Every field of an entity has its own generated finders, that filters
+/// the rows by the value of that concrete column
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_field_operations() {
+    let european_leagues: Vec<League> = League::find_by_region("EUROPE")
+        .await
+        .expect("Failed the query to the database");
+    assert!(!european_leagues.is_empty());
+    assert!(european_leagues
+        .iter()
+        .all(|league| league.region == "EUROPE"));
+    assert_eq!(
+        League::count_by_region("EUROPE")
+            .await
+            .expect("Failed the query to the database"),
+        european_leagues.len() as i64
+    );
+    // Only the first one of the european leagues is retrieved
+    let european_league = League::find_one_by_region("EUROPE")
+        .await
+        .expect("Failed the query to the database")
+        .expect("There are several european leagues");
+    assert!(european_leagues.contains(&european_league));
+
+    // No other test modifies the league with ID 9, so it can be safely found by its columns
+    let nlc: Option<League> = League::find_one_by_slug("nlc")
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(nlc.map(|league| league.id), Some(9));
+
+    assert!(League::exists_by_ext_id(&105266098308571975)
+        .await
+        .expect("Failed the query to the database"));
+    assert!(!League::exists_by_slug("non-existent-league")
+        .await
+        .expect("Failed the query to the database"));
+}
+
+/// The finder of a field annotated with `#[unique]` returns an `Option`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_unique_field_operation() {
+    let ruler: Option<Player> = Player::find_by_summoner_name("Ruler")
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(
+        ruler.map(|player| player.first_name),
+        Some("Jaehyeok".to_string())
+    );
+
+    assert_eq!(
+        Player::find_by_summoner_name_datasource("Nobody", PSQL_DS)
+            .await
+            .expect("Failed the query to the database"),
+        None
+    );
+}
+
+/// Tests the `find_by_pks` and `exists` operations, generated for the entities
+/// with a primary key, that returns the found entities in the requested order,
+/// along with the primary keys that weren't found
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_pks_operation() {
+    let leagues = League::find_by_pks(&[2, 999_999, 1, 2])
+        .await
+        .expect("Failed the query to the database");
+
+    assert_eq!(
+        leagues
+            .found
+            .iter()
+            .map(|league| league.id)
+            .collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+    assert_eq!(leagues.missing, vec![999_999]);
+    assert!(!leagues.is_complete());
+
+    assert!(League::exists_datasource(&1, PSQL_DS)
+        .await
+        .expect("Failed the query to the database"));
+    assert!(!League::exists(&999_999)
+        .await
+        .expect("Failed the query to the database"));
+}
+
+/// Tests the `query_raw` function, that runs ad-hoc SQL returning untyped rows,
+/// whose values are retrieved by the name or the position of their columns
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_raw_dynamic_rows() {
+    use canyon_sql::crud::bounds::{DataType, QueryParameter};
+
+    let rows = canyon_sql::query_raw(
+        "SELECT id, ext_id, name FROM league WHERE id = $1",
+        [&9 as &dyn QueryParameter<'_>],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+
+    assert_eq!(row.get::<i32, _>("id"), 9);
+    assert_eq!(row.get::<i64, _>(1), 105266098308571975);
+    assert_eq!(row.get::<String, _>("name"), "NLC");
+
+    assert_eq!(
+        row.columns()
+            .iter()
+            .map(|column| (column.name().to_owned(), column.data_type().clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("id".to_owned(), DataType::Int),
+            ("ext_id".to_owned(), DataType::BigInt),
+            ("name".to_owned(), DataType::Text),
+        ]
+    );
+
+    let missing = row.try_get::<i32, _>("slug").unwrap_err();
+    assert_eq!(missing.column, "slug");
+    assert!(missing.actual.is_none());
+    let mismatch = row.try_get::<i32, _>("name").unwrap_err();
+    assert_eq!(mismatch.actual.as_deref(), Some("text"));
+}
+
+/// Tests the conversion of the untyped rows returned by `query_raw` into JSON objects
+#[cfg(all(feature = "postgres", feature = "json"))]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_raw_dynamic_rows_to_json() {
+    let rows = canyon_sql::query_raw(
+        "SELECT id, region, NULL::TEXT AS nothing FROM league WHERE id = 1",
+        [],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+
+    let json = rows[0]
+        .to_json()
+        .expect("Failed to convert the row into JSON");
+    assert_eq!(json["id"], 1);
+    assert_eq!(json["region"], "EUROPE");
+    assert!(json["nothing"].is_null());
+}
+
+/// Tests the `query_as` and `query_scalar` functions, that maps the results of
+/// ad-hoc SQL into tuples of supported types and into single values
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_as_tuples_and_scalars() {
+    let leagues: Vec<(i32, String)> = canyon_sql::query_as(
+        "SELECT id, name FROM league WHERE id IN (1, 8) ORDER BY id",
+        [],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(
+        leagues,
+        vec![
+            (1, "European Masters".to_owned()),
+            (8, "PG Nationals".to_owned())
+        ]
+    );
+
+    let count: i64 = canyon_sql::query_scalar(
+        "SELECT COUNT(*) FROM league WHERE id IN (1, 8)",
+        [],
+        PSQL_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(count, 2);
+
+    let max_id: Option<i32> =
+        canyon_sql::query_scalar("SELECT max(id) FROM league WHERE id < 0", [], PSQL_DS)
+            .await
+            .expect("Failed the query to the database");
+    assert_eq!(max_id, None);
+
+    assert!(canyon_sql::query_scalar::<i64, _, _>(
+        "SELECT id FROM league WHERE id < 0",
+        [],
+        PSQL_DS
+    )
+    .await
+    .is_err());
+}
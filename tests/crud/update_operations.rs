@@ -1,7 +1,12 @@
+use crate::tests_models::audited_league::*;
+use crate::tests_models::hooked_league::*;
 use crate::tests_models::league::*;
+use crate::tests_models::sponsor::*;
+use crate::tests_models::versioned_league::*;
 // Integration tests for the CRUD operations available in `Canyon` that
 /// generates and executes *UPDATE* statements
-use canyon_sql::crud::CrudOperations;
+use canyon_sql::crud::{errors::StaleObjectError, CrudOperations, Tracked};
+use canyon_sql::date_time::{DateTime, NaiveDateTime, Utc};
 
 #[cfg(feature = "mysql")]
 use crate::constants::MYSQL_DS;
@@ -36,10 +41,11 @@ fn test_crud_update_method_operation() {
     // Modify the value, and perform the update
     let updt_value: i64 = 593064_i64;
     updt_candidate.ext_id = updt_value;
-    updt_candidate
+    let affected_rows = updt_candidate
         .update()
         .await
         .expect("Failed the update operation");
+    assert_eq!(affected_rows, 1);
 
     // Retrieve it again, and check if the value was really updated
     let updt_entity: League = League::find_by_pk(&1)
@@ -140,3 +146,222 @@ fn test_crud_update_datasource_mysql_method_operation() {
         .await
         .expect("Failed to restablish the initial value update operation");
 }
+
+/// When the entity has a `#[version]` field, the update only succeeds if the row
+/// still holds the version of the instance. Updating an instance whose row was
+/// modified by someone else since it was retrieved must fail with a
+/// [`StaleObjectError`], without overwriting the changes of the other party
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_with_version_operation() {
+    let mut new_league = VersionedLeague {
+        id: Default::default(),
+        name: "Versioned league".to_string(),
+        version: 0,
+    };
+    new_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    let mut first_copy = new_league.clone();
+    let mut second_copy = new_league.clone();
+
+    first_copy.name = "Updated by the first copy".to_string();
+    first_copy
+        .update_refreshing()
+        .await
+        .expect("Failed the update operation");
+    assert_eq!(first_copy.version, 1);
+
+    // The refreshed instance can be updated again
+    first_copy.name = "Updated twice by the first copy".to_string();
+    first_copy
+        .update_refreshing()
+        .await
+        .expect("Failed the update operation");
+    assert_eq!(first_copy.version, 2);
+
+    second_copy.name = "Updated by the second copy".to_string();
+    let stale_update = second_copy.update().await;
+    assert!(stale_update
+        .expect_err("The update over an stale instance must fail")
+        .downcast_ref::<StaleObjectError>()
+        .is_some());
+
+    let persisted: VersionedLeague = VersionedLeague::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(persisted, first_copy);
+}
+
+/// An error returned by the `after_update` hook of the entity rolls back the update,
+/// so the row keeps its previous values
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_rolled_back_by_hook_operation() {
+    let mut league = HookedLeague {
+        id: Default::default(),
+        slug: "updated-league".to_string(),
+        name: "Rolled Back League".to_string(),
+    };
+    league.insert().await.expect("Failed the insert operation");
+
+    league.slug = "rolled-back".to_string();
+    assert!(league.update().await.is_err());
+
+    let persisted: HookedLeague = HookedLeague::find_by_pk(&league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(persisted.slug, "UPDATED-LEAGUE");
+}
+
+/// The fields annotated with `#[created_at]` and `#[updated_at]` are filled with the
+/// current time when the entity is inserted, and only the `#[updated_at]` one is
+/// refreshed on every update
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_and_update_with_audit_timestamps_operation() {
+    let mut new_league = AuditedLeague {
+        id: Default::default(),
+        name: "Audited league".to_string(),
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    };
+    new_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    assert_ne!(new_league.created_at, NaiveDateTime::default());
+    assert_ne!(new_league.updated_at, DateTime::<Utc>::default());
+    let (created_at, inserted_at) = (new_league.created_at, new_league.updated_at);
+
+    new_league.name = "Audited league after the update".to_string();
+    new_league
+        .update()
+        .await
+        .expect("Failed the update operation");
+
+    // The `update` operation doesn't modify the instance
+    assert_eq!(new_league.updated_at, inserted_at);
+    let persisted: AuditedLeague = AuditedLeague::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(persisted.name, new_league.name);
+    assert!(persisted.updated_at >= inserted_at);
+
+    new_league
+        .update_refreshing()
+        .await
+        .expect("Failed the update operation");
+
+    assert_eq!(new_league.created_at, created_at);
+    assert!(new_league.updated_at >= persisted.updated_at);
+
+    let persisted: AuditedLeague = AuditedLeague::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(persisted.updated_at, new_league.updated_at);
+}
+
+/// A [`Tracked`] entity only writes the columns that were modified since it was
+/// retrieved, so the concurrent changes made over other columns are kept, and
+/// nothing is sent to the database if there's no modification at all
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_only_changed_columns_operation() {
+    let mut new_league = League {
+        id: Default::default(),
+        ext_id: 7892635306594_i64,
+        slug: "tracked-league".to_string(),
+        name: "Tracked League".to_string(),
+        region: "EU".to_string(),
+        image_url: "https://tracked.league".to_string(),
+    };
+    new_league
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    let mut tracked = Tracked::new(
+        League::find_by_pk(&new_league.id)
+            .await
+            .expect("Failed the query to the database")
+            .expect("No entity found for the primary key value passed in"),
+    );
+    assert!(!tracked.is_dirty());
+
+    // Someone else modifies another column in the meantime
+    new_league.region = "NA".to_string();
+    new_league
+        .update()
+        .await
+        .expect("Failed the update operation");
+
+    tracked.name = "Renamed Tracked League".to_string();
+    assert_eq!(tracked.changed_columns(), vec!["name"]);
+    assert_eq!(
+        tracked.update().await.expect("Failed the update operation"),
+        1
+    );
+    assert!(!tracked.is_dirty());
+    // Nothing changed, so nothing is sent to the database
+    assert_eq!(
+        tracked.update().await.expect("Failed the update operation"),
+        0
+    );
+
+    let persisted: League = League::find_by_pk(&new_league.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(persisted.name, "Renamed Tracked League");
+    assert_eq!(persisted.region, "NA");
+}
+
+/// The fields of an `#[embedded]` value object are written to and read from their
+/// own prefixed columns, and a [`Tracked`] entity writes all of them when the value
+/// object changes
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_and_update_with_embedded_fields_operation() {
+    let mut new_sponsor = Sponsor {
+        id: Default::default(),
+        name: "Embedded Sponsor".to_string(),
+        billing: Address {
+            street: "Gran Via, 1".to_string(),
+            city: "Madrid".to_string(),
+        },
+    };
+    new_sponsor
+        .insert()
+        .await
+        .expect("Failed the insert operation");
+
+    let mut tracked = Tracked::new(
+        Sponsor::find_by_pk(&new_sponsor.id)
+            .await
+            .expect("Failed the query to the database")
+            .expect("No entity found for the primary key value passed in"),
+    );
+    assert_eq!(*tracked, new_sponsor);
+
+    tracked.billing.city = "Barcelona".to_string();
+    assert_eq!(
+        tracked.changed_columns(),
+        vec!["billing_street", "billing_city"]
+    );
+    tracked.update().await.expect("Failed the update operation");
+
+    let persisted: Sponsor = Sponsor::find_by_pk(&new_sponsor.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(persisted.billing.street, "Gran Via, 1");
+    assert_eq!(persisted.billing.city, "Barcelona");
+}
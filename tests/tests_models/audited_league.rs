@@ -0,0 +1,14 @@
+use canyon_sql::date_time::{DateTime, NaiveDateTime, Utc};
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "audited_league")]
+pub struct AuditedLeague {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[created_at]
+    created_at: NaiveDateTime,
+    #[updated_at(clock = "database")]
+    updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,13 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "defaulted_league")]
+pub struct DefaultedLeague {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[db_default]
+    region: String,
+    #[db_default]
+    score: i32,
+}
@@ -0,0 +1,72 @@
+use canyon_sql::crud::CanyonHooks;
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_crud(hooks)]
+#[canyon_entity(table_name = "hooked_league")]
+pub struct HookedLeague {
+    #[primary_key]
+    id: i32,
+    slug: String,
+    name: String,
+}
+
+/// The slugs are stored in lowercase, but they are shown in uppercase,
+/// and the leagues without name can't be written. The `rolled-back` ones
+/// are rejected once they're written, so the changes are rolled back
+#[async_trait]
+impl CanyonHooks for HookedLeague {
+    async fn before_insert(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.normalize()
+    }
+
+    async fn after_insert(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.check_not_rolled_back()
+    }
+
+    async fn before_update(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.check_name()
+    }
+
+    async fn after_update(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.check_not_rolled_back()
+    }
+
+    async fn before_delete(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.slug.eq_ignore_ascii_case("protected") {
+            return Err("The protected leagues can't be deleted".into());
+        }
+        Ok(())
+    }
+
+    async fn after_delete(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.check_not_rolled_back()
+    }
+
+    async fn after_load(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.slug = self.slug.to_uppercase();
+        Ok(())
+    }
+}
+
+impl HookedLeague {
+    fn normalize(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.check_name()?;
+        self.slug = self.slug.trim().to_lowercase();
+        Ok(())
+    }
+
+    fn check_name(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.name.trim().is_empty() {
+            return Err("A league must have a name".into());
+        }
+        Ok(())
+    }
+
+    fn check_not_rolled_back(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.slug.eq_ignore_ascii_case("rolled-back") {
+            return Err("The rolled back leagues can't be written".into());
+        }
+        Ok(())
+    }
+}
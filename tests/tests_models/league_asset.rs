@@ -0,0 +1,13 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, PartialEq)]
+#[canyon_entity(table_name = "league_asset")]
+pub struct LeagueAsset {
+    #[primary_key]
+    id: i32,
+    code: uuid::Uuid,
+    price: rust_decimal::Decimal,
+    discount: Option<rust_decimal::Decimal>,
+    metadata: serde_json::Value,
+    logo: Vec<u8>,
+}
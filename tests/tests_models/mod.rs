@@ -1,3 +1,17 @@
+pub mod audited_league;
+pub mod defaulted_league;
+pub mod hooked_league;
 pub mod league;
+#[cfg(all(feature = "uuid", feature = "decimal", feature = "json"))]
+pub mod league_asset;
 pub mod player;
+pub mod ranked_league;
+pub mod related_league;
+pub mod soft_deleted_league;
+pub mod soft_deleted_tournament;
+pub mod sponsor;
+pub mod tag;
 pub mod tournament;
+pub mod typed_league;
+pub mod validated_league;
+pub mod versioned_league;
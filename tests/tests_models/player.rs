@@ -18,6 +18,7 @@ pub struct Player {
     ext_id: i64,
     first_name: String,
     last_name: String,
+    #[unique]
     summoner_name: String,
     image_url: Option<String>,
     role: String,
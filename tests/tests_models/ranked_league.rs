@@ -0,0 +1,29 @@
+use canyon_sql::macros::*;
+
+/// The status of a `RankedLeague`, stored on a column of a native `ENUM` type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanyonEnum)]
+#[canyon_enum(repr = "pg_enum", type_name = "league_status")]
+pub enum LeagueStatus {
+    Scheduled,
+    InProgress,
+    Closed,
+}
+
+/// The tier of a `RankedLeague`, stored as the discriminants of its variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanyonEnum)]
+#[canyon_enum(repr = "int")]
+pub enum LeagueTier {
+    Bronze = 1,
+    Silver,
+    Gold = 10,
+}
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "ranked_league")]
+pub struct RankedLeague {
+    #[primary_key]
+    id: i32,
+    name: String,
+    status: LeagueStatus,
+    tier: Option<LeagueTier>,
+}
@@ -0,0 +1,20 @@
+use crate::tests_models::{tag::Tag, tournament::Tournament};
+use canyon_sql::macros::*;
+
+/// The leagues along with their tournaments, and the tags related with them
+/// through the `league_tag` join table
+#[derive(Debug, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "league")]
+pub struct RelatedLeague {
+    #[primary_key]
+    id: i32,
+    ext_id: i64,
+    slug: String,
+    name: String,
+    region: String,
+    image_url: String,
+    #[has_many(Tournament, fk = "league")]
+    tournaments: Vec<Tournament>,
+    #[many_to_many(Tag, through = "league_tag")]
+    tags: Vec<Tag>,
+}
@@ -0,0 +1,12 @@
+use canyon_sql::date_time::{DateTime, Utc};
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, ForeignKeyable, Eq, PartialEq)]
+#[canyon_entity(table_name = "soft_deleted_league")]
+pub struct SoftDeletedLeague {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[soft_delete]
+    deleted_at: Option<DateTime<Utc>>,
+}
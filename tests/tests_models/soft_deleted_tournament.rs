@@ -0,0 +1,15 @@
+use crate::tests_models::soft_deleted_league::SoftDeletedLeague;
+use canyon_sql::date_time::NaiveDateTime;
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "soft_deleted_tournament")]
+pub struct SoftDeletedTournament {
+    #[primary_key]
+    id: i32,
+    slug: String,
+    #[foreign_key(table = "soft_deleted_league", column = "id")]
+    league: i32,
+    #[soft_delete]
+    deleted_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,18 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Eq, PartialEq, CanyonEmbeddable)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+}
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_crud(tracked)]
+#[canyon_entity(table_name = "sponsor")]
+pub struct Sponsor {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[embedded(prefix = "billing_")]
+    billing: Address,
+}
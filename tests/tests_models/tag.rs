@@ -0,0 +1,9 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+pub struct Tag {
+    #[primary_key]
+    id: i32,
+    name: String,
+}
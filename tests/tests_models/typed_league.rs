@@ -0,0 +1,20 @@
+use canyon_sql::macros::*;
+
+/// Newtype over the slug of a `TypedLeague`
+#[derive(Debug, Clone, PartialEq, Eq, CanyonType)]
+pub struct LeagueSlug(pub String);
+
+/// Newtype over the score of a `TypedLeague`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanyonType)]
+pub struct LeagueScore {
+    pub value: i64,
+}
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "typed_league")]
+pub struct TypedLeague {
+    #[primary_key]
+    id: i32,
+    slug: LeagueSlug,
+    score: Option<LeagueScore>,
+}
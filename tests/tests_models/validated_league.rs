@@ -0,0 +1,25 @@
+use canyon_sql::macros::*;
+
+/// The same `league` table, but validating the data before writing it
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "league")]
+pub struct ValidatedLeague {
+    #[primary_key]
+    id: i32,
+    #[validate(range(min = 0))]
+    ext_id: i64,
+    #[validate(length(min = 2, max = 40), regex = "^[a-z0-9-]+$")]
+    slug: String,
+    #[validate(length(min = 1))]
+    name: String,
+    #[validate(custom = "known_region")]
+    region: String,
+    image_url: String,
+}
+
+fn known_region(region: &str) -> Result<(), String> {
+    match region {
+        "EU" | "NA" | "Korea" | "China" => Ok(()),
+        _ => Err(format!("`{region}` isn't a known region")),
+    }
+}
@@ -0,0 +1,11 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity(table_name = "versioned_league")]
+pub struct VersionedLeague {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[version]
+    version: i32,
+}